@@ -1,10 +1,10 @@
-use winapi::shared::minwindef::*;
+#[cfg(windows)] use winapi::shared::minwindef::*;
 
-use std::io;
+#[cfg(windows)] use std::io;
 
 
 
-pub(crate) fn succeeded_to_result(succeeded: BOOL) -> io::Result<()> {
+#[cfg(windows)] pub(crate) fn succeeded_to_result(succeeded: BOOL) -> io::Result<()> {
     match succeeded {
         0 => Err(io::Error::last_os_error()),
         _ => Ok(()),
@@ -15,7 +15,7 @@ pub(crate) const fn size_of_32<T>() -> u32 {
     SizeOf32Impl::<T>::SIZE
 }
 
-pub(crate) const fn size_of_val_32_sized<T>(_: &T) -> u32 {
+#[cfg(windows)] pub(crate) const fn size_of_val_32_sized<T>(_: &T) -> u32 {
     SizeOf32Impl::<T>::SIZE
 }
 
@@ -1,23 +1,37 @@
-#![doc = include_str!("../Readme.md")]
-#![cfg(windows)]
+#![cfg_attr(not(doctest), doc = include_str!("../Readme.md"))]
 #![allow(unused_unsafe)]
+#![allow(clippy::identity_op)] // `x << 0` etc. used for alignment
 
 
 
 #[doc = include_str!("../doc/console-function-mapping.md")]
-#[cfg(doc)] pub mod _console_function_mapping {}
+#[cfg(all(doc, windows))] pub mod _console_function_mapping {}
 
-#[path = r"values\_values.rs"]                      mod values;                 pub use values::*;
+#[doc = include_str!("../Readme.md")]
+#[cfg(all(doctest, windows))] struct _ReadmeDoctests;
+
+#[path = "codepage/_codepage.rs"]                   mod codepage;               pub use codepage::*;
+#[path = "color_scheme/_color_scheme.rs"]           mod color_scheme;           pub use color_scheme::*;
+#[path = "export/_export.rs"]                       mod export;
+#[path = "values/_values.rs"]                       mod values;                 pub use values::*;
+#[path = "vt/_vt.rs"]                               mod vt;                     pub use vt::*;
 
 mod _impl;      pub(crate) use _impl::*;
-mod _traits;    pub use _traits::*;
-mod alias;      pub use alias::*;
-mod alloc;      pub use alloc::*;
+#[cfg(test)] mod _test; #[cfg(test)] pub(crate) use _test::*;
+mod backend;    pub use backend::*;
+mod char_info_grid; pub use char_info_grid::*;
 mod charattrib; pub use charattrib::*;
-mod codepage;   pub use codepage::*;
-mod font;       pub use font::*;
-mod handles;    pub use handles::*;
-mod io;         pub use io::*;
+mod frame_renderer;       pub use frame_renderer::*;
+mod memory_console;       pub use memory_console::*;
+mod memory_screen_buffer; pub use memory_screen_buffer::*;
 mod mode;       pub use mode::*;
-mod text;       pub use text::*;
-mod window;     pub use window::*;
+mod scroll;     pub use scroll::*;
+
+#[cfg(windows)] mod _traits;    #[cfg(windows)] pub use _traits::*;
+#[cfg(windows)] mod alias;      #[cfg(windows)] pub use alias::*;
+#[cfg(windows)] mod alloc;      #[cfg(windows)] pub use alloc::*;
+#[cfg(windows)] mod font;       #[cfg(windows)] pub use font::*;
+#[cfg(windows)] mod handles;    #[cfg(windows)] pub use handles::*;
+#[cfg(windows)] mod io;         #[cfg(windows)] pub use io::*;
+#[cfg(windows)] mod text;       #[cfg(windows)] pub use text::*;
+#[cfg(windows)] mod window;     #[cfg(windows)] pub use window::*;
@@ -0,0 +1,17 @@
+//! Fixtures shared by the crate's `#[test]`s.
+
+use crate::*;
+
+
+
+/// A cell of ASCII `ch` with default attributes.
+pub(crate) fn cell(ch: u8) -> CharInfo { CharInfo::new(ch.into(), Attributes::default()) }
+
+/// A grid of `size` whose cells are the ASCII `text` (row-major) with default attributes.
+pub(crate) fn grid_from_text(size: impl Into<Coord>, text: &[u8]) -> CharInfoGrid { CharInfoGrid::from_cells(size, text.iter().map(|&ch| cell(ch)).collect()).unwrap() }
+
+/// The (ASCII) characters of `cells`, e.g. to compare with the `text` of [`grid_from_text`].
+pub(crate) fn grid_text(cells: &[CharInfo]) -> String { cells.iter().map(|c| c.char.unicode_char() as u8 as char).collect() }
+
+/// A color table whose entry `i` is `#ii0000`, so the color index used is visible in the output.
+pub(crate) fn test_color_table() -> [ColorRef; 16] { core::array::from_fn(|i| ColorRef::from_rgb([i as u8, 0, 0])) }
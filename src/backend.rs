@@ -0,0 +1,322 @@
+use crate::*;
+
+use std::ffi::{OsStr, OsString};
+use std::io;
+
+
+
+/// \[~~microsoft.com~~\]
+/// The console operations of `io.rs`, `mode.rs`, `window.rs`, and `font.rs`,
+/// bundled together with the input and output handles they operate on.
+///
+/// Implemented by:
+/// *   [`Win32Console`] - forwards to the real Win32 console (windows only)
+/// *   [`MemoryConsole`] - a pure-Rust emulation, usable off Windows and in tests
+///
+/// Generic application code can be written against `impl ConsoleBackend` or `&mut dyn ConsoleBackend`.
+///
+/// Not (yet) abstracted, as they expose raw `winapi` types or have no reasonable emulation:
+/// `get_console_history_info`, `get_console_selection_info`, `get_console_window`, `set_console_active_screen_buffer`, `get_console_font_size`.
+///
+/// ### Example
+/// ```
+/// # use maulingmonkey_console_winapi_wrappers::*;
+/// # use std::io;
+/// fn draw(console: &mut impl ConsoleBackend) -> io::Result<()> {
+///     console.set_console_text_attribute(FOREGROUND_GREEN)?;
+///     console.set_console_cursor_position(Coord::new(2, 1))?;
+///     console.write_console(&"Hello!".encode_utf16().collect::<Vec<_>>())?;
+///     Ok(())
+/// }
+///
+/// let mut console = MemoryConsole::new((80, 25));
+/// draw(&mut console).unwrap();
+/// let cell = console.screen_buffer().cell((2, 1)).unwrap();
+/// assert_eq!(cell.char.unicode_char(), b'H' as u16);
+/// assert_eq!(cell.attributes, FOREGROUND_GREEN);
+/// ```
+///
+pub trait ConsoleBackend {
+    #[doc(alias = "FillConsoleOutputAttribute")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/fillconsoleoutputattribute)\]
+    /// Sets the attributes of `length` cells, starting at `write_coord`.
+    fn fill_console_output_attribute(&mut self, attribute: Attributes, length: u32, write_coord: Coord) -> io::Result<u32>;
+
+    #[doc(alias = "FillConsoleOutputCharacter")]
+    #[doc(alias = "FillConsoleOutputCharacterW")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/fillconsoleoutputcharacter)\]
+    /// Writes `character` to `length` cells, starting at `write_coord`.
+    fn fill_console_output_character(&mut self, character: char, length: u32, write_coord: Coord) -> io::Result<u32>;
+
+    #[doc(alias = "FlushConsoleInputBuffer")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/flushconsoleinputbuffer)\]
+    /// Discards all unread input records.
+    fn flush_console_input_buffer(&mut self) -> io::Result<()>;
+
+    #[doc(alias = "GetConsoleCursorInfo")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/getconsolecursorinfo)\]
+    /// Retrieves the size and visibility of the cursor.
+    fn get_console_cursor_info(&self) -> io::Result<ConsoleCursorInfo>;
+
+    #[doc(alias = "GetConsoleDisplayMode")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/getconsoledisplaymode)\]
+    /// Retrieves the display mode (e.g. `CONSOLE_FULLSCREEN`) of the console.
+    fn get_console_display_mode(&self) -> io::Result<u32>;
+
+    #[doc(alias = "GetConsoleMode")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/getconsolemode)\]
+    /// Retrieves the [`InputMode`] of the input buffer.
+    fn get_console_input_mode(&self) -> io::Result<InputMode>;
+
+    #[doc(alias = "GetConsoleMode")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/getconsolemode)\]
+    /// Retrieves the [`OutputMode`] of the screen buffer.
+    fn get_console_output_mode(&self) -> io::Result<OutputMode>;
+
+    #[doc(alias = "GetConsoleOriginalTitle")]
+    #[doc(alias = "GetConsoleOriginalTitleW")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/getconsoleoriginaltitle)\]
+    /// Retrieves the original title of the console window.
+    fn get_console_original_title(&self) -> io::Result<OsString>;
+
+    #[doc(alias = "GetConsoleScreenBufferInfo")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/getconsolescreenbufferinfo)\]
+    /// Retrieves information about the screen buffer.
+    fn get_console_screen_buffer_info(&self) -> io::Result<ConsoleScreenBufferInfo>;
+
+    #[doc(alias = "GetConsoleScreenBufferInfoEx")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/getconsolescreenbufferinfoex)\]
+    /// Retrieves extended information about the screen buffer.
+    fn get_console_screen_buffer_info_ex(&self) -> io::Result<ConsoleScreenBufferInfoEx>;
+
+    #[doc(alias = "GetConsoleTitle")]
+    #[doc(alias = "GetConsoleTitleW")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/getconsoletitle)\]
+    /// Retrieves the title of the console window.
+    fn get_console_title(&self) -> io::Result<OsString>;
+
+    #[doc(alias = "GetCurrentConsoleFont")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/getcurrentconsolefont)\]
+    /// Retrieves information about the current console font.
+    fn get_current_console_font(&self, maximum_window: bool) -> io::Result<ConsoleFontInfo>;
+
+    #[doc(alias = "GetCurrentConsoleFontEx")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/getcurrentconsolefontex)\]
+    /// Retrieves extended information about the current console font.
+    fn get_current_console_font_ex(&self, maximum_window: bool) -> io::Result<ConsoleFontInfoEx>;
+
+    #[doc(alias = "GetLargestConsoleWindowSize")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/getlargestconsolewindowsize)\]
+    /// Retrieves the size of the largest possible console window.
+    fn get_largest_console_window_size(&self) -> io::Result<Coord>;
+
+    #[doc(alias = "GetNumberOfConsoleInputEvents")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/getnumberofconsoleinputevents)\]
+    /// Retrieves the number of unread input records.
+    fn get_number_of_console_input_events(&self) -> io::Result<usize>;
+
+    #[doc(alias = "GetNumberOfConsoleMouseButtons")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/getnumberofconsolemousebuttons)\]
+    /// Retrieves the number of buttons on the mouse used by the console.
+    fn get_number_of_console_mouse_buttons(&self) -> io::Result<usize>;
+
+    #[doc(alias = "PeekConsoleInput")]
+    #[doc(alias = "PeekConsoleInputW")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/peekconsoleinput)\]
+    /// Reads, without removing, the oldest input records.
+    fn peek_console_input_with<'i>(&self, buffer: &'i mut [InputRecord]) -> io::Result<&'i [InputRecord]>;
+
+    #[doc(alias = "ReadConsole")]
+    #[doc(alias = "ReadConsoleW")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/readconsole)\]
+    /// Reads and removes character input.
+    fn read_console<'i>(&mut self, buffer: &'i mut [u16]) -> io::Result<&'i [u16]>;
+
+    #[doc(alias = "ReadConsoleInput")]
+    #[doc(alias = "ReadConsoleInputW")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/readconsoleinput)\]
+    /// Reads and removes the oldest input records.
+    fn read_console_input_with<'i>(&mut self, buffer: &'i mut [InputRecord]) -> io::Result<&'i [InputRecord]>;
+
+    #[doc(alias = "ReadConsoleOutput")]
+    #[doc(alias = "ReadConsoleOutputW")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/readconsoleoutput)\]
+    /// Reads the inclusive `read_region` of the screen buffer into `buffer`, updating `read_region` to the region actually read.
+    fn read_console_output(&self, buffer: &mut [CharInfo], buffer_size: Coord, buffer_coord: Coord, read_region: &mut InclusiveRect) -> io::Result<()>;
+
+    #[doc(alias = "ReadConsoleOutput")]
+    #[doc(alias = "ReadConsoleOutputW")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/readconsoleoutput)\]
+    /// [`read_console_output`](Self::read_console_output) into `grid`, with its top left corner at `grid_coord`.
+    fn read_console_output_grid(&self, grid: &mut CharInfoGrid, grid_coord: Coord, read_region: &mut InclusiveRect) -> io::Result<()> {
+        let size = grid.size();
+        self.read_console_output(grid.cells_mut(), size, grid_coord, read_region)
+    }
+
+    #[doc(alias = "ReadConsoleOutputAttribute")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/readconsoleoutputattribute)\]
+    /// Reads a line of [`Attributes`], starting at `read_coord`.
+    fn read_console_output_attribute<'a>(&self, attributes: &'a mut [Attributes], read_coord: Coord) -> io::Result<&'a [Attributes]>;
+
+    #[doc(alias = "ReadConsoleOutputCharacter")]
+    #[doc(alias = "ReadConsoleOutputCharacterW")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/readconsoleoutputcharacter)\]
+    /// Reads a line of characters, starting at `read_coord`.
+    fn read_console_output_character<'a>(&self, characters: &'a mut [u16], read_coord: Coord) -> io::Result<&'a [u16]>;
+
+    #[doc(alias = "ScrollConsoleScreenBuffer")]
+    #[doc(alias = "ScrollConsoleScreenBufferW")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/scrollconsolescreenbuffer)\]
+    /// Moves the inclusive `scroll_rect` so its top left corner lands on `destination`, filling vacated cells with `fill`.
+    fn scroll_console_screen_buffer(&mut self, scroll_rect: InclusiveRect, clip_rect: Option<InclusiveRect>, destination: Coord, fill: CharInfo) -> io::Result<()>;
+
+    #[doc(alias = "SetConsoleCursorInfo")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/setconsolecursorinfo)\]
+    /// Sets the size and visibility of the cursor.
+    fn set_console_cursor_info(&mut self, info: ConsoleCursorInfo) -> io::Result<()>;
+
+    #[doc(alias = "SetConsoleCursorPosition")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/setconsolecursorposition)\]
+    /// Sets the cursor position.
+    fn set_console_cursor_position(&mut self, cursor_position: Coord) -> io::Result<()>;
+
+    #[doc(alias = "SetConsoleMode")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/setconsolemode)\]
+    /// Sets the [`InputMode`] of the input buffer.
+    fn set_console_input_mode(&mut self, mode: InputMode) -> io::Result<()>;
+
+    #[doc(alias = "SetConsoleMode")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/setconsolemode)\]
+    /// Sets the [`OutputMode`] of the screen buffer.
+    fn set_console_output_mode(&mut self, mode: OutputMode) -> io::Result<()>;
+
+    #[doc(alias = "SetConsoleScreenBufferSize")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/setconsolescreenbuffersize)\]
+    /// Changes the size of the screen buffer.
+    fn set_console_screen_buffer_size(&mut self, size: Coord) -> io::Result<()>;
+
+    #[doc(alias = "SetConsoleTextAttribute")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/setconsoletextattribute)\]
+    /// Sets the [`Attributes`] used by [`write_console`](Self::write_console).
+    fn set_console_text_attribute(&mut self, attributes: Attributes) -> io::Result<()>;
+
+    #[doc(alias = "SetConsoleTitle")]
+    #[doc(alias = "SetConsoleTitleW")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/setconsoletitle)\]
+    /// Sets the title of the console window.
+    fn set_console_title(&mut self, title: &OsStr) -> io::Result<()>;
+
+    #[doc(alias = "SetConsoleWindowInfo")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/setconsolewindowinfo)\]
+    /// Sets the size and position of the screen buffer's window.
+    fn set_console_window_info(&mut self, absolute: bool, console_window: InclusiveRect) -> io::Result<()>;
+
+    #[doc(alias = "WriteConsole")]
+    #[doc(alias = "WriteConsoleW")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/writeconsole)\]
+    /// Writes a string to the screen buffer, beginning at and advancing the cursor.
+    fn write_console(&mut self, buffer: &[u16]) -> io::Result<usize>;
+
+    #[doc(alias = "WriteConsoleInput")]
+    #[doc(alias = "WriteConsoleInputW")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/writeconsoleinput)\]
+    /// Appends records to the input buffer.
+    fn write_console_input(&mut self, buffer: &[InputRecord]) -> io::Result<usize>;
+
+    #[doc(alias = "WriteConsoleOutput")]
+    #[doc(alias = "WriteConsoleOutputW")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/writeconsoleoutput)\]
+    /// Writes `buffer` to the inclusive `write_region` of the screen buffer, updating `write_region` to the region actually written.
+    fn write_console_output(&mut self, buffer: &[CharInfo], buffer_size: Coord, buffer_coord: Coord, write_region: &mut InclusiveRect) -> io::Result<()>;
+
+    #[doc(alias = "WriteConsoleOutput")]
+    #[doc(alias = "WriteConsoleOutputW")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/writeconsoleoutput)\]
+    /// [`write_console_output`](Self::write_console_output) from `grid`, starting at `grid_coord`.
+    fn write_console_output_grid(&mut self, grid: &CharInfoGrid, grid_coord: Coord, write_region: &mut InclusiveRect) -> io::Result<()> {
+        self.write_console_output(grid.cells(), grid.size(), grid_coord, write_region)
+    }
+
+    #[doc(alias = "WriteConsoleOutputAttribute")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/writeconsoleoutputattribute)\]
+    /// Writes a line of [`Attributes`], starting at `write_coord`.
+    fn write_console_output_attribute(&mut self, attributes: &[Attributes], write_coord: Coord) -> io::Result<usize>;
+
+    #[doc(alias = "WriteConsoleOutputCharacter")]
+    #[doc(alias = "WriteConsoleOutputCharacterW")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/writeconsoleoutputcharacter)\]
+    /// Writes a line of characters, starting at `write_coord`.
+    fn write_console_output_character(&mut self, characters: &[u16], write_coord: Coord) -> io::Result<usize>;
+}
+
+
+
+/// \[~~microsoft.com~~\]
+/// A [`ConsoleBackend`] which forwards to the real Win32 console through a pair of handles.
+///
+/// ### Example
+/// ```
+/// # use maulingmonkey_console_winapi_wrappers::*;
+/// # use std::io::{self, *};
+/// # let _ = (|| -> io::Result<()> {
+/// let mut console = Win32Console::stdio();
+/// console.set_console_cursor_position(Coord::new(0, 0))?;
+///
+/// let mut console = Win32Console { input: stdin(), output: stderr() };
+/// console.set_console_text_attribute(FOREGROUND_RED | FOREGROUND_GREEN | FOREGROUND_BLUE)?;
+/// # Ok(())
+/// # })();
+/// ```
+///
+#[cfg(windows)]
+#[derive(Debug)] pub struct Win32Console<I = std::io::Stdin, O = std::io::Stdout> {
+    pub input:  I,
+    pub output: O,
+}
+
+#[cfg(windows)]
+impl Win32Console {
+    /// A [`Win32Console`] operating on [`stdin`](std::io::stdin) and [`stdout`](std::io::stdout).
+    pub fn stdio() -> Self { Self { input: std::io::stdin(), output: std::io::stdout() } }
+}
+
+#[cfg(windows)]
+impl<I: AsConsoleInputHandle, O: AsConsoleOutputHandle> ConsoleBackend for Win32Console<I, O> {
+    fn fill_console_output_attribute(&mut self, attribute: Attributes, length: u32, write_coord: Coord) -> io::Result<u32> { crate::fill_console_output_attribute(&mut self.output, attribute, length, write_coord) }
+    fn fill_console_output_character(&mut self, character: char, length: u32, write_coord: Coord) -> io::Result<u32> { crate::fill_console_output_character(&mut self.output, character, length, write_coord) }
+    fn flush_console_input_buffer(&mut self) -> io::Result<()> { crate::flush_console_input_buffer(&mut self.input) }
+    fn get_console_cursor_info(&self) -> io::Result<ConsoleCursorInfo> { crate::get_console_cursor_info(&self.output) }
+    fn get_console_display_mode(&self) -> io::Result<u32> { crate::get_console_display_mode() }
+    fn get_console_input_mode(&self) -> io::Result<InputMode> { crate::get_console_mode(&self.input) }
+    fn get_console_output_mode(&self) -> io::Result<OutputMode> { crate::get_console_mode(&self.output) }
+    fn get_console_original_title(&self) -> io::Result<OsString> { crate::get_console_original_title() }
+    fn get_console_screen_buffer_info(&self) -> io::Result<ConsoleScreenBufferInfo> { crate::get_console_screen_buffer_info(&self.output) }
+    fn get_console_screen_buffer_info_ex(&self) -> io::Result<ConsoleScreenBufferInfoEx> { crate::get_console_screen_buffer_info_ex(&self.output) }
+    fn get_console_title(&self) -> io::Result<OsString> { crate::get_console_title() }
+    fn get_current_console_font(&self, maximum_window: bool) -> io::Result<ConsoleFontInfo> { crate::get_current_console_font(&self.output, maximum_window) }
+    fn get_current_console_font_ex(&self, maximum_window: bool) -> io::Result<ConsoleFontInfoEx> { crate::get_current_console_font_ex(&self.output, maximum_window) }
+    fn get_largest_console_window_size(&self) -> io::Result<Coord> { crate::get_largest_console_window_size(&self.output) }
+    fn get_number_of_console_input_events(&self) -> io::Result<usize> { crate::get_number_of_console_input_events(&self.input) }
+    fn get_number_of_console_mouse_buttons(&self) -> io::Result<usize> { crate::get_number_of_console_mouse_buttons() }
+    fn peek_console_input_with<'i>(&self, buffer: &'i mut [InputRecord]) -> io::Result<&'i [InputRecord]> { crate::peek_console_input_with(&self.input, buffer) }
+    fn read_console<'i>(&mut self, buffer: &'i mut [u16]) -> io::Result<&'i [u16]> { crate::read_console(&mut self.input, buffer, None) }
+    fn read_console_input_with<'i>(&mut self, buffer: &'i mut [InputRecord]) -> io::Result<&'i [InputRecord]> { crate::read_console_input_with(&mut self.input, buffer) }
+    fn read_console_output_attribute<'a>(&self, attributes: &'a mut [Attributes], read_coord: Coord) -> io::Result<&'a [Attributes]> { crate::read_console_output_attribute(&self.output, attributes, read_coord) }
+    fn read_console_output_character<'a>(&self, characters: &'a mut [u16], read_coord: Coord) -> io::Result<&'a [u16]> { crate::read_console_output_character(&self.output, characters, read_coord) }
+    fn scroll_console_screen_buffer(&mut self, scroll_rect: InclusiveRect, clip_rect: Option<InclusiveRect>, destination: Coord, fill: CharInfo) -> io::Result<()> { crate::scroll_console_screen_buffer(&mut self.output, scroll_rect, clip_rect, destination, fill) }
+    fn set_console_cursor_info(&mut self, info: ConsoleCursorInfo) -> io::Result<()> { crate::set_console_cursor_info(&mut self.output, info) }
+    fn set_console_cursor_position(&mut self, cursor_position: Coord) -> io::Result<()> { crate::set_console_cursor_position(&mut self.output, cursor_position) }
+    fn set_console_input_mode(&mut self, mode: InputMode) -> io::Result<()> { crate::set_console_mode(&mut self.input, mode) }
+    fn set_console_output_mode(&mut self, mode: OutputMode) -> io::Result<()> { crate::set_console_mode(&mut self.output, mode) }
+    fn set_console_screen_buffer_size(&mut self, size: Coord) -> io::Result<()> { crate::set_console_screen_buffer_size(&mut self.output, size) }
+    fn set_console_text_attribute(&mut self, attributes: Attributes) -> io::Result<()> { crate::set_console_text_attribute(&mut self.output, attributes) }
+    fn set_console_title(&mut self, title: &OsStr) -> io::Result<()> { crate::set_console_title(title) }
+    fn set_console_window_info(&mut self, absolute: bool, console_window: InclusiveRect) -> io::Result<()> { crate::set_console_window_info(&mut self.output, absolute, console_window) }
+    fn write_console(&mut self, buffer: &[u16]) -> io::Result<usize> { crate::write_console(&mut self.output, buffer, ()) }
+    fn write_console_input(&mut self, buffer: &[InputRecord]) -> io::Result<usize> { crate::write_console_input(&mut self.input, buffer) }
+    fn write_console_output_attribute(&mut self, attributes: &[Attributes], write_coord: Coord) -> io::Result<usize> { crate::write_console_output_attribute(&mut self.output, attributes, write_coord) }
+    fn write_console_output_character(&mut self, characters: &[u16], write_coord: Coord) -> io::Result<usize> { crate::write_console_output_character(&mut self.output, characters, write_coord) }
+
+    fn read_console_output(&self, buffer: &mut [CharInfo], buffer_size: Coord, buffer_coord: Coord, read_region: &mut InclusiveRect) -> io::Result<()> { crate::read_console_output(&self.output, buffer, buffer_size, buffer_coord, read_region) }
+    fn write_console_output(&mut self, buffer: &[CharInfo], buffer_size: Coord, buffer_coord: Coord, write_region: &mut InclusiveRect) -> io::Result<()> { crate::write_console_output(&mut self.output, buffer, buffer_size, buffer_coord, write_region) }
+}
@@ -0,0 +1,360 @@
+use crate::*;
+
+use std::io;
+
+use core::ops::{Index, IndexMut};
+
+
+
+/// \[~~microsoft.com~~\]
+/// An owned, row-major grid of [`CharInfo`]s which knows its own size.
+///
+/// Pass it to e.g. [`MemoryScreenBuffer::read_console_output_grid`] / [`MemoryScreenBuffer::write_console_output_grid`]
+/// instead of a loose `&mut [CharInfo]` + `buffer_size`, which can't disagree with each other.
+///
+/// ### Example
+/// ```
+/// # use maulingmonkey_console_winapi_wrappers::*;
+/// let blank = CharInfo::new(b' '.into(), FOREGROUND_RED | FOREGROUND_GREEN | FOREGROUND_BLUE);
+/// let mut grid = CharInfoGrid::new((10, 2), blank);
+/// grid[Coord::new(3, 1)].char = u16::from(b'!').into();
+/// grid.fill((0, 0) .. (2, 1), CharInfo::new(b'#'.into(), FOREGROUND_GREEN));
+///
+/// let mut screen = MemoryScreenBuffer::new((80, 25));
+/// let mut region = InclusiveRect::new(5, 5, 14, 6);
+/// screen.write_console_output_grid(&grid, (0, 0), &mut region).unwrap();
+/// assert_eq!(screen.cell((8, 6)).unwrap().char.unicode_char(), u16::from(b'!'));
+/// assert_eq!(screen.cell((6, 5)).unwrap().attributes, FOREGROUND_GREEN);
+/// ```
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CharInfoGrid {
+    size:   Coord,
+    cells:  Vec<CharInfo>,
+}
+
+/// \[~~microsoft.com~~\]
+/// A borrowed rectangular region of a [`CharInfoGrid`].  Coordinates are relative to the top left corner of the region.
+#[derive(Clone, Copy, Debug)]
+pub struct CharInfoGridView<'g> {
+    cells:  &'g [CharInfo],
+    stride: usize,
+    rect:   ExclusiveRect,
+}
+
+/// \[~~microsoft.com~~\]
+/// A mutably borrowed rectangular region of a [`CharInfoGrid`].  Coordinates are relative to the top left corner of the region.
+#[derive(Debug)]
+pub struct CharInfoGridViewMut<'g> {
+    cells:  &'g mut [CharInfo],
+    stride: usize,
+    rect:   ExclusiveRect,
+}
+
+impl CharInfoGrid {
+    /// Creates a grid of `size` cells, all set to `fill`.  Negative dimensions are treated as `0`.
+    pub fn new(size: impl Into<Coord>, fill: impl Into<CharInfo>) -> Self {
+        let size = size.into();
+        let size = Coord::new(size.x.max(0), size.y.max(0));
+        Self { size, cells: vec![fill.into(); (size.x as usize) * (size.y as usize)] }
+    }
+
+    /// Creates a grid of `size` cells from row-major `cells`.  Fails if `size` is negative, or if `cells.len()` isn't exactly `size.x * size.y`.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// assert!(CharInfoGrid::from_cells((2, 2), vec![CharInfo::default(); 4]).is_ok());
+    /// assert!(CharInfoGrid::from_cells((2, 2), vec![CharInfo::default(); 5]).is_err());
+    /// ```
+    ///
+    pub fn from_cells(size: impl Into<Coord>, cells: Vec<CharInfo>) -> io::Result<Self> {
+        let size = size.into();
+        if size.x < 0 || size.y < 0 { return Err(io::Error::new(io::ErrorKind::InvalidInput, "CharInfoGrid::from_cells(): negative `size`")) }
+        if (size.x as usize) * (size.y as usize) != cells.len() { return Err(io::Error::new(io::ErrorKind::InvalidInput, "CharInfoGrid::from_cells(): `cells.len()` doesn't match `size`")) }
+        Ok(Self { size, cells })
+    }
+
+    /// The size of the grid, in cells.
+    pub fn size(&self) -> Coord { self.size }
+
+    /// The cells covered by the grid: `(0, 0) .. size`.
+    pub fn bounds(&self) -> ExclusiveRect { ExclusiveRect::from(Coord::new(0, 0) .. self.size) }
+
+    /// All cells of the grid, in row-major order.
+    pub fn cells(&self) -> &[CharInfo] { &self.cells }
+
+    /// All cells of the grid, in row-major order.
+    pub fn cells_mut(&mut self) -> &mut [CharInfo] { &mut self.cells }
+
+    /// Returns all cells of the grid, in row-major order.
+    pub fn into_cells(self) -> Vec<CharInfo> { self.cells }
+
+    /// Returns the cell at `coord`, or [`None`] if `coord` is outside of the grid.
+    pub fn get(&self, coord: impl Into<Coord>) -> Option<&CharInfo> { self.as_view().get(coord) }
+
+    /// Returns the cell at `coord`, or [`None`] if `coord` is outside of the grid.
+    pub fn get_mut(&mut self, coord: impl Into<Coord>) -> Option<&mut CharInfo> {
+        let i = self.index_of(coord.into())?;
+        Some(&mut self.cells[i])
+    }
+
+    /// Returns row `y`, or [`None`] if `y` is outside of the grid.
+    pub fn row(&self, y: i16) -> Option<&[CharInfo]> { self.as_view().row(y) }
+
+    /// Returns row `y`, or [`None`] if `y` is outside of the grid.
+    pub fn row_mut(&mut self, y: i16) -> Option<&mut [CharInfo]> {
+        if !(0 .. self.size.y).contains(&y) { return None }
+        let w = self.size.x as usize;
+        let start = (y as usize) * w;
+        Some(&mut self.cells[start .. start + w])
+    }
+
+    /// Iterates over the rows of the grid, top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[CharInfo]> + Clone + '_ { self.as_view().into_rows() }
+
+    /// Iterates over the rows of the grid, top to bottom.
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [CharInfo]> + '_ { self.as_view_mut().into_rows_mut() }
+
+    /// Borrows the entire grid as a [`CharInfoGridView`].
+    pub fn as_view(&self) -> CharInfoGridView<'_> { CharInfoGridView { cells: &self.cells, stride: self.size.x as usize, rect: self.bounds() } }
+
+    /// Borrows the entire grid as a [`CharInfoGridViewMut`].
+    pub fn as_view_mut(&mut self) -> CharInfoGridViewMut<'_> { CharInfoGridViewMut { rect: self.bounds(), stride: self.size.x as usize, cells: &mut self.cells } }
+
+    /// Borrows the cells of `rect`, or returns [`None`] if `rect` is inverted or extends outside of the grid.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let grid = CharInfoGrid::from_cells((3, 2), b"abcdef".iter().map(|&ch| CharInfo::new(ch.into(), Attributes::default())).collect()).unwrap();
+    /// let view = grid.view((1, 0) .. (3, 2)).unwrap();
+    /// assert_eq!(view.size(), Coord::new(2, 2));
+    /// assert_eq!(view.get((0, 1)).unwrap().char.unicode_char(), u16::from(b'e'));
+    /// assert!(grid.view((1, 0) .. (4, 2)).is_none());
+    /// ```
+    ///
+    pub fn view(&self, rect: impl Into<ExclusiveRect>) -> Option<CharInfoGridView<'_>> {
+        let rect = self.check_view_rect(rect.into())?;
+        Some(CharInfoGridView { cells: &self.cells, stride: self.size.x as usize, rect })
+    }
+
+    /// Mutably borrows the cells of `rect`, or returns [`None`] if `rect` is inverted or extends outside of the grid.
+    pub fn view_mut(&mut self, rect: impl Into<ExclusiveRect>) -> Option<CharInfoGridViewMut<'_>> {
+        let rect = self.check_view_rect(rect.into())?;
+        Some(CharInfoGridViewMut { cells: &mut self.cells, stride: self.size.x as usize, rect })
+    }
+
+    /// Sets every cell of `rect` (clipped to the grid) to `fill`, and returns the clipped rectangle.
+    pub fn fill(&mut self, rect: impl Into<ExclusiveRect>, fill: impl Into<CharInfo>) -> ExclusiveRect {
+        let rect = rect.into().clamp_to(self.size);
+        if rect.is_empty() { return rect }
+        self.view_mut(rect).expect("clamped").fill(fill);
+        rect
+    }
+
+    /// Copies `source` into the grid with its top left corner at `destination`, clipping to the grid.
+    /// Returns the rectangle of the grid that was written to, which is empty if `source` landed entirely outside of the grid.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let mut dst = CharInfoGrid::new((4, 4), CharInfo::default());
+    /// let src = CharInfoGrid::new((3, 3), CharInfo::new(b'x'.into(), FOREGROUND_RED));
+    /// assert_eq!(dst.blit((2, -1), src.as_view()), ExclusiveRect::new(2, 0, 4, 2));
+    /// assert_eq!(dst[Coord::new(3, 1)].attributes, FOREGROUND_RED);
+    /// assert_eq!(dst[Coord::new(1, 1)], CharInfo::default());
+    /// ```
+    ///
+    pub fn blit(&mut self, destination: impl Into<Coord>, source: CharInfoGridView<'_>) -> ExclusiveRect {
+        let destination = destination.into();
+        let size = source.size();
+        let (left, top) = (i32::from(destination.x), i32::from(destination.y));
+        let clip = |v: i32, max: i16| v.clamp(0, i32::from(max)) as i16;
+        let written = ExclusiveRect::new(
+            clip(left, self.size.x),
+            clip(top, self.size.y),
+            clip(left + i32::from(size.x), self.size.x),
+            clip(top + i32::from(size.y), self.size.y),
+        );
+        if written.is_empty() { return ExclusiveRect::default() }
+
+        let (sx, sy) = ((i32::from(written.left) - left) as usize, (i32::from(written.top) - top) as usize);
+        let w = (written.right - written.left) as usize;
+        let mut target = self.view_mut(written).expect("clipped");
+        for (dst, src) in target.rows_mut().zip(source.into_rows().skip(sy)) {
+            dst.copy_from_slice(&src[sx .. sx + w]);
+        }
+        written
+    }
+
+    /// Changes the size of the grid, keeping the cells of the top left corner common to both sizes, and setting any new cells to `fill`.
+    /// Negative dimensions are treated as `0`.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let a = CharInfo::new(b'a'.into(), Attributes::default());
+    /// let mut grid = CharInfoGrid::new((2, 2), a);
+    /// grid.resize((3, 1), CharInfo::default());
+    /// assert_eq!(grid.cells(), &[a, a, CharInfo::default()]);
+    /// ```
+    ///
+    pub fn resize(&mut self, size: impl Into<Coord>, fill: impl Into<CharInfo>) {
+        let mut resized = Self::new(size, fill);
+        resized.blit((0, 0), self.as_view());
+        *self = resized;
+    }
+
+    fn index_of(&self, coord: Coord) -> Option<usize> {
+        if !self.bounds().contains(coord) { return None }
+        coord.to_index(self.size.x as usize)
+    }
+
+    fn check_view_rect(&self, rect: ExclusiveRect) -> Option<ExclusiveRect> {
+        let inside = 0 <= rect.left && rect.left <= rect.right && rect.right <= self.size.x
+            && 0 <= rect.top && rect.top <= rect.bottom && rect.bottom <= self.size.y;
+        inside.then_some(rect)
+    }
+}
+
+impl Index<Coord> for CharInfoGrid {
+    type Output = CharInfo;
+    fn index(&self, coord: Coord) -> &CharInfo { self.get(coord).expect("CharInfoGrid: coord out of bounds") }
+}
+
+impl IndexMut<Coord> for CharInfoGrid {
+    fn index_mut(&mut self, coord: Coord) -> &mut CharInfo { self.get_mut(coord).expect("CharInfoGrid: coord out of bounds") }
+}
+
+impl<'g> CharInfoGridView<'g> {
+    /// The size of the region, in cells.
+    pub fn size(&self) -> Coord { Coord::new(self.rect.right - self.rect.left, self.rect.bottom - self.rect.top) }
+
+    /// The region of the underlying [`CharInfoGrid`] covered by `self`.
+    pub fn rect(&self) -> ExclusiveRect { self.rect }
+
+    /// Returns the cell at `coord` (relative to the region), or [`None`] if `coord` is outside of the region.
+    pub fn get(&self, coord: impl Into<Coord>) -> Option<&'g CharInfo> {
+        let Coord { x, y } = coord.into();
+        if !(0 .. self.size().x).contains(&x) { return None }
+        self.row(y).map(|row| &row[x as usize])
+    }
+
+    /// Returns row `y` (relative to the region), or [`None`] if `y` is outside of the region.
+    pub fn row(&self, y: i16) -> Option<&'g [CharInfo]> {
+        if !(0 .. self.size().y).contains(&y) { return None }
+        let start = (self.rect.top + y) as usize * self.stride + self.rect.left as usize;
+        Some(&self.cells[start .. start + self.size().x as usize])
+    }
+
+    /// Iterates over the rows of the region, top to bottom.
+    pub fn into_rows(self) -> impl Iterator<Item = &'g [CharInfo]> + Clone {
+        (0 .. self.size().y).map(move |y| self.row(y).unwrap())
+    }
+
+    /// Copies the region into a new [`CharInfoGrid`].
+    pub fn to_grid(&self) -> CharInfoGrid {
+        CharInfoGrid { size: self.size(), cells: self.into_rows().flatten().copied().collect() }
+    }
+}
+
+impl<'g> CharInfoGridViewMut<'g> {
+    /// The size of the region, in cells.
+    pub fn size(&self) -> Coord { self.as_view().size() }
+
+    /// The region of the underlying [`CharInfoGrid`] covered by `self`.
+    pub fn rect(&self) -> ExclusiveRect { self.rect }
+
+    /// Reborrows `self` immutably.
+    pub fn as_view(&self) -> CharInfoGridView<'_> { CharInfoGridView { cells: self.cells, stride: self.stride, rect: self.rect } }
+
+    /// Reborrows `self`, for passing to something that consumes a [`CharInfoGridViewMut`] without giving up `self`.
+    pub fn reborrow(&mut self) -> CharInfoGridViewMut<'_> { CharInfoGridViewMut { cells: self.cells, stride: self.stride, rect: self.rect } }
+
+    /// Returns the cell at `coord` (relative to the region), or [`None`] if `coord` is outside of the region.
+    pub fn get_mut(&mut self, coord: impl Into<Coord>) -> Option<&mut CharInfo> {
+        let Coord { x, y } = coord.into();
+        if !(0 .. self.size().x).contains(&x) { return None }
+        self.row_mut(y).map(|row| &mut row[x as usize])
+    }
+
+    /// Returns row `y` (relative to the region), or [`None`] if `y` is outside of the region.
+    pub fn row_mut(&mut self, y: i16) -> Option<&mut [CharInfo]> {
+        if !(0 .. self.size().y).contains(&y) { return None }
+        let start = (self.rect.top + y) as usize * self.stride + self.rect.left as usize;
+        let w = self.size().x as usize;
+        Some(&mut self.cells[start .. start + w])
+    }
+
+    /// Iterates over the rows of the region, top to bottom.
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [CharInfo]> + '_ { self.reborrow().into_rows_mut() }
+
+    /// Iterates over the rows of the region, top to bottom.
+    pub fn into_rows_mut(self) -> impl Iterator<Item = &'g mut [CharInfo]> {
+        let Coord { x: w, y: h } = self.size();
+        let (w, gap) = (w as usize, self.stride - w as usize);
+        let start = (self.rect.top as usize) * self.stride + self.rect.left as usize;
+        let mut rest = self.cells.get_mut(start ..).unwrap_or_default();
+        (0 .. h).map(move |_| {
+            let (row, after) = core::mem::take(&mut rest).split_at_mut(w);
+            rest = after.get_mut(gap ..).unwrap_or_default();
+            row
+        })
+    }
+
+    /// Sets every cell of the region to `fill`.
+    pub fn fill(&mut self, fill: impl Into<CharInfo>) {
+        let fill = fill.into();
+        for row in self.rows_mut() { row.fill(fill); }
+    }
+}
+
+/// Decodes the characters of `cells`, yielding each alongside the index and value of the (first) cell it came from.
+///
+/// Cells marked [`COMMON_LVB_TRAILING_BYTE`] (the second half of double-width characters) are skipped, and UTF-16 surrogate pairs split across two cells are combined.
+/// NUL decodes as a space, unpaired surrogates as U+FFFD.
+pub(crate) fn decode_cells(cells: &[CharInfo]) -> impl Iterator<Item = (usize, CharInfo, char)> + '_ {
+    let mut cells = cells.iter().copied().enumerate().filter(|(_, cell)| u16::from(cell.attributes) & u16::from(COMMON_LVB_TRAILING_BYTE) == 0).peekable();
+    core::iter::from_fn(move || {
+        let (x, cell) = cells.next()?;
+        let unit = cell.char.unicode_char();
+        let ch = match unit {
+            0xD800 ..= 0xDBFF => match cells.peek().map(|(_, next)| next.char.unicode_char()) {
+                Some(low @ 0xDC00 ..= 0xDFFF) => { cells.next(); char::decode_utf16([unit, low]).next().and_then(Result::ok) },
+                _ => None,
+            },
+            0 => Some(' '),
+            unit => char::from_u32(unit.into()),
+        };
+        Some((x, cell, ch.unwrap_or(char::REPLACEMENT_CHARACTER)))
+    })
+}
+
+
+
+#[test] fn views() {
+    let text = |grid: &CharInfoGrid| grid_text(grid.cells());
+    let mut grid = grid_from_text((4, 3), b"abcdefghijkl");
+
+    let view = grid.view((1, 1) .. (3, 3)).unwrap();
+    assert_eq!(text(&view.to_grid()), "fgjk");
+    assert_eq!(view.get((2, 0)), None);
+
+    grid.view_mut((2, 0) .. (4, 2)).unwrap().fill(cell(b'.'));
+    assert_eq!(text(&grid), "ab..ef..ijkl");
+
+    assert_eq!(grid.fill((-1, 2) .. (1, 9), cell(b'#')), ExclusiveRect::new(0, 2, 1, 3));
+    assert_eq!(text(&grid), "ab..ef..#jkl");
+
+    let copy = grid.clone();
+    assert_eq!(grid.blit((1, 1), copy.as_view()), ExclusiveRect::new(1, 1, 4, 3));
+    assert_eq!(text(&grid), "ab..eab.#ef.");
+    assert_eq!(grid.blit((4, 0), copy.as_view()), ExclusiveRect::default());
+
+    grid.resize((5, 2), cell(b' '));
+    assert_eq!(text(&grid), "ab.. eab. ");
+    assert_eq!(grid.rows().count(), 2);
+    assert!(grid.view((0, 0) .. (0, 2)).unwrap().into_rows().all(|row| row.is_empty()));
+    assert!(grid.view((3, 3) .. (3, 3)).is_none());
+}
@@ -1,112 +1,132 @@
 use bytemuck::{Pod, Zeroable};
 
-use winapi::shared::minwindef::WORD;
-use winapi::um::wincon;
-
 use core::ops::*;
 
 
 
 /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/console-screen-buffers#character-attributes)\]
 /// 4-bit foreground and background color, plus text decoration.
-#[repr(transparent)] #[derive(Clone, Copy, Pod, Debug, Default, Zeroable, PartialEq, Eq, PartialOrd, Ord, Hash)] pub struct Attributes(WORD);
+#[repr(transparent)] #[derive(Clone, Copy, Pod, Debug, Default, Zeroable, PartialEq, Eq, PartialOrd, Ord, Hash)] pub struct Attributes(u16);
 
 
 
 /// Text color contains blue.
-#[doc(hidden)] pub const FOREGROUND_BLUE : Attributes = Attributes(wincon::FOREGROUND_BLUE);
+#[doc(hidden)] pub const FOREGROUND_BLUE : Attributes = Attributes(0x0001);
 
 /// Text color contains green.
-#[doc(hidden)] pub const FOREGROUND_GREEN : Attributes = Attributes(wincon::FOREGROUND_GREEN);
+#[doc(hidden)] pub const FOREGROUND_GREEN : Attributes = Attributes(0x0002);
 
 /// Text color contains red.
-#[doc(hidden)] pub const FOREGROUND_RED : Attributes = Attributes(wincon::FOREGROUND_RED);
+#[doc(hidden)] pub const FOREGROUND_RED : Attributes = Attributes(0x0004);
 
 /// Text color is intensified.
-#[doc(hidden)] pub const FOREGROUND_INTENSITY : Attributes = Attributes(wincon::FOREGROUND_INTENSITY);
+#[doc(hidden)] pub const FOREGROUND_INTENSITY : Attributes = Attributes(0x0008);
 
 /// Background color contains blue.
-#[doc(hidden)] pub const BACKGROUND_BLUE : Attributes = Attributes(wincon::BACKGROUND_BLUE);
+#[doc(hidden)] pub const BACKGROUND_BLUE : Attributes = Attributes(0x0010);
 
 /// Background color contains green.
-#[doc(hidden)] pub const BACKGROUND_GREEN : Attributes = Attributes(wincon::BACKGROUND_GREEN);
+#[doc(hidden)] pub const BACKGROUND_GREEN : Attributes = Attributes(0x0020);
 
 /// Background color contains red.
-#[doc(hidden)] pub const BACKGROUND_RED : Attributes = Attributes(wincon::BACKGROUND_RED);
+#[doc(hidden)] pub const BACKGROUND_RED : Attributes = Attributes(0x0040);
 
 /// Background color is intensified.
-#[doc(hidden)] pub const BACKGROUND_INTENSITY : Attributes = Attributes(wincon::BACKGROUND_INTENSITY);
+#[doc(hidden)] pub const BACKGROUND_INTENSITY : Attributes = Attributes(0x0080);
 
 /// Leading byte.
-#[doc(hidden)] pub const COMMON_LVB_LEADING_BYTE : Attributes = Attributes(wincon::COMMON_LVB_LEADING_BYTE);
+#[doc(hidden)] pub const COMMON_LVB_LEADING_BYTE : Attributes = Attributes(0x0100);
 
 /// Trailing byte.
-#[doc(hidden)] pub const COMMON_LVB_TRAILING_BYTE : Attributes = Attributes(wincon::COMMON_LVB_TRAILING_BYTE);
+#[doc(hidden)] pub const COMMON_LVB_TRAILING_BYTE : Attributes = Attributes(0x0200);
 
 /// Top horizontal.
-#[doc(hidden)] pub const COMMON_LVB_GRID_HORIZONTAL : Attributes = Attributes(wincon::COMMON_LVB_GRID_HORIZONTAL);
+#[doc(hidden)] pub const COMMON_LVB_GRID_HORIZONTAL : Attributes = Attributes(0x0400);
 
 /// Left vertical.
-#[doc(hidden)] pub const COMMON_LVB_GRID_LVERTICAL : Attributes = Attributes(wincon::COMMON_LVB_GRID_LVERTICAL);
+#[doc(hidden)] pub const COMMON_LVB_GRID_LVERTICAL : Attributes = Attributes(0x0800);
 
 /// Right vertical.
-#[doc(hidden)] pub const COMMON_LVB_GRID_RVERTICAL : Attributes = Attributes(wincon::COMMON_LVB_GRID_RVERTICAL);
+#[doc(hidden)] pub const COMMON_LVB_GRID_RVERTICAL : Attributes = Attributes(0x1000);
 
 /// Reverse foreground and background attributes.
-#[doc(hidden)] pub const COMMON_LVB_REVERSE_VIDEO : Attributes = Attributes(wincon::COMMON_LVB_REVERSE_VIDEO);
+#[doc(hidden)] pub const COMMON_LVB_REVERSE_VIDEO : Attributes = Attributes(0x4000);
 
 /// Underscore.
-#[doc(hidden)] pub const COMMON_LVB_UNDERSCORE : Attributes = Attributes(wincon::COMMON_LVB_UNDERSCORE);
+#[doc(hidden)] pub const COMMON_LVB_UNDERSCORE : Attributes = Attributes(0x8000);
+
+#[cfg(windows)] const _ : () = {
+    use winapi::um::wincon;
+    assert!(FOREGROUND_BLUE.0             == wincon::FOREGROUND_BLUE            );
+    assert!(FOREGROUND_GREEN.0            == wincon::FOREGROUND_GREEN           );
+    assert!(FOREGROUND_RED.0              == wincon::FOREGROUND_RED             );
+    assert!(FOREGROUND_INTENSITY.0        == wincon::FOREGROUND_INTENSITY       );
+    assert!(BACKGROUND_BLUE.0             == wincon::BACKGROUND_BLUE            );
+    assert!(BACKGROUND_GREEN.0            == wincon::BACKGROUND_GREEN           );
+    assert!(BACKGROUND_RED.0              == wincon::BACKGROUND_RED             );
+    assert!(BACKGROUND_INTENSITY.0        == wincon::BACKGROUND_INTENSITY       );
+    assert!(COMMON_LVB_LEADING_BYTE.0     == wincon::COMMON_LVB_LEADING_BYTE    );
+    assert!(COMMON_LVB_TRAILING_BYTE.0    == wincon::COMMON_LVB_TRAILING_BYTE   );
+    assert!(COMMON_LVB_GRID_HORIZONTAL.0  == wincon::COMMON_LVB_GRID_HORIZONTAL );
+    assert!(COMMON_LVB_GRID_LVERTICAL.0   == wincon::COMMON_LVB_GRID_LVERTICAL  );
+    assert!(COMMON_LVB_GRID_RVERTICAL.0   == wincon::COMMON_LVB_GRID_RVERTICAL  );
+    assert!(COMMON_LVB_REVERSE_VIDEO.0    == wincon::COMMON_LVB_REVERSE_VIDEO   );
+    assert!(COMMON_LVB_UNDERSCORE.0       == wincon::COMMON_LVB_UNDERSCORE      );
+};
 
 impl Attributes {
     /// Text color contains blue.
-    pub const FOREGROUND_BLUE : Attributes = Attributes(wincon::FOREGROUND_BLUE);
+    pub const FOREGROUND_BLUE : Attributes = FOREGROUND_BLUE;
 
     /// Text color contains green.
-    pub const FOREGROUND_GREEN : Attributes = Attributes(wincon::FOREGROUND_GREEN);
+    pub const FOREGROUND_GREEN : Attributes = FOREGROUND_GREEN;
 
     /// Text color contains red.
-    pub const FOREGROUND_RED : Attributes = Attributes(wincon::FOREGROUND_RED);
+    pub const FOREGROUND_RED : Attributes = FOREGROUND_RED;
 
     /// Text color is intensified.
-    pub const FOREGROUND_INTENSITY : Attributes = Attributes(wincon::FOREGROUND_INTENSITY);
+    pub const FOREGROUND_INTENSITY : Attributes = FOREGROUND_INTENSITY;
 
     /// Background color contains blue.
-    pub const BACKGROUND_BLUE : Attributes = Attributes(wincon::BACKGROUND_BLUE);
+    pub const BACKGROUND_BLUE : Attributes = BACKGROUND_BLUE;
 
     /// Background color contains green.
-    pub const BACKGROUND_GREEN : Attributes = Attributes(wincon::BACKGROUND_GREEN);
+    pub const BACKGROUND_GREEN : Attributes = BACKGROUND_GREEN;
 
     /// Background color contains red.
-    pub const BACKGROUND_RED : Attributes = Attributes(wincon::BACKGROUND_RED);
+    pub const BACKGROUND_RED : Attributes = BACKGROUND_RED;
 
     /// Background color is intensified.
-    pub const BACKGROUND_INTENSITY : Attributes = Attributes(wincon::BACKGROUND_INTENSITY);
+    pub const BACKGROUND_INTENSITY : Attributes = BACKGROUND_INTENSITY;
 
     /// Leading byte.
-    pub const COMMON_LVB_LEADING_BYTE : Attributes = Attributes(wincon::COMMON_LVB_LEADING_BYTE);
+    pub const COMMON_LVB_LEADING_BYTE : Attributes = COMMON_LVB_LEADING_BYTE;
 
     /// Trailing byte.
-    pub const COMMON_LVB_TRAILING_BYTE : Attributes = Attributes(wincon::COMMON_LVB_TRAILING_BYTE);
+    pub const COMMON_LVB_TRAILING_BYTE : Attributes = COMMON_LVB_TRAILING_BYTE;
 
     /// Top horizontal.
-    pub const COMMON_LVB_GRID_HORIZONTAL : Attributes = Attributes(wincon::COMMON_LVB_GRID_HORIZONTAL);
+    pub const COMMON_LVB_GRID_HORIZONTAL : Attributes = COMMON_LVB_GRID_HORIZONTAL;
 
     /// Left vertical.
-    pub const COMMON_LVB_GRID_LVERTICAL : Attributes = Attributes(wincon::COMMON_LVB_GRID_LVERTICAL);
+    pub const COMMON_LVB_GRID_LVERTICAL : Attributes = COMMON_LVB_GRID_LVERTICAL;
 
     /// Right vertical.
-    pub const COMMON_LVB_GRID_RVERTICAL : Attributes = Attributes(wincon::COMMON_LVB_GRID_RVERTICAL);
+    pub const COMMON_LVB_GRID_RVERTICAL : Attributes = COMMON_LVB_GRID_RVERTICAL;
 
     /// Reverse foreground and background attributes.
-    pub const COMMON_LVB_REVERSE_VIDEO : Attributes = Attributes(wincon::COMMON_LVB_REVERSE_VIDEO);
+    pub const COMMON_LVB_REVERSE_VIDEO : Attributes = COMMON_LVB_REVERSE_VIDEO;
 
     /// Underscore.
-    pub const COMMON_LVB_UNDERSCORE : Attributes = Attributes(wincon::COMMON_LVB_UNDERSCORE);
+    pub const COMMON_LVB_UNDERSCORE : Attributes = COMMON_LVB_UNDERSCORE;
+}
+
+impl Attributes {
+    pub(crate) const fn from_bits(bits: u16) -> Self { Self(bits) }
 }
 
-impl From<WORD> for Attributes { fn from(value: WORD) -> Self { Self(value) } }
-impl From<Attributes> for WORD { fn from(value: Attributes) -> Self { value.0 } }
+impl From<u16> for Attributes { fn from(value: u16) -> Self { Self(value) } }
+impl From<Attributes> for u16 { fn from(value: Attributes) -> Self { value.0 } }
 
 impl BitAnd for Attributes { type Output = Self; fn bitand(self, rhs: Self) -> Self::Output { Self(self.0 & rhs.0) } }
 impl BitXor for Attributes { type Output = Self; fn bitxor(self, rhs: Self) -> Self::Output { Self(self.0 ^ rhs.0) } }
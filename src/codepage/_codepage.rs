@@ -0,0 +1,551 @@
+#[cfg(windows)] use crate::*;
+
+#[cfg(windows)] use winapi::um::consoleapi::*;
+#[cfg(windows)] use winapi::um::wincon::*;
+
+use std::io;
+
+use core::fmt::{self, Debug, Formatter};
+use core::str::FromStr;
+
+mod tables;
+
+
+
+/// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/intl/code-page-identifiers)\]
+/// Code page identifiers (IBM437, UTF8, etc.)
+///
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CodePage(u32);
+
+impl CodePage {
+    /// OEM United States
+    pub const IBM437 : CodePage = CodePage(437);
+
+    /// OEM Greek (formerly 437G); Greek (DOS)
+    pub const IBM737 : CodePage = CodePage(737);
+
+    /// OEM Baltic; Baltic (DOS)
+    pub const IBM775 : CodePage = CodePage(775);
+
+    /// OEM Multilingual Latin 1; Western European (DOS)
+    pub const IBM850 : CodePage = CodePage(850);
+
+    /// OEM Latin 2; Central European (DOS)
+    pub const IBM852 : CodePage = CodePage(852);
+
+    /// OEM Cyrillic (primarily Russian)
+    pub const IBM855 : CodePage = CodePage(855);
+
+    /// OEM Turkish; Turkish (DOS)
+    pub const IBM857 : CodePage = CodePage(857);
+
+    /// OEM Multilingual Latin 1 + Euro symbol
+    pub const IBM00858 : CodePage = CodePage(858);
+
+    /// OEM Portuguese; Portuguese (DOS)
+    pub const IBM860 : CodePage = CodePage(860);
+
+    /// OEM Icelandic; Icelandic (DOS)
+    pub const IBM861 : CodePage = CodePage(861);
+
+    /// OEM Hebrew; Hebrew (DOS)
+    pub const DOS_862 : CodePage = CodePage(862);
+
+    /// OEM French Canadian; French Canadian (DOS)
+    pub const IBM863 : CodePage = CodePage(863);
+
+    /// OEM Arabic; Arabic (864)
+    pub const IBM864 : CodePage = CodePage(864);
+
+    /// OEM Nordic; Nordic (DOS)
+    pub const IBM865 : CodePage = CodePage(865);
+
+    /// OEM Russian; Cyrillic (DOS)
+    pub const CP866 : CodePage = CodePage(866);
+
+    /// OEM Modern Greek; Greek, Modern (DOS)
+    pub const IBM869 : CodePage = CodePage(869);
+
+    /// Thai (Windows)
+    pub const WINDOWS_874 : CodePage = CodePage(874);
+
+    /// ANSI/OEM Japanese; Japanese (Shift-JIS)
+    pub const SHIFT_JIS : CodePage = CodePage(932);
+
+    /// ANSI/OEM Simplified Chinese (PRC, Singapore); Chinese Simplified (GB2312)
+    pub const GB2312 : CodePage = CodePage(936);
+
+    /// ANSI/OEM Korean (Unified Hangul Code)
+    pub const KS_C_5601_1987 : CodePage = CodePage(949);
+
+    /// ANSI/OEM Traditional Chinese (Taiwan; Hong Kong SAR, PRC); Chinese Traditional (Big5)
+    pub const BIG5 : CodePage = CodePage(950);
+
+    /// ANSI Central European; Central European (Windows)
+    pub const WINDOWS_1250 : CodePage = CodePage(1250);
+
+    /// ANSI Cyrillic; Cyrillic (Windows)
+    pub const WINDOWS_1251 : CodePage = CodePage(1251);
+
+    /// ANSI Latin 1; Western European (Windows)
+    pub const WINDOWS_1252 : CodePage = CodePage(1252);
+
+    /// ANSI Greek; Greek (Windows)
+    pub const WINDOWS_1253 : CodePage = CodePage(1253);
+
+    /// ANSI Turkish; Turkish (Windows)
+    pub const WINDOWS_1254 : CodePage = CodePage(1254);
+
+    /// ANSI Hebrew; Hebrew (Windows)
+    pub const WINDOWS_1255 : CodePage = CodePage(1255);
+
+    /// ANSI Arabic; Arabic (Windows)
+    pub const WINDOWS_1256 : CodePage = CodePage(1256);
+
+    /// ANSI Baltic; Baltic (Windows)
+    pub const WINDOWS_1257 : CodePage = CodePage(1257);
+
+    /// ANSI/OEM Vietnamese; Vietnamese (Windows)
+    pub const WINDOWS_1258 : CodePage = CodePage(1258);
+
+    #[doc(alias = "CP_UTF7")]
+    /// Unicode (UTF-7)
+    pub const UTF7 : CodePage = CodePage(65000);
+
+    #[doc(alias = "CP_UTF8")]
+    /// Unicode (UTF-8)
+    pub const UTF8 : CodePage = CodePage(65001);
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)] enum Kind { SingleByte, DoubleByte, Unicode }
+use Kind::*;
+
+struct Known {
+    codepage:       CodePage,
+    constant:       &'static str,
+    name:           &'static str,
+    kind:           Kind,
+    description:    &'static str,
+}
+
+/// Code pages with names and descriptions, from <https://learn.microsoft.com/en-us/windows/win32/intl/code-page-identifiers>.
+const KNOWN : &[Known] = &[
+    Known { codepage: CodePage::IBM437,         constant: "IBM437",         name: "IBM437",         kind: SingleByte, description: "OEM United States" },
+    Known { codepage: CodePage::IBM737,         constant: "IBM737",         name: "ibm737",         kind: SingleByte, description: "OEM Greek (formerly 437G); Greek (DOS)" },
+    Known { codepage: CodePage::IBM775,         constant: "IBM775",         name: "ibm775",         kind: SingleByte, description: "OEM Baltic; Baltic (DOS)" },
+    Known { codepage: CodePage::IBM850,         constant: "IBM850",         name: "ibm850",         kind: SingleByte, description: "OEM Multilingual Latin 1; Western European (DOS)" },
+    Known { codepage: CodePage::IBM852,         constant: "IBM852",         name: "ibm852",         kind: SingleByte, description: "OEM Latin 2; Central European (DOS)" },
+    Known { codepage: CodePage::IBM855,         constant: "IBM855",         name: "IBM855",         kind: SingleByte, description: "OEM Cyrillic (primarily Russian)" },
+    Known { codepage: CodePage::IBM857,         constant: "IBM857",         name: "ibm857",         kind: SingleByte, description: "OEM Turkish; Turkish (DOS)" },
+    Known { codepage: CodePage::IBM00858,       constant: "IBM00858",       name: "IBM00858",       kind: SingleByte, description: "OEM Multilingual Latin 1 + Euro symbol" },
+    Known { codepage: CodePage::IBM860,         constant: "IBM860",         name: "IBM860",         kind: SingleByte, description: "OEM Portuguese; Portuguese (DOS)" },
+    Known { codepage: CodePage::IBM861,         constant: "IBM861",         name: "ibm861",         kind: SingleByte, description: "OEM Icelandic; Icelandic (DOS)" },
+    Known { codepage: CodePage::DOS_862,        constant: "DOS_862",        name: "DOS-862",        kind: SingleByte, description: "OEM Hebrew; Hebrew (DOS)" },
+    Known { codepage: CodePage::IBM863,         constant: "IBM863",         name: "IBM863",         kind: SingleByte, description: "OEM French Canadian; French Canadian (DOS)" },
+    Known { codepage: CodePage::IBM864,         constant: "IBM864",         name: "IBM864",         kind: SingleByte, description: "OEM Arabic; Arabic (864)" },
+    Known { codepage: CodePage::IBM865,         constant: "IBM865",         name: "IBM865",         kind: SingleByte, description: "OEM Nordic; Nordic (DOS)" },
+    Known { codepage: CodePage::CP866,          constant: "CP866",          name: "cp866",          kind: SingleByte, description: "OEM Russian; Cyrillic (DOS)" },
+    Known { codepage: CodePage::IBM869,         constant: "IBM869",         name: "ibm869",         kind: SingleByte, description: "OEM Modern Greek; Greek, Modern (DOS)" },
+    Known { codepage: CodePage::WINDOWS_874,    constant: "WINDOWS_874",    name: "windows-874",    kind: SingleByte, description: "Thai (Windows)" },
+    Known { codepage: CodePage::SHIFT_JIS,      constant: "SHIFT_JIS",      name: "shift_jis",      kind: DoubleByte, description: "ANSI/OEM Japanese; Japanese (Shift-JIS)" },
+    Known { codepage: CodePage::GB2312,         constant: "GB2312",         name: "gb2312",         kind: DoubleByte, description: "ANSI/OEM Simplified Chinese (PRC, Singapore); Chinese Simplified (GB2312)" },
+    Known { codepage: CodePage::KS_C_5601_1987, constant: "KS_C_5601_1987", name: "ks_c_5601-1987", kind: DoubleByte, description: "ANSI/OEM Korean (Unified Hangul Code)" },
+    Known { codepage: CodePage::BIG5,           constant: "BIG5",           name: "big5",           kind: DoubleByte, description: "ANSI/OEM Traditional Chinese (Taiwan; Hong Kong SAR, PRC); Chinese Traditional (Big5)" },
+    Known { codepage: CodePage::WINDOWS_1250,   constant: "WINDOWS_1250",   name: "windows-1250",   kind: SingleByte, description: "ANSI Central European; Central European (Windows)" },
+    Known { codepage: CodePage::WINDOWS_1251,   constant: "WINDOWS_1251",   name: "windows-1251",   kind: SingleByte, description: "ANSI Cyrillic; Cyrillic (Windows)" },
+    Known { codepage: CodePage::WINDOWS_1252,   constant: "WINDOWS_1252",   name: "windows-1252",   kind: SingleByte, description: "ANSI Latin 1; Western European (Windows)" },
+    Known { codepage: CodePage::WINDOWS_1253,   constant: "WINDOWS_1253",   name: "windows-1253",   kind: SingleByte, description: "ANSI Greek; Greek (Windows)" },
+    Known { codepage: CodePage::WINDOWS_1254,   constant: "WINDOWS_1254",   name: "windows-1254",   kind: SingleByte, description: "ANSI Turkish; Turkish (Windows)" },
+    Known { codepage: CodePage::WINDOWS_1255,   constant: "WINDOWS_1255",   name: "windows-1255",   kind: SingleByte, description: "ANSI Hebrew; Hebrew (Windows)" },
+    Known { codepage: CodePage::WINDOWS_1256,   constant: "WINDOWS_1256",   name: "windows-1256",   kind: SingleByte, description: "ANSI Arabic; Arabic (Windows)" },
+    Known { codepage: CodePage::WINDOWS_1257,   constant: "WINDOWS_1257",   name: "windows-1257",   kind: SingleByte, description: "ANSI Baltic; Baltic (Windows)" },
+    Known { codepage: CodePage::WINDOWS_1258,   constant: "WINDOWS_1258",   name: "windows-1258",   kind: SingleByte, description: "ANSI/OEM Vietnamese; Vietnamese (Windows)" },
+    Known { codepage: CodePage::UTF7,           constant: "UTF7",           name: "utf-7",          kind: Unicode,    description: "Unicode (UTF-7)" },
+    Known { codepage: CodePage::UTF8,           constant: "UTF8",           name: "utf-8",          kind: Unicode,    description: "Unicode (UTF-8)" },
+];
+
+impl CodePage {
+    /// \[~~microsoft.com~~\]
+    /// Returns the code pages this crate knows the names and descriptions of, in ascending order.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// assert!(CodePage::known().any(|cp| cp == CodePage::WINDOWS_1252));
+    /// assert!(CodePage::known().all(|cp| cp.name().is_some()));
+    /// ```
+    ///
+    pub fn known() -> impl Iterator<Item = CodePage> { KNOWN.iter().map(|known| known.codepage) }
+
+    fn known_info(self) -> Option<&'static Known> { KNOWN.iter().find(|known| known.codepage == self) }
+
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/intl/code-page-identifiers)\]
+    /// Returns the identifier name of a known code page (e.g. `"IBM437"`, `"windows-1252"`, `"utf-8"`), as listed by Microsoft.
+    pub fn name(self) -> Option<&'static str> { self.known_info().map(|known| known.name) }
+
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/intl/code-page-identifiers)\]
+    /// Returns the description of a known code page (e.g. `"OEM United States"`), as listed by Microsoft.
+    pub fn description(self) -> Option<&'static str> { self.known_info().map(|known| known.description) }
+
+    /// \[~~microsoft.com~~\]
+    /// Returns `true` if `self` is a known code page with exactly one byte per character (e.g. [`CodePage::IBM437`], [`CodePage::WINDOWS_1252`].)
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// assert!( CodePage::IBM437   .is_single_byte());
+    /// assert!(!CodePage::SHIFT_JIS.is_single_byte());
+    /// assert!(!CodePage::UTF8     .is_single_byte());
+    /// assert!(!CodePage::from(12345).is_single_byte()); // unknown
+    /// ```
+    ///
+    pub fn is_single_byte(self) -> bool { self.known_info().is_some_and(|known| known.kind == SingleByte) }
+
+    #[doc(alias = "IsDBCSLeadByteEx")]
+    /// \[~~microsoft.com~~\]
+    /// Returns `true` if `self` is a known double-byte character set, where characters are one or two bytes (e.g. [`CodePage::SHIFT_JIS`], [`CodePage::BIG5`].)
+    ///
+    /// Consoles using a DBCS code page mark the halves of full-width characters with [`COMMON_LVB_LEADING_BYTE`](crate::COMMON_LVB_LEADING_BYTE) / [`COMMON_LVB_TRAILING_BYTE`](crate::COMMON_LVB_TRAILING_BYTE).
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// assert!( CodePage::SHIFT_JIS.is_dbcs());
+    /// assert!(!CodePage::IBM437   .is_dbcs());
+    /// assert!(!CodePage::UTF8     .is_dbcs());
+    /// ```
+    ///
+    pub fn is_dbcs(self) -> bool { self.known_info().is_some_and(|known| known.kind == DoubleByte) }
+}
+
+impl CodePage {
+    /// Returns the characters of bytes `0x80 ..= 0xFF`, for code pages with built-in tables.
+    fn table(self) -> Option<&'static [char; 128]> {
+        Some(match self.0 {
+            437     => &tables::CP437,
+            850     => &tables::CP850,
+            852     => &tables::CP852,
+            866     => &tables::CP866,
+            1250    => &tables::CP1250,
+            1251    => &tables::CP1251,
+            1252    => &tables::CP1252,
+            1253    => &tables::CP1253,
+            1254    => &tables::CP1254,
+            1255    => &tables::CP1255,
+            1256    => &tables::CP1256,
+            1257    => &tables::CP1257,
+            1258    => &tables::CP1258,
+            _       => return None,
+        })
+    }
+
+    #[doc(alias = "MultiByteToWideChar")]
+    /// \[~~microsoft.com~~\]
+    /// Decodes `bytes` (e.g. from `ReadConsoleA`, a redirected file, or an old log) from this code page, without calling into Windows.
+    ///
+    /// *   [`CodePage::UTF8`] is decoded lossily (invalid sequences become U+FFFD.)
+    /// *   The OEM pages 437, 850, 852, and 866, and the ANSI pages 1250 ..= 1258, use built-in tables.
+    ///     Bytes a code page leaves undefined (e.g. `0x81` in 1252) become U+FFFD.
+    ///     Like `MultiByteToWideChar` without `MB_USEGLYPHCHARS`, bytes `0x00 ..= 0x1F` decode as control characters, not as the glyphs (e.g. `☺`) a console draws for them.
+    ///
+    /// Fails with [`io::ErrorKind::Unsupported`] for other code pages.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// assert_eq!(CodePage::IBM437      .decode(b"\xC9\xCD\xBB 100\xF8").unwrap(), "╔═╗ 100°");
+    /// assert_eq!(CodePage::WINDOWS_1252.decode(b"\x93caf\xE9\x94"    ).unwrap(), "“café”");
+    /// assert_eq!(CodePage::CP866       .decode(b"\x8F\xE0\xA8\xA2\xA5\xE2").unwrap(), "Привет");
+    /// assert!(CodePage::from(932).decode(b"").is_err());
+    /// ```
+    ///
+    pub fn decode(self, bytes: &[u8]) -> io::Result<String> {
+        if self == Self::UTF8 { return Ok(String::from_utf8_lossy(bytes).into_owned()) }
+        let table = self.table().ok_or_else(|| io::Error::new(io::ErrorKind::Unsupported, "CodePage::decode(): no built-in table for this code page"))?;
+        Ok(bytes.iter().map(|&b| if b < 0x80 { char::from(b) } else { table[usize::from(b - 0x80)] }).collect())
+    }
+
+    #[doc(alias = "WideCharToMultiByte")]
+    /// \[~~microsoft.com~~\]
+    /// Encodes `text` into this code page (e.g. for `WriteConsoleA`, or a file read by legacy tools), without calling into Windows.
+    ///
+    /// Supports the same code pages as [`CodePage::decode`].  Characters the code page can't represent become `?`, as they do with `WideCharToMultiByte`'s default character
+    /// (although no "best fit" mappings, such as `é` to `e`, are attempted.)
+    ///
+    /// Fails with [`io::ErrorKind::Unsupported`] for other code pages.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// assert_eq!(CodePage::IBM437      .encode("╔═╗ 100°").unwrap(), b"\xC9\xCD\xBB 100\xF8");
+    /// assert_eq!(CodePage::WINDOWS_1252.encode("€5 ☃"    ).unwrap(), b"\x805 ?");
+    /// assert_eq!(CodePage::UTF8        .encode("☃"       ).unwrap(), "☃".as_bytes());
+    /// ```
+    ///
+    pub fn encode(self, text: &str) -> io::Result<Vec<u8>> {
+        if self == Self::UTF8 { return Ok(text.as_bytes().to_vec()) }
+        let table = self.table().ok_or_else(|| io::Error::new(io::ErrorKind::Unsupported, "CodePage::encode(): no built-in table for this code page"))?;
+        Ok(text.chars().map(|ch| match u8::try_from(ch) {
+            Ok(b) if b < 0x80   => b,
+            _                   => table.iter().position(|&c| c == ch && c != char::REPLACEMENT_CHARACTER).map_or(b'?', |i| 0x80 + i as u8),
+        }).collect())
+    }
+}
+
+impl Debug for CodePage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.known_info() {
+            Some(known) => write!(f, "CodePage::{} ({})", known.constant, known.description),
+            None        => write!(f, "CodePage({})", self.0),
+        }
+    }
+}
+
+/// Parses a code page's identifier name or constant name (e.g. `"IBM437"`, `"windows-1252"`, `"utf-8"`, `"UTF8"`, case insensitive),
+/// or a number with an optional `cp`, `ibm`, `windows-`, or `dos-` prefix (e.g. `"cp437"`, `"ibm850"`, `"65001"`.)
+///
+/// ### Example
+/// ```
+/// # use maulingmonkey_console_winapi_wrappers::*;
+/// assert_eq!("cp437"       .parse::<CodePage>().unwrap(), CodePage::IBM437);
+/// assert_eq!("IBM850"      .parse::<CodePage>().unwrap(), CodePage::IBM850);
+/// assert_eq!("utf-8"       .parse::<CodePage>().unwrap(), CodePage::UTF8);
+/// assert_eq!("Windows-1252".parse::<CodePage>().unwrap(), CodePage::WINDOWS_1252);
+/// assert_eq!("Shift_JIS"   .parse::<CodePage>().unwrap(), CodePage::SHIFT_JIS);
+/// assert_eq!("20127"       .parse::<CodePage>().unwrap(), CodePage::from(20127));
+/// assert!("latin-1".parse::<CodePage>().is_err());
+/// ```
+///
+impl FromStr for CodePage {
+    type Err = io::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(known) = KNOWN.iter().find(|known| known.name.eq_ignore_ascii_case(s) || known.constant.eq_ignore_ascii_case(s)) {
+            return Ok(known.codepage);
+        }
+        let s = s.to_ascii_lowercase();
+        ["cp", "ibm", "windows-", "dos-", ""].iter()
+            .filter_map(|prefix| s.strip_prefix(prefix))
+            .find(|digits| !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|digits| digits.parse().ok())
+            .map(CodePage)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "CodePage::from_str(): unrecognized code page name"))
+    }
+}
+
+impl From<u32> for CodePage { fn from(value: u32) -> Self { Self(value) } }
+impl From<CodePage> for u32 { fn from(value: CodePage) -> Self { value.0 } }
+
+
+
+#[doc(alias = "GetConsoleCP")]
+/// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/getconsolecp)\]
+/// Retrieves the current console's input code page.
+///
+/// A console uses its input code page to translate keyboard input into the corresponding character value.
+///
+/// ### Example
+/// ```no_run
+/// # use maulingmonkey_console_winapi_wrappers::*;
+/// assert_eq!(get_console_input_cp().unwrap(), CodePage::IBM437);
+/// ```
+///
+#[cfg(windows)]
+pub fn get_console_input_cp() -> io::Result<CodePage> {
+    match unsafe { GetConsoleCP() } {
+        0 => Err(io::Error::last_os_error()),
+        n => Ok(CodePage(n)),
+    }
+}
+
+#[doc(alias = "GetConsoleOutputCP")]
+/// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/getconsoleoutputcp)\]
+/// Retrieves the current console's output code page.
+///
+/// A console uses its output code page to translate the character values written by the various output functions into the images displayed in the console window.
+///
+/// ### Example
+/// ```no_run
+/// # use maulingmonkey_console_winapi_wrappers::*;
+/// assert_eq!(get_console_output_cp().unwrap(), CodePage::IBM437);
+/// ```
+///
+#[cfg(windows)]
+pub fn get_console_output_cp() -> io::Result<CodePage> {
+    match unsafe { GetConsoleOutputCP() } {
+        0 => Err(io::Error::last_os_error()),
+        n => Ok(CodePage(n)),
+    }
+}
+
+#[doc(alias = "SetConsoleCP")]
+/// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/setconsolecp)\]
+/// Sets the input code page of the current console.
+///
+/// A console uses its input code page to translate keyboard input into the corresponding character value.
+///
+/// ### Example
+/// ```
+/// # use maulingmonkey_console_winapi_wrappers::*;
+/// # let _scope = InputCodePageScope::new(None);
+/// set_console_input_cp(437).unwrap();
+/// set_console_input_cp(CodePage::IBM437).unwrap();
+/// set_console_input_cp(CodePage::from(437)).unwrap();
+/// ```
+///
+#[cfg(windows)]
+pub fn set_console_input_cp(codepage: impl Into<CodePage>) -> io::Result<()> {
+    succeeded_to_result(unsafe{SetConsoleCP(codepage.into().0)})
+}
+
+#[doc(alias = "SetConsoleOutputCP")]
+/// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/setconsoleoutputcp)\]
+/// Sets the output code page of the current console.
+///
+/// A console uses its output code page to translate the character values written by the various output functions into the images displayed in the console window.
+///
+/// ### Example
+/// ```
+/// # use maulingmonkey_console_winapi_wrappers::*;
+/// # let _scope = OutputCodePageScope::new(None);
+/// set_console_output_cp(437).unwrap();
+/// set_console_output_cp(CodePage::IBM437).unwrap();
+/// set_console_output_cp(CodePage::from(437)).unwrap();
+/// ```
+///
+#[cfg(windows)]
+pub fn set_console_output_cp(codepage: impl Into<CodePage>) -> io::Result<()> {
+    succeeded_to_result(unsafe{SetConsoleOutputCP(codepage.into().0)})
+}
+
+
+
+/// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/setconsolecp)\]
+/// Sets the input code page, then restores the previous code page on drop.
+///
+/// ### Example
+/// ```
+/// # use maulingmonkey_console_winapi_wrappers::*;
+/// let _scope = InputCodePageScope::new(None);
+/// let _scope = InputCodePageScope::new(CodePage::from(437));
+/// let _scope = InputCodePageScope::new(CodePage::IBM437);
+/// let _scope = InputCodePageScope::new(Some(CodePage::IBM437));
+/// ```
+///
+/// ### See Also
+/// *   [`set_console_input_cp`] - impl fn
+/// *   [`get_console_input_cp`] - impl fn
+/// *   [`OutputCodePageScope`] - twin
+///
+#[cfg(windows)]
+#[derive(Debug)] pub struct InputCodePageScope  { old: CodePage }
+
+/// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/setconsoleoutputcp)\]
+/// Sets the output code page, then restores the previous code page on drop.
+///
+/// ### Example
+/// ```
+/// # use maulingmonkey_console_winapi_wrappers::*;
+/// let _scope = OutputCodePageScope::new(None);
+/// let _scope = OutputCodePageScope::new(CodePage::from(437));
+/// let _scope = OutputCodePageScope::new(CodePage::IBM437);
+/// let _scope = OutputCodePageScope::new(Some(CodePage::IBM437));
+/// ```
+///
+/// ### See Also
+/// *   [`set_console_output_cp`] - impl fn
+/// *   [`get_console_output_cp`] - impl fn
+/// *   [`InputCodePageScope`] - twin
+///
+#[cfg(windows)]
+#[derive(Debug)] pub struct OutputCodePageScope { old: CodePage }
+
+#[cfg(windows)]
+impl InputCodePageScope {
+    #[doc(alias = "GetConsoleCP")]
+    #[doc(alias = "SetConsoleCP")]
+    pub fn new(new: impl Into<Option<CodePage>>) -> io::Result<Self> {
+        let scope = Self { old: get_console_input_cp()? };
+        if let Some(new) = new.into() {
+            set_console_input_cp(new)?;
+        }
+        Ok(scope)
+    }
+}
+
+#[cfg(windows)]
+impl OutputCodePageScope {
+    #[doc(alias = "GetConsoleOutputCP")]
+    #[doc(alias = "SetConsoleOutputCP")]
+    pub fn new(new: impl Into<Option<CodePage>>) -> io::Result<Self> {
+        let scope = Self { old: get_console_output_cp()? };
+        if let Some(new) = new.into() {
+            set_console_output_cp(new)?;
+        }
+        Ok(scope)
+    }
+}
+
+#[cfg(windows)]
+impl Drop for InputCodePageScope {
+    #[doc(alias = "SetConsoleCP")]
+    fn drop(&mut self) {
+        let result = set_console_input_cp(self.old);
+        if cfg!(debug_assertions) {
+            result.expect("Unable to restore input code page after InputCodePageScope");
+        }
+    }
+}
+
+#[cfg(windows)]
+impl Drop for OutputCodePageScope {
+    #[doc(alias = "SetConsoleOutputCP")]
+    fn drop(&mut self) {
+        let result = set_console_output_cp(self.old);
+        if cfg!(debug_assertions) {
+            result.expect("Unable to restore output code page after OutputCodePageScope");
+        }
+    }
+}
+
+
+
+#[test] fn transcode() {
+    for codepage in [437, 850, 852, 866, 1250, 1251, 1252, 1253, 1254, 1255, 1256, 1257, 1258].map(CodePage::from) {
+        let bytes : Vec<u8> = (0 ..= 255).collect();
+        let text = codepage.decode(&bytes).unwrap();
+        let round_trip = codepage.encode(&text).unwrap();
+        for (b, (ch, rt)) in bytes.iter().copied().zip(text.chars().zip(round_trip.iter().copied())) {
+            if ch == char::REPLACEMENT_CHARACTER {
+                assert_eq!(rt, b'?', "{codepage:?} 0x{b:02X}");
+            } else {
+                assert_eq!(rt, b, "{codepage:?} 0x{b:02X} {ch:?}");
+            }
+        }
+        assert_eq!(text.chars().count(), 256);
+    }
+    assert_eq!(CodePage::UTF8.decode(b"a\xFFb").unwrap(), "a\u{FFFD}b");
+    assert_eq!(CodePage::UTF7.encode("a").unwrap_err().kind(), io::ErrorKind::Unsupported);
+}
+
+#[test] fn names() {
+    let mut previous = None;
+    for codepage in CodePage::known() {
+        assert!(previous < Some(codepage));
+        previous = Some(codepage);
+        assert_eq!(codepage.name().unwrap().parse::<CodePage>().unwrap(), codepage);
+        assert_eq!(format!("cp{}", codepage.0).parse::<CodePage>().unwrap(), codepage);
+        assert!(!(codepage.is_single_byte() && codepage.is_dbcs()));
+        if codepage.table().is_some() { assert!(codepage.is_single_byte() || codepage == CodePage::UTF8); }
+    }
+    assert_eq!(format!("{:?}", CodePage::IBM437), "CodePage::IBM437 (OEM United States)");
+    assert_eq!(format!("{:?}", CodePage::WINDOWS_1252), "CodePage::WINDOWS_1252 (ANSI Latin 1; Western European (Windows))");
+    assert_eq!(format!("{:?}", CodePage::from(12345)), "CodePage(12345)");
+    assert_eq!("ibm00858".parse::<CodePage>().unwrap(), CodePage::IBM00858);
+    assert_eq!("ks_c_5601-1987".parse::<CodePage>().unwrap(), CodePage::KS_C_5601_1987);
+    assert!("".parse::<CodePage>().is_err());
+    assert!("cp".parse::<CodePage>().is_err());
+    assert!("cp99999999999".parse::<CodePage>().is_err());
+    assert_eq!(CodePage::from(12345).name(), None);
+}
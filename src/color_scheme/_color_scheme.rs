@@ -0,0 +1,194 @@
+//! \[~~microsoft.com~~\]
+//! Named console color tables, and converting them to (and from) other formats.
+
+use crate::*;
+
+use std::borrow::Cow;
+use std::io;
+use core::str::FromStr;
+
+mod quantize;
+mod reg;
+mod windows_terminal;
+
+
+
+/// \[~~microsoft.com~~\]
+/// A named console color table, with the screen and popup [`Attributes`] meant to be used with it.
+///
+/// The well-known schemes shipped with conhost and Windows Terminal are available as associated constants (e.g. [`ColorScheme::CAMPBELL`]),
+/// and by name via [`ColorScheme::from_name`] / [`str::parse`].
+///
+/// `color_table` is in console order (index `1` is blue, `4` is red, matching [`FOREGROUND_BLUE`] and [`FOREGROUND_RED`]),
+/// which differs from the ANSI order used by e.g. Windows Terminal's `settings.json` (where index `1` is red, `4` is blue.)
+///
+/// ### Example
+/// ```
+/// # use maulingmonkey_console_winapi_wrappers::*;
+/// let mut info = MemoryScreenBuffer::new((80, 25)).get_console_screen_buffer_info_ex().unwrap();
+/// let scheme : ColorScheme = "one half dark".parse().unwrap();
+/// scheme.apply_to(&mut info);
+/// assert_eq!(info.color_table[1], ColorRef::from_rgb([0x61, 0xAF, 0xEF])); // blue
+/// assert_eq!(info.attributes, FOREGROUND_RED | FOREGROUND_GREEN | FOREGROUND_BLUE);
+/// ```
+///
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ColorScheme {
+    /// The display name of the scheme (e.g. `"One Half Dark"`.)
+    pub name:               Cow<'static, str>,
+
+    /// The 16 colors [`Attributes`] index, in console order.
+    pub color_table:        [ColorRef; 16],
+
+    /// The default foreground and background colors of text (e.g. [`ConsoleScreenBufferInfoEx::attributes`].)
+    pub attributes:         Attributes,
+
+    /// The foreground and background colors of popups, such as the F7 command history (e.g. [`ConsoleScreenBufferInfoEx::popup_attributes`].)
+    pub popup_attributes:   Attributes,
+}
+
+impl ColorScheme {
+    /// The VGA palette used by conhost before Windows 10 (1709), and still used when "Use legacy console" is checked.  Light gray on black.
+    pub const LEGACY : ColorScheme = ColorScheme::ansi("Legacy", [
+        0x000000, 0x800000, 0x008000, 0x808000, 0x000080, 0x800080, 0x008080, 0xC0C0C0,
+        0x808080, 0xFF0000, 0x00FF00, 0xFFFF00, 0x0000FF, 0xFF00FF, 0x00FFFF, 0xFFFFFF,
+    ], 0x07, 0xF5);
+
+    /// The default color table of conhost since Windows 10 (1709), and of Windows Terminal.  Light gray on black.
+    pub const CAMPBELL : ColorScheme = ColorScheme::ansi("Campbell", [
+        0x0C0C0C, 0xC50F1F, 0x13A10E, 0xC19C00, 0x0037DA, 0x881798, 0x3A96DD, 0xCCCCCC,
+        0x767676, 0xE74856, 0x16C60C, 0xF9F1A5, 0x3B78FF, 0xB4009E, 0x61D6D6, 0xF2F2F2,
+    ], 0x07, 0xF5);
+
+    /// Campbell as set up by the Windows PowerShell shortcut, which repurposes dark magenta as its blue background (`#012456`)
+    /// and dark yellow as its off-white foreground (`#EEEDF0`.)
+    pub const CAMPBELL_POWERSHELL : ColorScheme = ColorScheme::ansi("Campbell Powershell", [
+        0x0C0C0C, 0xC50F1F, 0x13A10E, 0xEEEDF0, 0x0037DA, 0x012456, 0x3A96DD, 0xCCCCCC,
+        0x767676, 0xE74856, 0x16C60C, 0xF9F1A5, 0x3B78FF, 0xB4009E, 0x61D6D6, 0xF2F2F2,
+    ], 0x56, 0xF3);
+
+    /// Windows Terminal's One Half Dark.  Light gray on dark gray.
+    pub const ONE_HALF_DARK : ColorScheme = ColorScheme::ansi("One Half Dark", [
+        0x282C34, 0xE06C75, 0x98C379, 0xE5C07B, 0x61AFEF, 0xC678DD, 0x56B6C2, 0xDCDFE4,
+        0x5A6374, 0xE06C75, 0x98C379, 0xE5C07B, 0x61AFEF, 0xC678DD, 0x56B6C2, 0xDCDFE4,
+    ], 0x07, 0xF5);
+
+    /// Windows Terminal's One Half Light.  Dark gray on off-white.
+    pub const ONE_HALF_LIGHT : ColorScheme = ColorScheme::ansi("One Half Light", [
+        0x383A42, 0xE45649, 0x50A14F, 0xC18301, 0x0184BC, 0xA626A4, 0x0997B3, 0xFAFAFA,
+        0x4F525D, 0xDF6C75, 0x98C379, 0xE4C07A, 0x61AFEF, 0xC577DD, 0x56B5C1, 0xFFFFFF,
+    ], 0x70, 0x07);
+
+    /// Windows Terminal's Solarized Dark.  Base0 on base03.
+    pub const SOLARIZED_DARK : ColorScheme = ColorScheme::ansi("Solarized Dark", [
+        0x002B36, 0xDC322F, 0x859900, 0xB58900, 0x268BD2, 0xD33682, 0x2AA198, 0xEEE8D5,
+        0x073642, 0xCB4B16, 0x586E75, 0x657B83, 0x839496, 0x6C71C4, 0x93A1A1, 0xFDF6E3,
+    ], 0x09, 0xF5);
+
+    /// Windows Terminal's Solarized Light.  Base00 on base3.
+    pub const SOLARIZED_LIGHT : ColorScheme = ColorScheme::ansi("Solarized Light", [
+        0x002B36, 0xDC322F, 0x859900, 0xB58900, 0x268BD2, 0xD33682, 0x2AA198, 0xEEE8D5,
+        0x073642, 0xCB4B16, 0x586E75, 0x657B83, 0x839496, 0x6C71C4, 0x93A1A1, 0xFDF6E3,
+    ], 0xFE, 0xEF);
+
+    /// Windows Terminal's Tango Dark.  Light gray on black.
+    pub const TANGO_DARK : ColorScheme = ColorScheme::ansi("Tango Dark", [
+        0x000000, 0xCC0000, 0x4E9A06, 0xC4A000, 0x3465A4, 0x75507B, 0x06989A, 0xD3D7CF,
+        0x555753, 0xEF2929, 0x8AE234, 0xFCE94F, 0x729FCF, 0xAD7FA8, 0x34E2E2, 0xEEEEEC,
+    ], 0x07, 0xF5);
+
+    /// Windows Terminal's Tango Light.  Dark gray on bright white (`#EEEEEC`, the closest table entry to Windows Terminal's `#FFFFFF` background.)
+    pub const TANGO_LIGHT : ColorScheme = ColorScheme::ansi("Tango Light", [
+        0x000000, 0xCC0000, 0x4E9A06, 0xC4A000, 0x3465A4, 0x75507B, 0x06989A, 0xD3D7CF,
+        0x555753, 0xEF2929, 0x8AE234, 0xFCE94F, 0x729FCF, 0xAD7FA8, 0x34E2E2, 0xEEEEEC,
+    ], 0xF8, 0x8F);
+
+    /// Windows Terminal's Vintage: the same VGA palette as [`ColorScheme::LEGACY`].  Light gray on black.
+    pub const VINTAGE : ColorScheme = ColorScheme::ansi("Vintage", [
+        0x000000, 0x800000, 0x008000, 0x808000, 0x000080, 0x800080, 0x008080, 0xC0C0C0,
+        0x808080, 0xFF0000, 0x00FF00, 0xFFFF00, 0x0000FF, 0xFF00FF, 0x00FFFF, 0xFFFFFF,
+    ], 0x07, 0xF5);
+
+    /// Every well-known scheme above.
+    pub const ALL : &'static [ColorScheme] = &[
+        Self::LEGACY,
+        Self::CAMPBELL,
+        Self::CAMPBELL_POWERSHELL,
+        Self::ONE_HALF_DARK,
+        Self::ONE_HALF_LIGHT,
+        Self::SOLARIZED_DARK,
+        Self::SOLARIZED_LIGHT,
+        Self::TANGO_DARK,
+        Self::TANGO_LIGHT,
+        Self::VINTAGE,
+    ];
+
+    /// Builds a scheme from `0xRRGGBB` colors in ANSI order (black, red, green, yellow, blue, magenta, cyan, white, then the bright variants.)
+    const fn ansi(name: &'static str, rgb: [u32; 16], attributes: u16, popup_attributes: u16) -> Self {
+        let mut color_table = [ColorRef::from_rgb([0, 0, 0]); 16];
+        let mut i = 0;
+        while i < 16 {
+            let [_, r, g, b] = rgb[ansi_index(i)].to_be_bytes();
+            color_table[i] = ColorRef::from_rgb([r, g, b]);
+            i += 1;
+        }
+        Self { name: Cow::Borrowed(name), color_table, attributes: Attributes::from_bits(attributes), popup_attributes: Attributes::from_bits(popup_attributes) }
+    }
+
+    /// Looks up one of the well-known schemes in [`ColorScheme::ALL`] by name.
+    ///
+    /// Matching ignores case, spaces, `-`, and `_`, so `"One Half Dark"`, `"one-half-dark"`, and `"ONE_HALF_DARK"` are all equivalent.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// assert_eq!(ColorScheme::from_name("campbell"), Some(&ColorScheme::CAMPBELL));
+    /// assert_eq!(ColorScheme::from_name("Solarized_Light"), Some(&ColorScheme::SOLARIZED_LIGHT));
+    /// assert_eq!(ColorScheme::from_name("Solarized"), None);
+    /// ```
+    ///
+    pub fn from_name(name: &str) -> Option<&'static ColorScheme> {
+        let key = |s: &str| s.chars().filter(|c| !matches!(c, ' ' | '-' | '_')).map(|c| c.to_ascii_lowercase()).collect::<String>();
+        let name = key(name);
+        Self::ALL.iter().find(|scheme| key(&scheme.name) == name)
+    }
+
+    /// Copies the scheme's colors and attributes into `info`, e.g. to pass along to `SetConsoleScreenBufferInfoEx`.
+    ///
+    /// Only `color_table`, `attributes`, and `popup_attributes` are modified.
+    /// Note that existing cells keep their attributes: only their colors change, via the new `color_table`.
+    pub fn apply_to(&self, info: &mut ConsoleScreenBufferInfoEx) {
+        info.color_table        = self.color_table;
+        info.attributes         = self.attributes;
+        info.popup_attributes   = self.popup_attributes;
+    }
+}
+
+/// Converts a console color index (blue = `1`, red = `4`) to the equivalent ANSI color index (red = `1`, blue = `4`), or vice versa.
+const fn ansi_index(index: usize) -> usize { (index & 0b1010) | ((index & 0b0001) << 2) | ((index & 0b0100) >> 2) }
+
+impl Default for ColorScheme { fn default() -> Self { Self::CAMPBELL } }
+
+/// Parses a well-known scheme name.  See [`ColorScheme::from_name`].
+impl FromStr for ColorScheme {
+    type Err = io::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_name(s).cloned().ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "ColorScheme::from_str(): unrecognized color scheme name"))
+    }
+}
+
+
+
+#[test] fn names() {
+    for scheme in ColorScheme::ALL {
+        assert_eq!(scheme.name.parse::<ColorScheme>().unwrap(), *scheme);
+        let fg = scheme.color_table[usize::from(u16::from(scheme.attributes) & 0xF)];
+        let bg = scheme.color_table[usize::from(u16::from(scheme.attributes) >> 4 & 0xF)];
+        assert_ne!(fg, bg, "{}", scheme.name);
+    }
+    assert_eq!(ColorScheme::CAMPBELL.color_table[1], ColorRef::from_rgb([0x00, 0x37, 0xDA])); // blue
+    assert_eq!(ColorScheme::CAMPBELL.color_table[4], ColorRef::from_rgb([0xC5, 0x0F, 0x1F])); // red
+    assert_eq!(ColorScheme::CAMPBELL.color_table[6], ColorRef::from_rgb([0xC1, 0x9C, 0x00])); // yellow
+    assert_eq!(ColorScheme::LEGACY.color_table, ColorScheme::VINTAGE.color_table);
+    assert!("".parse::<ColorScheme>().is_err());
+}
@@ -0,0 +1,144 @@
+use super::*;
+
+
+
+impl ColorRef {
+    /// \[~~microsoft.com~~\]
+    /// Returns xterm's default color for 256-color index `index` (e.g. from `ESC[38;5;{index}m`.)
+    ///
+    /// | Index         | Colors |
+    /// | ------------- | ------ |
+    /// | `0 ..= 15`    | xterm's default 16 color palette (ANSI order)
+    /// | `16 ..= 231`  | A 6×6×6 color cube: `16 + 36×r + 6×g + b`, with each component in `[0, 95, 135, 175, 215, 255]`
+    /// | `232 ..= 255` | A 24 step grayscale ramp, from `#080808` to `#EEEEEE`
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// assert_eq!(ColorRef::from_xterm_256(  9), ColorRef::from_rgb([255,   0,   0]));
+    /// assert_eq!(ColorRef::from_xterm_256(208), ColorRef::from_rgb([255, 135,   0]));
+    /// assert_eq!(ColorRef::from_xterm_256(244), ColorRef::from_rgb([128, 128, 128]));
+    /// ```
+    ///
+    pub const fn from_xterm_256(index: u8) -> Self {
+        const PALETTE : [[u8; 3]; 16] = [
+            [0x00, 0x00, 0x00], [0xCD, 0x00, 0x00], [0x00, 0xCD, 0x00], [0xCD, 0xCD, 0x00], [0x00, 0x00, 0xEE], [0xCD, 0x00, 0xCD], [0x00, 0xCD, 0xCD], [0xE5, 0xE5, 0xE5],
+            [0x7F, 0x7F, 0x7F], [0xFF, 0x00, 0x00], [0x00, 0xFF, 0x00], [0xFF, 0xFF, 0x00], [0x5C, 0x5C, 0xFF], [0xFF, 0x00, 0xFF], [0x00, 0xFF, 0xFF], [0xFF, 0xFF, 0xFF],
+        ];
+        const CUBE : [u8; 6] = [0, 95, 135, 175, 215, 255];
+        match index {
+            0   ..= 15  => Self::from_rgb(PALETTE[index as usize]),
+            16  ..= 231 => { let i = (index - 16) as usize; Self::from_rgb([CUBE[i / 36], CUBE[i / 6 % 6], CUBE[i % 6]]) },
+            232 ..= 255 => { let gray = 8 + 10 * (index - 232); Self::from_rgb([gray, gray, gray]) },
+        }
+    }
+}
+
+impl Attributes {
+    /// \[~~microsoft.com~~\]
+    /// Returns the foreground color bits ([`FOREGROUND_RED`], ..., [`FOREGROUND_INTENSITY`]) of the `color_table` entry perceptually closest to `color`.
+    ///
+    /// Colors are compared by their distance in the [Oklab](https://bottosson.github.io/posts/oklab/) color space rather than by raw RGB,
+    /// so e.g. truecolor content degrades to the palette entry that *looks* most similar.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let campbell = &ColorScheme::CAMPBELL.color_table;
+    /// let orange = ColorRef::from_rgb([0xFF, 0x80, 0x00]);
+    /// assert_eq!(Attributes::foreground_from_rgb(campbell, orange), FOREGROUND_RED | FOREGROUND_GREEN);
+    /// assert_eq!(Attributes::background_from_rgb(campbell, orange), BACKGROUND_RED | BACKGROUND_GREEN);
+    /// assert_eq!(Attributes::foreground_from_rgb(campbell, ColorRef::from_rgb([0, 0, 0])), Attributes::default());
+    /// ```
+    ///
+    pub fn foreground_from_rgb(color_table: &[ColorRef; 16], color: ColorRef) -> Self {
+        Self::from(nearest_index(color_table, color) as u16)
+    }
+
+    /// \[~~microsoft.com~~\]
+    /// Returns the background color bits ([`BACKGROUND_RED`], ..., [`BACKGROUND_INTENSITY`]) of the `color_table` entry perceptually closest to `color`.
+    /// See [`Attributes::foreground_from_rgb`].
+    pub fn background_from_rgb(color_table: &[ColorRef; 16], color: ColorRef) -> Self {
+        Self::from((nearest_index(color_table, color) as u16) << 4)
+    }
+
+    /// \[~~microsoft.com~~\]
+    /// Returns the foreground color bits for xterm 256-color index `index` (e.g. from `ESC[38;5;{index}m`.)
+    ///
+    /// Indices `0 ..= 15` name palette entries, and map directly to the corresponding `color_table` entry (whatever color it is.)
+    /// Other indices are converted with [`ColorRef::from_xterm_256`], then matched with [`Attributes::foreground_from_rgb`].
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let campbell = &ColorScheme::CAMPBELL.color_table;
+    /// assert_eq!(Attributes::foreground_from_xterm_256(campbell,   4), FOREGROUND_BLUE);
+    /// assert_eq!(Attributes::foreground_from_xterm_256(campbell,  12), FOREGROUND_BLUE | FOREGROUND_INTENSITY);
+    /// assert_eq!(Attributes::foreground_from_xterm_256(campbell,  34), FOREGROUND_GREEN); // #00AF00
+    /// assert_eq!(Attributes::foreground_from_xterm_256(campbell, 255), FOREGROUND_RED | FOREGROUND_GREEN | FOREGROUND_BLUE | FOREGROUND_INTENSITY);
+    /// ```
+    ///
+    pub fn foreground_from_xterm_256(color_table: &[ColorRef; 16], index: u8) -> Self {
+        Self::from(xterm_256_index(color_table, index) as u16)
+    }
+
+    /// \[~~microsoft.com~~\]
+    /// Returns the background color bits for xterm 256-color index `index` (e.g. from `ESC[48;5;{index}m`.)
+    /// See [`Attributes::foreground_from_xterm_256`].
+    pub fn background_from_xterm_256(color_table: &[ColorRef; 16], index: u8) -> Self {
+        Self::from((xterm_256_index(color_table, index) as u16) << 4)
+    }
+}
+
+/// Returns the console color index (`0 .. 16`) for xterm 256-color index `index`.
+fn xterm_256_index(color_table: &[ColorRef; 16], index: u8) -> usize {
+    match index {
+        0 ..= 15    => ansi_index(usize::from(index)),
+        _           => nearest_index(color_table, ColorRef::from_xterm_256(index)),
+    }
+}
+
+/// Returns the index of the color in `color_table` perceptually closest to `color`, preferring lower indices on ties.
+pub(super) fn nearest_index(color_table: &[ColorRef; 16], color: ColorRef) -> usize {
+    let target = oklab(color);
+    let distance = |c: ColorRef| oklab(c).iter().zip(target).map(|(a, b)| (a - b) * (a - b)).sum::<f64>();
+    (0 .. 16).map(|i| (i, distance(color_table[i]))).fold((0, f64::INFINITY), |best, next| if next.1 < best.1 { next } else { best }).0
+}
+
+/// Converts an sRGB color to [Oklab](https://bottosson.github.io/posts/oklab/) `[L, a, b]`.
+fn oklab(color: ColorRef) -> [f64; 3] {
+    let linear = |c: u8| {
+        let c = f64::from(c) / 255.0;
+        if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+    };
+    let [r, g, b] = color.to_rgb().map(linear);
+    let l = (0.412_221_470_8 * r + 0.536_332_536_3 * g + 0.051_445_992_9 * b).cbrt();
+    let m = (0.211_903_498_2 * r + 0.680_699_545_1 * g + 0.107_396_956_6 * b).cbrt();
+    let s = (0.088_302_461_9 * r + 0.281_718_837_6 * g + 0.629_978_700_5 * b).cbrt();
+    [
+        0.210_454_255_3 * l + 0.793_617_785_0 * m - 0.004_072_046_8 * s,
+        1.977_998_495_1 * l - 2.428_592_205_0 * m + 0.450_593_709_9 * s,
+        0.025_904_037_1 * l + 0.782_771_766_2 * m - 0.808_675_776_0 * s,
+    ]
+}
+
+
+
+#[test] fn quantize() {
+    for scheme in ColorScheme::ALL {
+        for (i, color) in scheme.color_table.iter().copied().enumerate() {
+            // Exact matches win (the lowest index, for schemes with duplicate entries)
+            let fg = u16::from(Attributes::foreground_from_rgb(&scheme.color_table, color));
+            assert_eq!(scheme.color_table[usize::from(fg)], color, "{}", scheme.name);
+            assert!(usize::from(fg) <= i);
+            assert_eq!(u16::from(Attributes::background_from_rgb(&scheme.color_table, color)), fg << 4);
+        }
+    }
+    for index in 0 ..= 255 {
+        assert_eq!(u16::from(Attributes::background_from_xterm_256(&ColorScheme::LEGACY.color_table, index)) >> 4, u16::from(Attributes::foreground_from_xterm_256(&ColorScheme::LEGACY.color_table, index)));
+    }
+    assert_eq!(Attributes::foreground_from_xterm_256(&ColorScheme::LEGACY.color_table, 196), FOREGROUND_RED | FOREGROUND_INTENSITY); // #FF0000
+    assert_eq!(ColorRef::from_xterm_256(231), ColorRef::from_rgb([255, 255, 255]));
+    assert_eq!(ColorRef::from_xterm_256(232), ColorRef::from_rgb([8, 8, 8]));
+    assert_eq!(ColorRef::from_xterm_256(255), ColorRef::from_rgb([238, 238, 238]));
+}
@@ -0,0 +1,135 @@
+use super::*;
+
+use core::fmt::Write;
+
+
+
+impl ColorScheme {
+    /// \[~~microsoft.com~~\]
+    /// Parses the console colors of a registry export (`.reg` file text), such as those shared as conhost themes.
+    ///
+    /// *   `"ColorTable00"` through `"ColorTable15"` are `COLORREF`s (`dword:00bbggrr`), in console order.
+    /// *   `"ScreenColors"` and `"PopupColors"` become `attributes` and `popup_attributes`.
+    /// *   Colors and attributes not mentioned keep their [`ColorScheme::CAMPBELL`] defaults, and `name` is `""`.
+    /// *   Values are read from every key in the file (later ones winning), and other values (e.g. `"FaceName"`) are ignored.
+    /// *   Value names are case insensitive, as they are in the registry.
+    ///
+    /// Only the text is parsed: the registry itself is neither read nor written.
+    /// `.reg` files are often UTF-16: decode them first (a leading byte order mark is ignored.)
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let scheme = ColorScheme::from_reg(r#"Windows Registry Editor Version 5.00
+    ///
+    /// [HKEY_CURRENT_USER\Console]
+    /// "ColorTable00"=dword:00562401
+    /// "ColorTable07"=dword:00f0edee
+    /// "ScreenColors"=dword:00000007
+    /// "FaceName"="Consolas"
+    /// "#).unwrap();
+    /// assert_eq!(scheme.color_table[0], ColorRef::from_rgb([0x01, 0x24, 0x56]));
+    /// assert_eq!(scheme.color_table[1], ColorScheme::CAMPBELL.color_table[1]);
+    /// assert_eq!(scheme.attributes, FOREGROUND_RED | FOREGROUND_GREEN | FOREGROUND_BLUE);
+    /// ```
+    ///
+    pub fn from_reg(text: &str) -> io::Result<Self> {
+        let err = |line_no: usize, msg: &str| io::Error::new(io::ErrorKind::InvalidData, format!("ColorScheme::from_reg(): line {line_no}: {msg}"));
+
+        let mut scheme = Self { name: Cow::Borrowed(""), .. Self::CAMPBELL };
+        let mut lines = text.strip_prefix('\u{FEFF}').unwrap_or(text).lines().enumerate().map(|(i, line)| (i + 1, line));
+        while let Some((line_no, line)) = lines.next() {
+            let mut line = line.trim().to_string();
+            while line.ends_with('\\') { // continued, e.g. long `hex:` values
+                line.pop();
+                line.push_str(lines.next().map_or("", |(_, next)| next.trim()));
+            }
+
+            if line.is_empty() || line.starts_with(';') || line.starts_with('[') || line == "Windows Registry Editor Version 5.00" || line == "REGEDIT4" { continue }
+            let (name, data) = if let Some(data) = line.strip_prefix("@=") { ("", data) } else {
+                let (name, data) = parse_quoted(&line).ok_or_else(|| err(line_no, "expected `\"name\"=data`"))?;
+                (name, data.strip_prefix('=').ok_or_else(|| err(line_no, "expected `=` after value name"))?)
+            };
+            let dword = || data.strip_prefix("dword:").filter(|hex| hex.len() == 8 && hex.bytes().all(|b| b.is_ascii_hexdigit())).and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .ok_or_else(|| err(line_no, &format!("expected `{name:?}` to be `dword:` followed by 8 hex digits")));
+
+            let name = name.to_ascii_lowercase();
+            if let Some(index) = name.strip_prefix("colortable").filter(|i| i.len() == 2 && i.bytes().all(|b| b.is_ascii_digit())).and_then(|i| i.parse::<usize>().ok()).filter(|&i| i < 16) {
+                scheme.color_table[index] = ColorRef::from(dword()? & 0x00FF_FFFF);
+            } else if name == "screencolors" {
+                scheme.attributes = Attributes::from(dword()? as u16 & 0xFF);
+            } else if name == "popupcolors" {
+                scheme.popup_attributes = Attributes::from(dword()? as u16 & 0xFF);
+            }
+        }
+        Ok(scheme)
+    }
+
+    /// \[~~microsoft.com~~\]
+    /// Formats the scheme as a registry export (`.reg` file text) setting `ColorTable00` .. `ColorTable15`, `ScreenColors`, and `PopupColors` under `key`
+    /// (e.g. `HKEY_CURRENT_USER\Console` for the defaults of all consoles.)
+    ///
+    /// Lines end with `\r\n`, as regedit writes them.  `name` isn't written.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let reg = ColorScheme::CAMPBELL.to_reg(r"HKEY_CURRENT_USER\Console");
+    /// assert!(reg.starts_with("Windows Registry Editor Version 5.00\r\n\r\n[HKEY_CURRENT_USER\\Console]\r\n\"ColorTable00\"=dword:000c0c0c\r\n\"ColorTable01\"=dword:00da3700\r\n"));
+    /// assert!(reg.ends_with("\"ScreenColors\"=dword:00000007\r\n\"PopupColors\"=dword:000000f5\r\n"));
+    /// assert_eq!(ColorScheme::from_reg(&reg).unwrap().color_table, ColorScheme::CAMPBELL.color_table);
+    /// ```
+    ///
+    pub fn to_reg(&self, key: &str) -> String {
+        let mut reg = format!("Windows Registry Editor Version 5.00\r\n\r\n[{key}]\r\n");
+        for (i, color) in self.color_table.iter().copied().enumerate() {
+            let _ = write!(reg, "\"ColorTable{i:02}\"=dword:{:08x}\r\n", u32::from(color));
+        }
+        let _ = write!(reg, "\"ScreenColors\"=dword:{:08x}\r\n", u16::from(self.attributes) & 0xFF);
+        let _ = write!(reg, "\"PopupColors\"=dword:{:08x}\r\n", u16::from(self.popup_attributes) & 0xFF);
+        reg
+    }
+}
+
+/// Splits `"quoted"rest` into `(quoted, rest)`.  Escapes (`\"`, `\\`) are left in `quoted`, as no value name we care about contains them.
+fn parse_quoted(line: &str) -> Option<(&str, &str)> {
+    let line = line.strip_prefix('"')?;
+    let mut escaped = false;
+    let end = line.char_indices().find(|&(_, ch)| {
+        let end = ch == '"' && !escaped;
+        escaped = ch == '\\' && !escaped;
+        end
+    })?.0;
+    Some((&line[..end], &line[end + 1 ..]))
+}
+
+
+
+#[test] fn reg_round_trip() {
+    for scheme in ColorScheme::ALL {
+        let parsed = ColorScheme::from_reg(&scheme.to_reg(r"HKEY_CURRENT_USER\Console")).unwrap();
+        assert_eq!(parsed, ColorScheme { name: Cow::Borrowed(""), .. scheme.clone() });
+    }
+
+    let scheme = ColorScheme::from_reg(concat!(
+        "\u{FEFF}REGEDIT4\n",
+        "; comment\n",
+        "[HKEY_CURRENT_USER\\Console\\%SystemRoot%_System32_WindowsPowerShell_v1.0_powershell.exe]\n",
+        "@=\"default\"\n",
+        "\"Quoted \\\"Name\\\"\"=\"a\"\n",
+        "\"Binary\"=hex:00,01,\\\n",
+        "  02,03\n",
+        "\"colortable05\"=dword:00562401\n",
+        "\"ColorTable16\"=dword:zzzzzzzz\n",
+        "\"PopupColors\"=dword:000000f3\n",
+    )).unwrap();
+    assert_eq!(scheme.color_table[5], ColorRef::from_rgb([0x01, 0x24, 0x56]));
+    assert_eq!(scheme.popup_attributes, Attributes::from(0xF3));
+    assert_eq!(scheme.attributes, ColorScheme::CAMPBELL.attributes);
+
+    assert!(ColorScheme::from_reg("\"ColorTable00\"=dword:0\n").is_err());
+    assert!(ColorScheme::from_reg("\"ScreenColors\"=\"7\"\n").is_err());
+    assert!(ColorScheme::from_reg("ColorTable00=dword:00000000\n").is_err());
+    assert!(ColorScheme::from_reg("\"ScreenColors\"=dword:+0000007\n").is_err());
+    assert_eq!(ColorScheme::from_reg("\"ColorTable+1\"=dword:00562401\n").unwrap().color_table, ColorScheme::CAMPBELL.color_table); // not `ColorTable01`: ignored
+}
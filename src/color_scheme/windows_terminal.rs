@@ -0,0 +1,313 @@
+use super::*;
+
+use core::fmt::Write;
+
+
+
+/// Windows Terminal's names for the 16 colors, in ANSI order.
+const ANSI_NAMES : [&str; 16] = [
+    "black",        "red",          "green",        "yellow",       "blue",         "purple",       "cyan",         "white",
+    "brightBlack",  "brightRed",    "brightGreen",  "brightYellow", "brightBlue",   "brightPurple", "brightCyan",   "brightWhite",
+];
+
+impl ColorScheme {
+    /// \[~~microsoft.com~~\]
+    /// Creates a scheme named `name` from the colors and attributes of a screen buffer (e.g. from [`get_console_screen_buffer_info_ex`](https://learn.microsoft.com/en-us/windows/console/getconsolescreenbufferinfoex).)
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let info = MemoryScreenBuffer::new((80, 25)).get_console_screen_buffer_info_ex().unwrap();
+    /// let scheme = ColorScheme::from_screen_buffer_info_ex("Current", &info);
+    /// assert_eq!(scheme.color_table, ColorScheme::CAMPBELL.color_table);
+    /// ```
+    ///
+    pub fn from_screen_buffer_info_ex(name: impl Into<Cow<'static, str>>, info: &ConsoleScreenBufferInfoEx) -> Self {
+        Self {
+            name:               name.into(),
+            color_table:        info.color_table,
+            attributes:         info.attributes,
+            popup_attributes:   info.popup_attributes,
+        }
+    }
+
+    /// \[~~microsoft.com~~\]
+    /// Parses a [Windows Terminal color scheme](https://learn.microsoft.com/en-us/windows/terminal/customize-settings/color-schemes): a JSON object such as one entry of `settings.json`'s `"schemes"`.
+    ///
+    /// *   `"black"` through `"brightWhite"` (in ANSI order) are required, and are reordered into console order for `color_table`.
+    /// *   `"foreground"` and `"background"` are required, and become `attributes` by picking the perceptually closest colors in the table.
+    ///     `popup_attributes` are the same colors, swapped.
+    /// *   `"name"` is optional (defaulting to `""`), and other members (e.g. `"cursorColor"`) are ignored.
+    /// *   Colors are `"#RRGGBB"` or `"#RGB"`.
+    /// *   Comments and trailing commas are allowed, as they are in `settings.json`.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let scheme = ColorScheme::from_windows_terminal_json(r##"{
+    ///     "name": "Campbell",
+    ///     "foreground": "#CCCCCC", "background": "#0C0C0C",
+    ///     "black":       "#0C0C0C", "red":       "#C50F1F", "green":       "#13A10E", "yellow":       "#C19C00",
+    ///     "blue":        "#0037DA", "purple":    "#881798", "cyan":        "#3A96DD", "white":        "#CCCCCC",
+    ///     "brightBlack": "#767676", "brightRed": "#E74856", "brightGreen": "#16C60C", "brightYellow": "#F9F1A5",
+    ///     "brightBlue":  "#3B78FF", "brightPurple": "#B4009E", "brightCyan": "#61D6D6", "brightWhite": "#F2F2F2", // trailing comma
+    /// }"##).unwrap();
+    /// assert_eq!(scheme.name, "Campbell");
+    /// assert_eq!(scheme.color_table, ColorScheme::CAMPBELL.color_table);
+    /// assert_eq!(scheme.attributes, FOREGROUND_RED | FOREGROUND_GREEN | FOREGROUND_BLUE);
+    /// ```
+    ///
+    pub fn from_windows_terminal_json(json: &str) -> io::Result<Self> {
+        let err = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, format!("ColorScheme::from_windows_terminal_json(): {msg}"));
+
+        let mut parser = JsonParser { json: json.as_bytes(), pos: 0, depth: 0 };
+        let members = parser.document().map_err(|msg| err(&format!("{msg} at byte {}", parser.pos)))?;
+        let member = |key: &str| members.iter().rev().find(|(k, _)| k == key).map(|(_, v)| v);
+        let color = |key: &str| match member(key) {
+            None                => Err(err(&format!("missing `{key:?}`"))),
+            Some(None)          => Err(err(&format!("`{key:?}` isn't a string"))),
+            Some(Some(value))   => parse_color(value).ok_or_else(|| err(&format!("`{key:?}` isn't a \"#RRGGBB\" color"))),
+        };
+
+        let mut color_table = [ColorRef::default(); 16];
+        for (i, entry) in color_table.iter_mut().enumerate() { *entry = color(ANSI_NAMES[ansi_index(i)])?; }
+        let fg = quantize::nearest_index(&color_table, color("foreground")?) as u16;
+        let bg = quantize::nearest_index(&color_table, color("background")?) as u16;
+        let name = match member("name") {
+            None                => String::new(),
+            Some(None)          => return Err(err("`\"name\"` isn't a string")),
+            Some(Some(name))    => name.clone(),
+        };
+
+        Ok(Self {
+            name:               Cow::Owned(name),
+            color_table,
+            attributes:         Attributes::from(bg << 4 | fg),
+            popup_attributes:   Attributes::from(fg << 4 | bg),
+        })
+    }
+
+    /// \[~~microsoft.com~~\]
+    /// Formats the scheme as a [Windows Terminal color scheme](https://learn.microsoft.com/en-us/windows/terminal/customize-settings/color-schemes), suitable for `settings.json`'s `"schemes"`.
+    ///
+    /// `"foreground"` and `"background"` are looked up from `attributes`, and the colors are written in ANSI order.
+    /// Windows Terminal has no equivalent of `popup_attributes`, so they're lost.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let json = ColorScheme::VINTAGE.to_windows_terminal_json();
+    /// assert!(json.starts_with("{\n    \"name\": \"Vintage\",\n    \"foreground\": \"#C0C0C0\",\n    \"background\": \"#000000\",\n    \"black\": \"#000000\",\n    \"red\": \"#800000\",\n"));
+    /// assert_eq!(ColorScheme::from_windows_terminal_json(&json).unwrap().color_table, ColorScheme::VINTAGE.color_table);
+    /// ```
+    ///
+    pub fn to_windows_terminal_json(&self) -> String {
+        let bits = usize::from(u16::from(self.attributes));
+        let hex = |c: ColorRef| { let [r, g, b] = c.to_rgb(); format!("#{r:02X}{g:02X}{b:02X}") };
+
+        let mut json = String::from("{\n    \"name\": \"");
+        for ch in self.name.chars() {
+            match ch {
+                '"'                 => json.push_str("\\\""),
+                '\\'                => json.push_str("\\\\"),
+                ch if ch < ' '      => { let _ = write!(json, "\\u{:04X}", u32::from(ch)); },
+                ch                  => json.push(ch),
+            }
+        }
+        json.push_str("\",\n");
+        let _ = writeln!(json, "    \"foreground\": \"{}\",", hex(self.color_table[bits & 0xF]));
+        let _ = writeln!(json, "    \"background\": \"{}\",", hex(self.color_table[(bits >> 4) & 0xF]));
+        for (i, name) in ANSI_NAMES.iter().enumerate() {
+            let _ = writeln!(json, "    \"{name}\": \"{}\"{}", hex(self.color_table[ansi_index(i)]), if i < 15 { "," } else { "" });
+        }
+        json.push_str("}\n");
+        json
+    }
+}
+
+/// Parses `"#RRGGBB"` or `"#RGB"`.
+fn parse_color(s: &str) -> Option<ColorRef> {
+    let hex = s.strip_prefix('#').filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))?;
+    let rgb = u32::from_str_radix(hex, 16).ok()?;
+    match hex.len() {
+        6 => { let [_, r, g, b] = rgb.to_be_bytes(); Some(ColorRef::from_rgb([r, g, b])) },
+        3 => { let nibble = |shift: u32| ((rgb >> shift) & 0xF) as u8 * 0x11; Some(ColorRef::from_rgb([nibble(8), nibble(4), nibble(0)])) },
+        _ => None,
+    }
+}
+
+
+
+/// Just enough of a JSON (with comments and trailing commas) parser to read the string members of an object.
+struct JsonParser<'j> {
+    json:   &'j [u8],
+    pos:    usize,
+    depth:  usize,
+}
+
+type JsonResult<T> = Result<T, &'static str>;
+
+impl JsonParser<'_> {
+    /// How deeply [`value`](Self::value) may nest objects and arrays before giving up, rather than overflowing the stack.
+    const MAX_DEPTH : usize = 64;
+
+    /// Parses a top level object, returning its members, with [`None`] for non-string values.
+    fn document(&mut self) -> JsonResult<Vec<(String, Option<String>)>> {
+        let mut members = Vec::new();
+        self.object(|parser, key| {
+            let value = if parser.peek() == Some(b'"') { Some(parser.string()?) } else { parser.value()?; None };
+            members.push((key, value));
+            Ok(())
+        })?;
+        self.skip_whitespace()?;
+        if self.pos < self.json.len() { return Err("expected end of JSON") }
+        Ok(members)
+    }
+
+    fn peek(&self) -> Option<u8> { self.json.get(self.pos).copied() }
+
+    fn eat(&mut self, b: u8) -> JsonResult<bool> {
+        self.skip_whitespace()?;
+        let eaten = self.peek() == Some(b);
+        if eaten { self.pos += 1; }
+        Ok(eaten)
+    }
+
+    fn skip_whitespace(&mut self) -> JsonResult<()> {
+        loop {
+            match (self.peek(), self.json.get(self.pos + 1)) {
+                (Some(b' ' | b'\t' | b'\r' | b'\n'), _) => self.pos += 1,
+                (Some(b'/'), Some(b'/')) => while self.peek().is_some_and(|b| b != b'\n') { self.pos += 1 },
+                (Some(b'/'), Some(b'*')) => {
+                    let end = self.json[self.pos + 2 ..].windows(2).position(|w| w == b"*/").ok_or("unterminated comment")?;
+                    self.pos += 2 + end + 2;
+                },
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    /// Parses `{ "key": value, ... }`, calling `member` to parse each value.
+    fn object(&mut self, mut member: impl FnMut(&mut Self, String) -> JsonResult<()>) -> JsonResult<()> {
+        if !self.eat(b'{')? { return Err("expected `{`") }
+        loop {
+            if self.eat(b'}')? { return Ok(()) }
+            self.skip_whitespace()?;
+            let key = self.string()?;
+            if !self.eat(b':')? { return Err("expected `:`") }
+            self.skip_whitespace()?;
+            member(self, key)?;
+            if self.eat(b',')? { continue }
+            if self.eat(b'}')? { return Ok(()) }
+            return Err("expected `,` or `}`")
+        }
+    }
+
+    /// Skips any value.
+    fn value(&mut self) -> JsonResult<()> {
+        self.skip_whitespace()?;
+        match self.peek() {
+            Some(b'"') => self.string().map(drop),
+            Some(b'{' | b'[') if self.depth >= Self::MAX_DEPTH => Err("nesting too deep"),
+            Some(b'{') => {
+                self.depth += 1;
+                self.object(|parser, _| parser.value())?;
+                self.depth -= 1;
+                Ok(())
+            },
+            Some(b'[') => {
+                self.depth += 1;
+                self.pos += 1;
+                loop {
+                    if self.eat(b']')? { break }
+                    self.value()?;
+                    if self.eat(b',')? { continue }
+                    if self.eat(b']')? { break }
+                    return Err("expected `,` or `]`")
+                }
+                self.depth -= 1;
+                Ok(())
+            },
+            _ => {
+                let start = self.pos;
+                while self.peek().is_some_and(|b| b.is_ascii_alphanumeric() || b"+-.".contains(&b)) { self.pos += 1; }
+                if self.pos == start { Err("expected a value") } else { Ok(()) }
+            },
+        }
+    }
+
+    /// Parses a `"string"`, decoding escapes.
+    fn string(&mut self) -> JsonResult<String> {
+        if self.peek() != Some(b'"') { return Err("expected a string") }
+        self.pos += 1;
+        let mut s = Vec::new();
+        loop {
+            let b = self.peek().ok_or("unterminated string")?;
+            self.pos += 1;
+            match b {
+                b'"'  => return String::from_utf8(s).map_err(|_| "invalid UTF-8"),
+                b'\\' => {
+                    let b = self.peek().ok_or("unterminated string")?;
+                    self.pos += 1;
+                    let ch = match b {
+                        b'"' | b'\\' | b'/' => char::from(b),
+                        b'b' => '\x08',
+                        b'f' => '\x0C',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => {
+                            let mut units = vec![self.hex4()?];
+                            if (0xD800 .. 0xDC00).contains(&units[0]) && self.json[self.pos ..].starts_with(b"\\u") {
+                                self.pos += 2;
+                                units.push(self.hex4()?);
+                            }
+                            for ch in char::decode_utf16(units) {
+                                let mut utf8 = [0u8; 4];
+                                s.extend_from_slice(ch.unwrap_or(char::REPLACEMENT_CHARACTER).encode_utf8(&mut utf8).as_bytes());
+                            }
+                            continue
+                        },
+                        _ => return Err("invalid escape"),
+                    };
+                    let mut utf8 = [0u8; 4];
+                    s.extend_from_slice(ch.encode_utf8(&mut utf8).as_bytes());
+                },
+                b => s.push(b),
+            }
+        }
+    }
+
+    fn hex4(&mut self) -> JsonResult<u16> {
+        let hex = self.json.get(self.pos .. self.pos + 4).filter(|hex| hex.iter().all(u8::is_ascii_hexdigit)).ok_or("expected 4 hex digits")?;
+        self.pos += 4;
+        Ok(hex.iter().fold(0, |n, &b| n << 4 | char::from(b).to_digit(16).unwrap_or(0) as u16))
+    }
+}
+
+
+
+#[test] fn windows_terminal_round_trip() {
+    for scheme in ColorScheme::ALL {
+        let json = scheme.to_windows_terminal_json();
+        let parsed = ColorScheme::from_windows_terminal_json(&json).unwrap();
+        assert_eq!(parsed.name, scheme.name);
+        assert_eq!(parsed.color_table, scheme.color_table, "{}", scheme.name);
+        assert_eq!(parsed.attributes, scheme.attributes, "{}", scheme.name);
+    }
+
+    let json = ColorScheme::CAMPBELL.to_windows_terminal_json().replace("\"Campbell\"", "\"\\\"Q\\u00e9\\ud83e\\udd8a\\/\\\"\" /* comment */, \"cursorColor\": [1, {\"a\": null}, true]");
+    assert_eq!(ColorScheme::from_windows_terminal_json(&json).unwrap().name, "\"Qé🦊/\"");
+    assert_eq!(parse_color("#abc"), Some(ColorRef::from_rgb([0xAA, 0xBB, 0xCC])));
+    assert_eq!(parse_color("#12345"), None);
+
+    assert!(ColorScheme::from_windows_terminal_json("").is_err());
+    assert!(ColorScheme::from_windows_terminal_json("{}").is_err());
+    assert!(ColorScheme::from_windows_terminal_json(&ColorScheme::CAMPBELL.to_windows_terminal_json().replace("\"red\"", "\"Red\"")).is_err());
+    assert!(ColorScheme::from_windows_terminal_json(&(ColorScheme::CAMPBELL.to_windows_terminal_json() + "}")).is_err());
+
+    let nested = |depth: usize| ColorScheme::CAMPBELL.to_windows_terminal_json().replacen('{', &format!("{{\"nested\": {}0{},", "[".repeat(depth), "]".repeat(depth)), 1);
+    assert!(ColorScheme::from_windows_terminal_json(&nested(64)).is_ok());
+    let err = ColorScheme::from_windows_terminal_json(&nested(1_000_000)).unwrap_err();
+    assert!(err.to_string().contains("nesting too deep"), "{err}");
+}
@@ -0,0 +1,39 @@
+//! \[~~microsoft.com~~\]
+//! Converting [`CharInfo`]s to (and from) other formats.
+
+use crate::*;
+
+mod ansi;
+mod html;
+mod svg;
+
+
+
+/// Returns the `(foreground, background)` colors of `attributes` from `color_table`, swapped if [`COMMON_LVB_REVERSE_VIDEO`] is set.
+fn colors(attributes: Attributes, color_table: &[ColorRef; 16]) -> (ColorRef, ColorRef) {
+    let bits = u16::from(attributes);
+    let fg = color_table[usize::from(bits & 0xF)];
+    let bg = color_table[usize::from((bits >> 4) & 0xF)];
+    if bits & u16::from(COMMON_LVB_REVERSE_VIDEO) != 0 { (bg, fg) } else { (fg, bg) }
+}
+
+/// Formats `color` as a CSS / SVG `#rrggbb` color.
+fn hex(color: ColorRef) -> String {
+    let [r, g, b] = color.to_rgb();
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// Pushes `ch` to HTML / SVG text, escaping `&`, `<`, and `>`.
+/// Control characters, which would break the layout (e.g. `\n`) or aren't allowed in XML at all (e.g. `\x1B`), become U+FFFD.
+fn push_escaped(s: &mut String, ch: char) {
+    match ch {
+        '&'                     => s.push_str("&amp;"),
+        '<'                     => s.push_str("&lt;"),
+        '>'                     => s.push_str("&gt;"),
+        ch if ch.is_control()   => s.push(char::REPLACEMENT_CHARACTER),
+        ch                      => s.push(ch),
+    }
+}
+
+/// Returns `true` if [`COMMON_LVB_UNDERSCORE`] is set.
+fn underscore(attributes: Attributes) -> bool { u16::from(attributes) & u16::from(COMMON_LVB_UNDERSCORE) != 0 }
@@ -0,0 +1,129 @@
+use super::*;
+
+use std::io;
+
+
+
+impl CharInfoGrid {
+    /// \[~~microsoft.com~~\]
+    /// Serializes the grid as UTF-8 text with SGR escapes.  See [`CharInfoGridView::to_ansi`].
+    pub fn to_ansi(&self) -> String { self.as_view().to_ansi() }
+
+    /// \[~~microsoft.com~~\]
+    /// Parses UTF-8 text with SGR escapes (e.g. from [`to_ansi`](Self::to_ansi)) into a grid.
+    ///
+    /// *   Each line becomes a row.  The grid is as wide as the longest line, and a final line break doesn't start another row.
+    /// *   Printable characters fill one cell each (characters outside the BMP fill two, as a UTF-16 surrogate pair.)
+    /// *   `\r` returns to the start of the line, `\t` advances to the next multiple of 8 columns, and backspace moves back a column.
+    /// *   `ESC[...m` sequences are applied with [`Attributes::apply_sgr`], starting from and resetting to `defaults`.  Attributes carry over from line to line.
+    /// *   Other control characters and escape sequences are ignored.
+    /// *   Cells that are never written are spaces with `defaults`.
+    ///
+    /// Fails if the text is too wide or tall for a [`Coord`].
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let defaults = FOREGROUND_RED | FOREGROUND_GREEN | FOREGROUND_BLUE;
+    /// let grid = CharInfoGrid::from_ansi("ok \x1B[32mgreen\x1B[0m\nA", defaults).unwrap();
+    /// assert_eq!(grid.size(), Coord::new(8, 2));
+    /// assert_eq!(grid[Coord::new(3, 0)], CharInfo::new(b'g'.into(), FOREGROUND_GREEN));
+    /// assert_eq!(grid[Coord::new(1, 1)], CharInfo::new(b' '.into(), defaults));
+    /// ```
+    ///
+    pub fn from_ansi(text: &str, defaults: Attributes) -> io::Result<Self> {
+        let mut rows = vec![Vec::<CharInfo>::new()];
+        let mut attributes = defaults;
+        let mut x = 0usize;
+        let mut parser = VtParser::new();
+        let blank = CharInfo::new(b' '.into(), defaults);
+        parser.advance(text.as_bytes(), |action| match action {
+            VtAction::Print(ch) => {
+                let row = rows.last_mut().unwrap();
+                let mut units = [0u16; 2];
+                for &mut unit in ch.encode_utf16(&mut units) {
+                    if row.len() <= x { row.resize(x + 1, blank); }
+                    row[x] = CharInfo::new(unit, attributes);
+                    x += 1;
+                }
+            },
+            VtAction::Execute(b'\n')    => { rows.push(Vec::new()); x = 0; },
+            VtAction::Execute(b'\r')    => x = 0,
+            VtAction::Execute(b'\t')    => x = (x / 8 + 1) * 8,
+            VtAction::Execute(0x08)     => x = x.saturating_sub(1),
+            VtAction::CsiDispatch { params, intermediates: [], final_byte: 'm' } => attributes.apply_sgr(defaults, params),
+            _ => {},
+        });
+        if rows.last().is_some_and(Vec::is_empty) { rows.pop(); }
+
+        let w = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let size = Coord::try_from((w, rows.len())).map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "CharInfoGrid::from_ansi(): text is too large for a CharInfoGrid"))?;
+        let mut cells = Vec::with_capacity(w * rows.len());
+        for mut row in rows {
+            row.resize(w, blank);
+            cells.append(&mut row);
+        }
+        Self::from_cells(size, cells)
+    }
+}
+
+impl CharInfoGridView<'_> {
+    /// \[~~microsoft.com~~\]
+    /// Serializes the region as UTF-8 text with SGR escapes, which reproduces it when written to (e.g. `cat`'d in) a VT terminal.
+    ///
+    /// *   Each row is written on its own line, ending with `\n`.
+    /// *   [`Attributes::to_sgr`] is written whenever the attributes change, and `ESC[0m` ends each line that set any.
+    ///     Only the bits SGR can represent (colors, [`COMMON_LVB_REVERSE_VIDEO`], and [`COMMON_LVB_UNDERSCORE`]) survive.
+    /// *   Double-width characters are written once (cells marked [`COMMON_LVB_TRAILING_BYTE`] are skipped.)
+    /// *   NUL is written as a space, and other control characters (which would be interpreted rather than displayed) as U+FFFD.
+    ///
+    /// [`CharInfoGrid::from_ansi`] reverses this exactly for grids of printable characters without other attribute bits, which makes the pair suitable for golden-file tests.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let gray = FOREGROUND_RED | FOREGROUND_GREEN | FOREGROUND_BLUE;
+    /// let mut grid = CharInfoGrid::new((3, 2), CharInfo::new(b' '.into(), gray));
+    /// grid[Coord::new(0, 0)] = CharInfo::new(b'!'.into(), FOREGROUND_RED | FOREGROUND_INTENSITY);
+    /// assert_eq!(grid.to_ansi(), "\x1B[0;91;40m!\x1B[0;37;40m  \x1B[0m\n\x1B[0;37;40m   \x1B[0m\n");
+    /// assert_eq!(CharInfoGrid::from_ansi(&grid.to_ansi(), gray).unwrap(), grid);
+    /// ```
+    ///
+    pub fn to_ansi(&self) -> String {
+        let mut text = String::new();
+        for row in self.into_rows() {
+            let mut sgr = None;
+            for (_, cell, ch) in decode_cells(row) {
+                let next = cell.attributes.to_sgr();
+                if sgr.as_ref() != Some(&next) {
+                    text.push_str(&next);
+                    sgr = Some(next);
+                }
+                text.push(if ch.is_control() { char::REPLACEMENT_CHARACTER } else { ch });
+            }
+            if sgr.is_some() { text.push_str("\x1B[0m"); }
+            text.push('\n');
+        }
+        text
+    }
+}
+
+
+
+#[test] fn ansi_round_trip() {
+    let defaults = FOREGROUND_RED | FOREGROUND_GREEN | FOREGROUND_BLUE;
+    let cells : Vec<CharInfo> = b"The quick brown fox jumps over the lazy dog. <&>".iter().zip((0u16 ..).map(|i| i.wrapping_mul(0x9E37))).map(|(&ch, bits)| {
+        CharInfo::new(ch.into(), Attributes::from(bits & (0x00FF | 0x4000 | 0x8000)))
+    }).collect();
+    let grid = CharInfoGrid::from_cells((12, 4), cells).unwrap();
+    assert_eq!(CharInfoGrid::from_ansi(&grid.to_ansi(), defaults).unwrap(), grid);
+
+    let units : Vec<u16> = "🦊!".encode_utf16().collect();
+    let grid = CharInfoGrid::from_cells((3, 1), units.iter().map(|&unit| CharInfo::new(unit, FOREGROUND_GREEN)).collect()).unwrap();
+    assert_eq!(grid.to_ansi(), "\x1B[0;32;40m🦊!\x1B[0m\n");
+    assert_eq!(CharInfoGrid::from_ansi(&grid.to_ansi(), defaults).unwrap(), grid);
+
+    assert_eq!(CharInfoGrid::from_ansi("", defaults).unwrap().size(), Coord::new(0, 0));
+    assert_eq!(CharInfoGrid::from_ansi("\n", defaults).unwrap().size(), Coord::new(0, 1));
+    assert_eq!(CharInfoGrid::from_ansi("a\tb\r\x08c\n\n", defaults).unwrap().to_ansi(), "\x1B[0;37;40mc       b\x1B[0m\n\x1B[0;37;40m         \x1B[0m\n");
+}
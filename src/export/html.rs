@@ -0,0 +1,83 @@
+use super::*;
+
+
+
+impl CharInfoGrid {
+    /// \[~~microsoft.com~~\]
+    /// Renders the grid as a standalone HTML `<pre>` snippet.  See [`CharInfoGridView::to_html`].
+    pub fn to_html(&self, color_table: &[ColorRef; 16]) -> String { self.as_view().to_html(color_table) }
+}
+
+impl CharInfoGridView<'_> {
+    /// \[~~microsoft.com~~\]
+    /// Renders the region as a standalone HTML `<pre>` snippet, e.g. for pasting a console "screenshot" into a bug report.
+    ///
+    /// *   Colors are looked up in `color_table` (e.g. [`ConsoleScreenBufferInfoEx::color_table`].)
+    /// *   [`COMMON_LVB_REVERSE_VIDEO`] swaps the foreground and background colors, and [`COMMON_LVB_UNDERSCORE`] underlines.
+    /// *   Consecutive cells of a row which render identically are merged into a single `<span>`.  Rows are separated by newlines.
+    /// *   Double-width characters are written once (cells marked [`COMMON_LVB_TRAILING_BYTE`] are skipped.)
+    /// *   Control characters (e.g. a `\n` stored in a cell) are written as U+FFFD, so they can't break the row layout.
+    /// *   Grid lines ([`COMMON_LVB_GRID_HORIZONTAL`] etc.) are ignored.  Use [`to_svg`](Self::to_svg) if they matter.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let color_table = MemoryScreenBuffer::new((80, 25)).get_console_screen_buffer_info_ex().unwrap().color_table;
+    /// let gray = FOREGROUND_RED | FOREGROUND_GREEN | FOREGROUND_BLUE;
+    /// let grid = CharInfoGrid::from_cells((3, 1), vec![
+    ///     CharInfo::new(b'a'.into(), FOREGROUND_GREEN),
+    ///     CharInfo::new(b'<'.into(), gray),
+    ///     CharInfo::new(b'b'.into(), gray),
+    /// ]).unwrap();
+    /// assert_eq!(grid.to_html(&color_table), concat!(
+    ///     r#"<pre style="font-family: monospace">"#,
+    ///     r#"<span style="color: #13a10e; background-color: #0c0c0c">a</span>"#,
+    ///     r#"<span style="color: #cccccc; background-color: #0c0c0c">&lt;b</span>"#,
+    ///     r#"</pre>"#,
+    /// ));
+    /// ```
+    ///
+    pub fn to_html(&self, color_table: &[ColorRef; 16]) -> String {
+        let mut html = String::from(r#"<pre style="font-family: monospace">"#);
+        for (y, row) in self.into_rows().enumerate() {
+            if y != 0 { html.push('\n'); }
+            let mut style = None;
+            for (_, cell, ch) in decode_cells(row) {
+                let (fg, bg) = colors(cell.attributes, color_table);
+                let next = (fg, bg, underscore(cell.attributes));
+                if style != Some(next) {
+                    if style.is_some() { html.push_str("</span>"); }
+                    style = Some(next);
+                    html.push_str(&format!(r#"<span style="color: {}; background-color: {}"#, hex(fg), hex(bg)));
+                    if next.2 { html.push_str("; text-decoration: underline"); }
+                    html.push_str(r#"">"#);
+                }
+                push_escaped(&mut html, ch);
+            }
+            if style.is_some() { html.push_str("</span>"); }
+        }
+        html.push_str("</pre>");
+        html
+    }
+}
+
+
+
+#[test] fn reverse_and_underscore() {
+    let color_table = test_color_table();
+    let grid = CharInfoGrid::from_cells((2, 2), vec![
+        CharInfo::new(b'a'.into(), FOREGROUND_BLUE | BACKGROUND_RED | COMMON_LVB_REVERSE_VIDEO),
+        CharInfo::new(b'&'.into(), FOREGROUND_BLUE | BACKGROUND_RED | COMMON_LVB_REVERSE_VIDEO | COMMON_LVB_LEADING_BYTE),
+        CharInfo::new(0x6F22, COMMON_LVB_UNDERSCORE | COMMON_LVB_LEADING_BYTE),
+        CharInfo::new(0x6F22, COMMON_LVB_UNDERSCORE | COMMON_LVB_TRAILING_BYTE),
+    ]).unwrap();
+    assert_eq!(grid.to_html(&color_table), concat!(
+        r#"<pre style="font-family: monospace">"#,
+        r#"<span style="color: #040000; background-color: #010000">a&amp;</span>"#, "\n",
+        r#"<span style="color: #000000; background-color: #000000; text-decoration: underline">漢</span>"#,
+        r#"</pre>"#,
+    ));
+
+    let grid = grid_from_text((3, 1), b"\n\r\x1B");
+    assert!(grid.to_html(&color_table).contains(">\u{FFFD}\u{FFFD}\u{FFFD}</span></pre>"));
+}
@@ -0,0 +1,136 @@
+use super::*;
+
+use core::fmt::Write;
+
+
+
+impl CharInfoGrid {
+    /// \[~~microsoft.com~~\]
+    /// Renders the grid as a standalone SVG image.  See [`CharInfoGridView::to_svg`].
+    pub fn to_svg(&self, cell_size: impl Into<Coord>, color_table: &[ColorRef; 16]) -> String { self.as_view().to_svg(cell_size, color_table) }
+}
+
+impl CharInfoGridView<'_> {
+    /// \[~~microsoft.com~~\]
+    /// Renders the region as a standalone SVG image, with each cell occupying a fixed `cell_size` pixel box (e.g. [`ConsoleFontInfoEx::font_size`].)
+    ///
+    /// *   Colors are looked up in `color_table` (e.g. [`ConsoleScreenBufferInfoEx::color_table`].)  [`COMMON_LVB_REVERSE_VIDEO`] swaps the foreground and background colors.
+    /// *   Backgrounds are `<rect>`s, merged across runs of cells with the same background color.
+    /// *   Characters are `<text>` with one explicit `x` per character, so glyphs land on the cell grid regardless of the viewer's font metrics.
+    ///     Double-width characters are drawn once, from their leading cell.  Spaces aren't drawn, and control characters (which XML forbids) are drawn as U+FFFD.
+    /// *   [`COMMON_LVB_UNDERSCORE`] (bottom edge), [`COMMON_LVB_GRID_HORIZONTAL`] (top edge), [`COMMON_LVB_GRID_LVERTICAL`] (left edge), and
+    ///     [`COMMON_LVB_GRID_RVERTICAL`] (right edge) are drawn as 1 pixel lines in the foreground color, as conhost draws them.
+    ///
+    /// The output only depends on the arguments, so it's suitable for checked-in or generated documentation.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let color_table = MemoryScreenBuffer::new((80, 25)).get_console_screen_buffer_info_ex().unwrap().color_table;
+    /// let grid = CharInfoGrid::from_cells((2, 1), vec![
+    ///     CharInfo::new(b'O'.into(), FOREGROUND_GREEN | COMMON_LVB_UNDERSCORE),
+    ///     CharInfo::new(b'K'.into(), FOREGROUND_GREEN),
+    /// ]).unwrap();
+    /// assert_eq!(grid.to_svg((8, 16), &color_table), concat!(
+    ///     r##"<svg xmlns="http://www.w3.org/2000/svg" width="16" height="16" viewBox="0 0 16 16" font-family="Consolas, monospace" font-size="16" shape-rendering="crispEdges">"##, "\n",
+    ///     r##"<rect x="0" y="0" width="16" height="16" fill="#0c0c0c"/>"##, "\n",
+    ///     r##"<text x="0 8" y="13" fill="#13a10e">OK</text>"##, "\n",
+    ///     r##"<rect x="0" y="15" width="8" height="1" fill="#13a10e"/>"##, "\n",
+    ///     r##"</svg>"##, "\n",
+    /// ));
+    /// ```
+    ///
+    pub fn to_svg(&self, cell_size: impl Into<Coord>, color_table: &[ColorRef; 16]) -> String {
+        let cell_size = cell_size.into();
+        let (cw, ch) = (i32::from(cell_size.x.max(0)), i32::from(cell_size.y.max(0)));
+        let size = self.size();
+        let (width, height) = (cw * i32::from(size.x), ch * i32::from(size.y));
+        let baseline = ch - ch / 5;
+
+        let mut svg = String::new();
+        let _ = writeln!(svg, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="Consolas, monospace" font-size="{ch}" shape-rendering="crispEdges">"#);
+        let rect = |svg: &mut String, x: i32, y: i32, w: i32, h: i32, color: ColorRef| {
+            let _ = writeln!(svg, r#"<rect x="{x}" y="{y}" width="{w}" height="{h}" fill="{}"/>"#, hex(color));
+        };
+
+        for (row, top) in self.into_rows().zip((0 ..).map(|y: i32| y * ch)) {
+            let left = |x: usize| x as i32 * cw;
+
+            // Backgrounds
+            for (start, end, bg) in runs(row, |cell| Some(colors(cell.attributes, color_table).1)) {
+                rect(&mut svg, left(start), top, left(end) - left(start), ch, bg);
+            }
+
+            // Characters
+            let mut chars = decode_cells(row).filter(|&(_, _, c)| c != ' ').peekable();
+            while let Some((x, cell, c)) = chars.next() {
+                let (fg, _) = colors(cell.attributes, color_table);
+                let (mut xs, mut text) = (left(x).to_string(), String::new());
+                push_escaped(&mut text, c);
+                while let Some(&(x, cell, c)) = chars.peek() {
+                    if colors(cell.attributes, color_table).0 != fg { break }
+                    chars.next();
+                    let _ = write!(xs, " {}", left(x));
+                    push_escaped(&mut text, c);
+                }
+                let _ = writeln!(svg, r#"<text x="{xs}" y="{}" fill="{}">{text}</text>"#, top + baseline, hex(fg));
+            }
+
+            // Lines
+            let fg_if = |flag: Attributes| move |cell: &CharInfo| (u16::from(cell.attributes) & u16::from(flag) != 0).then(|| colors(cell.attributes, color_table).0);
+            for (start, end, fg) in runs(row, fg_if(COMMON_LVB_GRID_HORIZONTAL)) { rect(&mut svg, left(start), top, left(end) - left(start), 1, fg); }
+            for (start, end, fg) in runs(row, fg_if(COMMON_LVB_UNDERSCORE     )) { rect(&mut svg, left(start), top + ch - 1, left(end) - left(start), 1, fg); }
+            for (x, cell) in row.iter().enumerate() {
+                if let Some(fg) = fg_if(COMMON_LVB_GRID_LVERTICAL)(cell) { rect(&mut svg, left(x), top, 1, ch, fg); }
+                if let Some(fg) = fg_if(COMMON_LVB_GRID_RVERTICAL)(cell) { rect(&mut svg, left(x) + cw - 1, top, 1, ch, fg); }
+            }
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+/// Returns the `(start, end, key)` runs of consecutive cells of `row` for which `key` returns the same [`Some`] value.
+fn runs<K: PartialEq>(row: &[CharInfo], key: impl Fn(&CharInfo) -> Option<K>) -> Vec<(usize, usize, K)> {
+    let mut runs = Vec::<(usize, usize, K)>::new();
+    for (x, cell) in row.iter().enumerate() {
+        let Some(k) = key(cell) else { continue };
+        match runs.last_mut() {
+            Some((_, end, prev)) if *end == x && *prev == k => *end = x + 1,
+            _ => runs.push((x, x + 1, k)),
+        }
+    }
+    runs
+}
+
+
+
+#[test] fn grid_lines() {
+    let color_table = test_color_table();
+    let grid = CharInfoGrid::from_cells((2, 2), vec![
+        CharInfo::new(b' '.into(), FOREGROUND_RED | COMMON_LVB_GRID_HORIZONTAL),
+        CharInfo::new(b'<'.into(), FOREGROUND_RED | COMMON_LVB_GRID_HORIZONTAL | COMMON_LVB_GRID_RVERTICAL),
+        CharInfo::new(b'x'.into(), FOREGROUND_BLUE | BACKGROUND_GREEN | COMMON_LVB_GRID_LVERTICAL | COMMON_LVB_REVERSE_VIDEO),
+        CharInfo::new(b'y'.into(), FOREGROUND_GREEN),
+    ]).unwrap();
+    assert_eq!(grid.to_svg((10, 20), &color_table), concat!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="40" viewBox="0 0 20 40" font-family="Consolas, monospace" font-size="20" shape-rendering="crispEdges">"##, "\n",
+        r##"<rect x="0" y="0" width="20" height="20" fill="#000000"/>"##, "\n",
+        r##"<text x="10" y="16" fill="#040000">&lt;</text>"##, "\n",
+        r##"<rect x="0" y="0" width="20" height="1" fill="#040000"/>"##, "\n",
+        r##"<rect x="19" y="0" width="1" height="20" fill="#040000"/>"##, "\n",
+        r##"<rect x="0" y="20" width="10" height="20" fill="#010000"/>"##, "\n",
+        r##"<rect x="10" y="20" width="10" height="20" fill="#000000"/>"##, "\n",
+        r##"<text x="0 10" y="36" fill="#020000">xy</text>"##, "\n",
+        r##"<rect x="0" y="20" width="1" height="20" fill="#020000"/>"##, "\n",
+        r##"</svg>"##, "\n",
+    ));
+
+    let grid = CharInfoGrid::from_cells((2, 1), vec![CharInfo::new(0x1B, FOREGROUND_RED), CharInfo::new(0x07, FOREGROUND_RED)]).unwrap();
+    assert!(grid.to_svg((10, 20), &color_table).contains("<text x=\"0 10\" y=\"16\" fill=\"#040000\">\u{FFFD}\u{FFFD}</text>"));
+
+    // Foreground changes don't split background runs
+    let grid = CharInfoGrid::from_cells((2, 1), vec![CharInfo::new(b'a'.into(), FOREGROUND_RED), CharInfo::new(b'b'.into(), FOREGROUND_BLUE)]).unwrap();
+    assert!(grid.to_svg((10, 20), &color_table).contains("\n<rect x=\"0\" y=\"0\" width=\"20\" height=\"20\" fill=\"#000000\"/>\n<text x=\"0\" "));
+}
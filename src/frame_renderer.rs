@@ -0,0 +1,251 @@
+use crate::*;
+
+use std::io;
+
+
+
+/// Dirty runs within a row separated by this many unchanged cells or fewer are merged, trading a few redundant cells for fewer writes.
+const MERGE_GAP : usize = 4;
+
+/// \[~~microsoft.com~~\]
+/// Returns the rectangles of `next` which differ from `previous`, sorted top to bottom, then left to right.
+///
+/// Changed cells within a row are grouped into runs (merging runs separated by only a few unchanged cells),
+/// and identical runs on consecutive rows are merged into a single rectangle.
+/// Runs are widened to cover both halves of double-width characters ([`COMMON_LVB_LEADING_BYTE`] / [`COMMON_LVB_TRAILING_BYTE`].)
+/// If the sizes of `previous` and `next` differ, the entire `next` frame is dirty.
+///
+/// ### Example
+/// ```
+/// # use maulingmonkey_console_winapi_wrappers::*;
+/// let blank = CharInfo::new(b' '.into(), Attributes::default());
+/// let previous = CharInfoGrid::new((80, 25), blank);
+/// let mut next = previous.clone();
+/// next.fill((10, 5) .. (20, 8), CharInfo::new(b'#'.into(), FOREGROUND_GREEN));
+/// next[Coord::new(0, 24)].attributes = FOREGROUND_RED;
+///
+/// assert_eq!(diff_frames(&previous, &next), [
+///     ExclusiveRect::new(10,  5, 20,  8),
+///     ExclusiveRect::new( 0, 24,  1, 25),
+/// ]);
+/// assert_eq!(diff_frames(&next, &next), []);
+/// ```
+///
+pub fn diff_frames(previous: &CharInfoGrid, next: &CharInfoGrid) -> Vec<ExclusiveRect> {
+    if previous.size() != next.size() {
+        let bounds = next.bounds();
+        return if bounds.is_empty() { Vec::new() } else { vec![bounds] };
+    }
+
+    let mut done = Vec::new();
+    let mut open = Vec::<ExclusiveRect>::new();
+    for (y, (prev, next)) in (0 ..).zip(previous.rows().zip(next.rows())) {
+        let mut extended = Vec::new();
+        for (left, right) in dirty_runs(prev, next) {
+            let (left, right) = (left as i16, right as i16);
+            match open.iter().position(|r| r.left == left && r.right == right) {
+                Some(i) => { let mut r = open.swap_remove(i); r.bottom = y + 1; extended.push(r); },
+                None    => extended.push(ExclusiveRect::new(left, y, right, y + 1)),
+            }
+        }
+        done.append(&mut open);
+        open = extended;
+    }
+    done.append(&mut open);
+    done.sort_by_key(|r| (r.top, r.left));
+    done
+}
+
+/// Returns the `(left, right)` (exclusive) runs of cells which differ between `prev` and `next`.
+///
+/// Runs are widened to never split a double-width character: a run never starts on a [`COMMON_LVB_TRAILING_BYTE`] cell, or ends just before one,
+/// so redrawing a run redraws whole glyphs, and output starts at the column the first glyph is drawn from.
+fn dirty_runs(prev: &[CharInfo], next: &[CharInfo]) -> Vec<(usize, usize)> {
+    let trailing = |x: usize| next.get(x).is_some_and(|cell| u16::from(cell.attributes) & u16::from(COMMON_LVB_TRAILING_BYTE) != 0);
+    let mut runs = Vec::<(usize, usize)>::new();
+    for x in (0 .. next.len()).filter(|&x| prev[x] != next[x]) {
+        let left  = if x > 0 && trailing(x) { x - 1 } else { x };
+        let right = if trailing(x + 1) { x + 2 } else { x + 1 };
+        match runs.last_mut() {
+            Some((_, end)) if left <= *end + MERGE_GAP => *end = right.max(*end),
+            _ => runs.push((left, right)),
+        }
+    }
+    runs
+}
+
+
+
+/// \[~~microsoft.com~~\]
+/// Double buffers full-frame redraws: remembers the last frame rendered, and only redraws cells that changed since.
+///
+/// Drawing every frame with a single full-window [`write_console_output`](ConsoleBackend::write_console_output) flickers, and is slow over e.g. RDP.
+/// Instead, build each frame in a [`CharInfoGrid`], and let [`render`](Self::render) / [`render_vt`](Self::render_vt) write the [`diff_frames`] between it and the last.
+///
+/// ### Example
+/// ```
+/// # use maulingmonkey_console_winapi_wrappers::*;
+/// let mut console = MemoryConsole::new((80, 25));
+/// let mut renderer = FrameRenderer::new();
+/// let mut frame = CharInfoGrid::new((80, 25), CharInfo::new(b' '.into(), Attributes::default()));
+///
+/// assert_eq!(renderer.render(&mut console, &frame, (0, 0)).unwrap(), [ExclusiveRect::new(0, 0, 80, 25)]); // first frame: everything
+/// frame[Coord::new(3, 4)].char = u16::from(b'@').into();
+/// assert_eq!(renderer.render(&mut console, &frame, (0, 0)).unwrap(), [ExclusiveRect::new(3, 4, 4, 5)]);
+/// assert_eq!(renderer.render(&mut console, &frame, (0, 0)).unwrap(), []);
+/// assert_eq!(console.screen_buffer().cell((3, 4)).unwrap().char.unicode_char(), u16::from(b'@'));
+/// ```
+///
+#[derive(Clone, Debug, Default)]
+pub struct FrameRenderer {
+    previous: Option<CharInfoGrid>,
+}
+
+impl FrameRenderer {
+    /// Creates a renderer which will redraw the entire first frame.
+    pub const fn new() -> Self { Self { previous: None } }
+
+    /// Forgets the previous frame, so the next frame is redrawn entirely.  Call after anything else draws over the console, or after it's resized.
+    pub fn invalidate(&mut self) { self.previous = None; }
+
+    /// The last frame rendered, if any since creation or [`invalidate`](Self::invalidate).
+    pub fn previous(&self) -> Option<&CharInfoGrid> { self.previous.as_ref() }
+
+    /// Returns the rectangles of `frame` which would be redrawn by rendering it next, without rendering it.
+    pub fn diff(&self, frame: &CharInfoGrid) -> Vec<ExclusiveRect> {
+        match self.previous.as_ref() {
+            Some(previous) => diff_frames(previous, frame),
+            None => if frame.bounds().is_empty() { Vec::new() } else { vec![frame.bounds()] },
+        }
+    }
+
+    /// Writes the cells of `frame` which changed since the previous frame to `console`, with `frame`'s top left corner at `origin` in the screen buffer.
+    /// Returns the rectangles of `frame` which were written.
+    ///
+    /// If a write fails, the renderer is [invalidated](Self::invalidate) so the next frame is redrawn entirely.
+    pub fn render(&mut self, console: &mut (impl ConsoleBackend + ?Sized), frame: &CharInfoGrid, origin: impl Into<Coord>) -> io::Result<Vec<ExclusiveRect>> {
+        let origin = origin.into();
+        let dirty = self.diff(frame);
+        self.previous = None;
+        for rect in dirty.iter().copied() {
+            let target = rect.translate(origin).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "FrameRenderer::render(): `origin` + frame size overflows"))?;
+            let mut region = InclusiveRect::from(target);
+            console.write_console_output_grid(frame, Coord::new(rect.left, rect.top), &mut region)?;
+        }
+        self.previous = Some(frame.clone());
+        Ok(dirty)
+    }
+
+    /// Appends VT sequences to `out` which redraw the cells of `frame` which changed since the previous frame, with `frame`'s top left corner at `origin` (0-based.)
+    /// Returns the rectangles of `frame` which were redrawn.
+    ///
+    /// Each dirty row is positioned with CUP (`ESC[row;colH`), colors and other attributes are set with [`Attributes::to_sgr`] as they change,
+    /// and attributes are reset (`ESC[0m`) at the end.  Cells marked [`COMMON_LVB_TRAILING_BYTE`] are skipped, as terminals advance past them when drawing the leading cell.
+    /// SGR is only written when it changes, so cells whose attributes differ only in bits SGR can't represent (e.g. [`COMMON_LVB_LEADING_BYTE`]) don't repeat it.
+    ///
+    /// With a negative `origin`, the rows and columns of `frame` which would land above or left of the screen aren't drawn (CUP can't address them.)
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let mut renderer = FrameRenderer::new();
+    /// let mut frame = CharInfoGrid::new((4, 2), CharInfo::new(b' '.into(), FOREGROUND_RED | FOREGROUND_GREEN | FOREGROUND_BLUE));
+    /// let mut out = Vec::new();
+    /// renderer.render_vt(&frame, (0, 0), &mut out);
+    ///
+    /// out.clear();
+    /// frame[Coord::new(1, 1)] = CharInfo::new(b'!'.into(), FOREGROUND_GREEN);
+    /// renderer.render_vt(&frame, (0, 0), &mut out);
+    /// assert_eq!(out, b"\x1B[2;2H\x1B[0;32;40m!\x1B[0m");
+    /// ```
+    ///
+    pub fn render_vt(&mut self, frame: &CharInfoGrid, origin: impl Into<Coord>, out: &mut Vec<u8>) -> Vec<ExclusiveRect> {
+        let origin = origin.into();
+        let dirty = self.diff(frame);
+        let mut sgr = None;
+        for rect in dirty.iter().copied() {
+            let view = frame.view(rect).expect("diff_frames returns rects within the frame");
+            for (y, row) in (rect.top ..).zip(view.into_rows()) {
+                let row_no = i32::from(origin.y) + i32::from(y) + 1;
+                if row_no < 1 { continue } // above the screen
+                let mut positioned = false;
+                for (x, cell, ch) in decode_cells(row) {
+                    let col_no = i32::from(origin.x) + i32::from(rect.left) + x as i32 + 1;
+                    if col_no < 1 { continue } // left of the screen
+                    if !positioned {
+                        positioned = true;
+                        out.extend_from_slice(format!("\x1B[{row_no};{col_no}H").as_bytes());
+                    }
+                    let next = cell.attributes.to_sgr();
+                    if sgr.as_ref() != Some(&next) {
+                        out.extend_from_slice(next.as_bytes());
+                        sgr = Some(next);
+                    }
+                    let mut utf8 = [0u8; 4];
+                    out.extend_from_slice(ch.encode_utf8(&mut utf8).as_bytes());
+                }
+            }
+        }
+        if sgr.is_some() { out.extend_from_slice(b"\x1B[0m"); }
+        self.previous = Some(frame.clone());
+        dirty
+    }
+}
+
+
+
+#[test] fn diff() {
+    let a = grid_from_text((12, 3), &[b'.'; 36]);
+    let b = grid_from_text((12, 3), concat!("x.....x.....", ".....x..x..x", "....x......x").as_bytes());
+    assert_eq!(diff_frames(&a, &b), [
+        ExclusiveRect::new( 0, 0,  1, 1), // 5 unchanged cells apart: not merged
+        ExclusiveRect::new( 6, 0,  7, 1),
+        ExclusiveRect::new( 5, 1, 12, 2), // 2 unchanged cells apart: merged
+        ExclusiveRect::new( 4, 2,  5, 3),
+        ExclusiveRect::new(11, 2, 12, 3),
+    ]);
+
+    // Identical runs on consecutive rows merge vertically
+    let b = grid_from_text((12, 3), b".xx.........".repeat(3).as_slice());
+    assert_eq!(diff_frames(&a, &b), [ExclusiveRect::new(1, 0, 3, 3)]);
+
+    // Size changes redraw everything
+    assert_eq!(diff_frames(&a, &grid_from_text((1, 1), b".")), [ExclusiveRect::new(0, 0, 1, 1)]);
+    assert_eq!(diff_frames(&a, &CharInfoGrid::default()), []);
+}
+
+#[test] fn wide_chars() {
+    let wide = u16::from(b'W'); // stand-in for a double-width character
+    let mut frame = CharInfoGrid::from_cells((12, 1), vec![
+        CharInfo::new(wide, COMMON_LVB_LEADING_BYTE),
+        CharInfo::new(wide, COMMON_LVB_TRAILING_BYTE),
+        cell(b'.'),
+    ].into_iter().chain(core::iter::repeat_n(cell(b' '), 9)).collect()).unwrap();
+
+    let mut renderer = FrameRenderer::new();
+    renderer.render_vt(&frame, (0, 0), &mut Vec::new());
+
+    // Changing the trailing half redraws the whole glyph, from its leading column
+    frame[Coord::new(1, 0)].attributes |= FOREGROUND_RED;
+    frame[Coord::new(2, 0)].char = u16::from(b'a').into();
+    let mut out = Vec::new();
+    assert_eq!(renderer.render_vt(&frame, (0, 0), &mut out), [ExclusiveRect::new(0, 0, 3, 1)]);
+    assert_eq!(String::from_utf8(out).unwrap(), "\x1B[1;1H\x1B[0;30;40mWa\x1B[0m");
+
+    // Changing the leading half includes the trailing half
+    let mut next = frame.clone();
+    next[Coord::new(0, 0)].attributes |= FOREGROUND_GREEN;
+    next[Coord::new(9, 0)].char = u16::from(b'!').into();
+    assert_eq!(diff_frames(&frame, &next), [ExclusiveRect::new(0, 0, 2, 1), ExclusiveRect::new(9, 0, 10, 1)]);
+}
+
+#[test] fn negative_origin() {
+    let frame = grid_from_text((3, 2), b"abcdef");
+    let mut out = Vec::new();
+    assert_eq!(FrameRenderer::new().render_vt(&frame, (-1, -1), &mut out), [ExclusiveRect::new(0, 0, 3, 2)]);
+    assert_eq!(String::from_utf8(out).unwrap(), "\x1B[1;1H\x1B[0;30;40mef\x1B[0m");
+
+    let mut out = Vec::new();
+    FrameRenderer::new().render_vt(&frame, (-3, 0), &mut out);
+    assert_eq!(out, b"");
+}
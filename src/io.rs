@@ -429,15 +429,14 @@ pub fn read_console_input_one<'i>(console_input: &mut impl AsConsoleInputHandle)
 /// # use maulingmonkey_console_winapi_wrappers::*;
 /// # use std::io::{self, *};
 /// # let _ = (|| -> io::Result<()> {
-/// use winapi::um::wincontypes::{SMALL_RECT};
 /// let mut buffer = [CharInfo::default(); 80 * 25];
-/// let mut region = SMALL_RECT { Left: 0, Right: 80, Top: 0, Bottom: 25 };
+/// let mut region = InclusiveRect::from((0,0) .. (80,25));
 /// read_console_output(&stdout(), &mut buffer, (80,25), (0,0), &mut region)?;
 /// # Ok(())
 /// # })();
 /// ```
 ///
-pub fn read_console_output(console_output: &impl AsConsoleOutputHandle, buffer: &mut [CharInfo], buffer_size: impl Into<Coord>, buffer_coord: impl Into<Coord>, read_region: &mut SMALL_RECT) -> io::Result<()> {
+pub fn read_console_output(console_output: &impl AsConsoleOutputHandle, buffer: &mut [CharInfo], buffer_size: impl Into<Coord>, buffer_coord: impl Into<Coord>, read_region: &mut InclusiveRect) -> io::Result<()> {
     let console_output = console_output.as_raw_handle().cast();
     let buffer_size  : COORD = buffer_size.into().into();
     let buffer_coord : COORD = buffer_coord.into().into();
@@ -451,7 +450,29 @@ pub fn read_console_output(console_output: &impl AsConsoleOutputHandle, buffer:
     if buffer_size_total > buffer.len() { return Err(io::Error::new(io::ErrorKind::InvalidInput, "read_console_output(): buffer_size is larger than buffer")); }
     // TODO: read_region bounds checking?
 
-    succeeded_to_result(unsafe { ReadConsoleOutputW(console_output, buffer.as_mut_ptr().cast(), buffer_size, buffer_coord, read_region) })
+    succeeded_to_result(unsafe { ReadConsoleOutputW(console_output, buffer.as_mut_ptr().cast(), buffer_size, buffer_coord, (read_region as *mut InclusiveRect).cast()) })
+}
+
+#[doc(alias = "ReadConsoleOutput")]
+#[doc(alias = "ReadConsoleOutputW")]
+/// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/readconsoleoutput)\]
+/// [`read_console_output`] into `grid`, with its top left corner at `grid_coord`.
+///
+/// ### Example
+/// ```
+/// # use maulingmonkey_console_winapi_wrappers::*;
+/// # use std::io::{self, *};
+/// # let _ = (|| -> io::Result<()> {
+/// let mut grid = CharInfoGrid::new((80, 25), CharInfo::default());
+/// let mut region = InclusiveRect::from((0,0) .. (80,25));
+/// read_console_output_grid(&stdout(), &mut grid, (0,0), &mut region)?;
+/// # Ok(())
+/// # })();
+/// ```
+///
+pub fn read_console_output_grid(console_output: &impl AsConsoleOutputHandle, grid: &mut CharInfoGrid, grid_coord: impl Into<Coord>, read_region: &mut InclusiveRect) -> io::Result<()> {
+    let size = grid.size();
+    read_console_output(console_output, grid.cells_mut(), size, grid_coord, read_region)
 }
 
 #[doc(alias = "ReadConsoleOutputAttribute")]
@@ -505,7 +526,36 @@ pub fn read_console_output_character<'a>(console_output: &impl AsConsoleOutputHa
     Ok(&characters[.. read as _])
 }
 
-// TODO: ScrollConsoleScreenBuffer
+#[doc(alias = "ScrollConsoleScreenBuffer")]
+#[doc(alias = "ScrollConsoleScreenBufferW")]
+/// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/scrollconsolescreenbuffer)\]
+/// Moves the inclusive `scroll_rect` of a screen buffer so its top left corner lands on `destination`, filling vacated cells with `fill`.
+///
+/// Only cells within `clip_rect` (the entire screen buffer if [`None`]) are modified.
+/// See [`scroll_char_info_grid`] for a pure-Rust equivalent.
+///
+/// ### Example
+/// ```
+/// # use maulingmonkey_console_winapi_wrappers::*;
+/// # use std::io::{self, *};
+/// # let _ = (|| -> io::Result<()> {
+/// let info = get_console_screen_buffer_info(&stdout())?;
+/// let fill = CharInfo::new(b' '.into(), info.attributes);
+/// scroll_console_screen_buffer(&mut stdout(), info.window, None, Coord::new(info.window.left, info.window.top - 1), fill)?;
+/// # Ok(())
+/// # })();
+/// ```
+///
+pub fn scroll_console_screen_buffer(console_output: &mut impl AsConsoleOutputHandle, scroll_rect: impl Into<InclusiveRect>, clip_rect: Option<InclusiveRect>, destination: impl Into<Coord>, fill: impl Into<CharInfo>) -> io::Result<()> {
+    let scroll_rect : SMALL_RECT = scroll_rect.into().into();
+    let clip_rect : Option<SMALL_RECT> = clip_rect.map(Into::into);
+    let clip_rect : *const SMALL_RECT = match clip_rect.as_ref() {
+        None => null(),
+        Some(clip_rect) => clip_rect,
+    };
+    let fill : CHAR_INFO = fill.into().into();
+    succeeded_to_result(unsafe { ScrollConsoleScreenBufferW(console_output.as_raw_handle().cast(), &scroll_rect, clip_rect, destination.into().into(), &fill) })
+}
 
 #[doc(alias = "SetConsoleActiveScreenBuffer")]
 /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/setconsoleactivescreenbuffer)\]
@@ -657,15 +707,13 @@ pub fn write_console(console_output: &mut impl AsConsoleOutputHandle, buffer: &[
 /// # use maulingmonkey_console_winapi_wrappers::*;
 /// # use std::io::{self, *};
 /// # let _ = (|| -> io::Result<()> { unsafe {
-/// use winapi::um::winuser::VK_SPACE;
-///
 /// let input = KeyEventRecord {
 ///     key_down:           true.into(),
 ///     repeat_count:       0,
-///     virtual_key_code:   VK_SPACE as _,
+///     virtual_key_code:   VK_SPACE,
 ///     virtual_scan_code:  b' '.into(),
 ///     char:               u16::from(b' ').into(),
-///     control_key_state:  0,
+///     control_key_state:  Default::default(),
 /// };
 ///
 /// write_console_input(&mut stdin(), &[input.into()])?;
@@ -690,17 +738,15 @@ pub fn write_console_input(console_input: &mut impl AsConsoleInputHandle, buffer
 /// # use maulingmonkey_console_winapi_wrappers::*;
 /// # use std::io::{self, *};
 /// # let _ = (|| -> io::Result<()> {
-/// use winapi::um::{wincontypes::*, winuser::*};
-///
 /// let output = [CharInfo::new(u16::from(b' '), Attributes::FOREGROUND_GREEN)];
-/// let mut region = SMALL_RECT { Left: 10, Right: 11, Top: 20, Bottom: 21 };
+/// let mut region = InclusiveRect::new(10, 20, 10, 20);
 ///
 /// write_console_output(&mut stdout(), &output, (1,1), (0, 0), &mut region)?;
 /// # Ok(())
 /// # })();
 /// ```
 ///
-pub fn write_console_output(console_output: &mut impl AsConsoleOutputHandle, buffer: &[CharInfo], buffer_size: impl Into<Coord>, buffer_coord: impl Into<Coord>, write_region: &mut SMALL_RECT) -> io::Result<()> {
+pub fn write_console_output(console_output: &mut impl AsConsoleOutputHandle, buffer: &[CharInfo], buffer_size: impl Into<Coord>, buffer_coord: impl Into<Coord>, write_region: &mut InclusiveRect) -> io::Result<()> {
     let console_output = console_output.as_raw_handle().cast();
     let buffer_size  : COORD = buffer_size.into().into();
     let buffer_coord : COORD = buffer_coord.into().into();
@@ -714,7 +760,28 @@ pub fn write_console_output(console_output: &mut impl AsConsoleOutputHandle, buf
     if buffer_size_total > buffer.len() { return Err(io::Error::new(io::ErrorKind::InvalidInput, "write_console_output(): buffer_size is larger than buffer")); }
     // TODO: write_region bounds checking?
 
-    succeeded_to_result(unsafe { WriteConsoleOutputW(console_output, buffer.as_ptr().cast(), buffer_size, buffer_coord, write_region) })
+    succeeded_to_result(unsafe { WriteConsoleOutputW(console_output, buffer.as_ptr().cast(), buffer_size, buffer_coord, (write_region as *mut InclusiveRect).cast()) })
+}
+
+#[doc(alias = "WriteConsoleOutput")]
+#[doc(alias = "WriteConsoleOutputW")]
+/// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/writeconsoleoutput)\]
+/// [`write_console_output`] from `grid`, starting at `grid_coord`.
+///
+/// ### Example
+/// ```
+/// # use maulingmonkey_console_winapi_wrappers::*;
+/// # use std::io::{self, *};
+/// # let _ = (|| -> io::Result<()> {
+/// let grid = CharInfoGrid::new((10, 1), CharInfo::new(u16::from(b'-'), Attributes::FOREGROUND_GREEN));
+/// let mut region = InclusiveRect::from((0,0) .. (10,1));
+/// write_console_output_grid(&mut stdout(), &grid, (0,0), &mut region)?;
+/// # Ok(())
+/// # })();
+/// ```
+///
+pub fn write_console_output_grid(console_output: &mut impl AsConsoleOutputHandle, grid: &CharInfoGrid, grid_coord: impl Into<Coord>, write_region: &mut InclusiveRect) -> io::Result<()> {
+    write_console_output(console_output, grid.cells(), grid.size(), grid_coord, write_region)
 }
 
 #[doc(alias = "WriteConsoleOutputAttribute")]
@@ -0,0 +1,169 @@
+use crate::*;
+
+use std::collections::VecDeque;
+use std::ffi::{OsStr, OsString};
+use std::io;
+
+
+
+/// \[~~microsoft.com~~\]
+/// A pure-Rust, in-memory [`ConsoleBackend`]: a [`MemoryScreenBuffer`], an input queue, modes, title, and font.
+///
+/// Reads never block: reading from an empty input queue returns an empty slice.
+/// Tests typically queue input with [`write_console_input`](ConsoleBackend::write_console_input),
+/// run the code under test, then inspect [`screen_buffer`](Self::screen_buffer).
+///
+/// ### Example
+/// ```
+/// # use maulingmonkey_console_winapi_wrappers::*;
+/// let mut console = MemoryConsole::new((80, 25));
+/// let key = KeyEventRecord { key_down: true.into(), repeat_count: 2, virtual_key_code: VK_A, virtual_scan_code: 0x1E, char: u16::from(b'a').into(), control_key_state: Default::default() };
+/// console.write_console_input(&[key.into()]).unwrap();
+///
+/// let mut buffer = [0u16; 16];
+/// assert_eq!(console.read_console(&mut buffer).unwrap(), &[b'a' as u16, b'a' as u16]);
+/// assert_eq!(console.get_number_of_console_input_events().unwrap(), 0);
+/// ```
+///
+#[derive(Clone, Debug)]
+pub struct MemoryConsole {
+    screen_buffer:      MemoryScreenBuffer,
+    input:              VecDeque<InputRecord>,
+    input_mode:         InputMode,
+    title:              OsString,
+    original_title:     OsString,
+    font:               ConsoleFontInfoEx,
+    mouse_buttons:      usize,
+}
+
+impl MemoryConsole {
+    /// Creates a console with a [`MemoryScreenBuffer`] of `size` cells, an empty input queue, and conhost's default input mode.
+    pub fn new(size: impl Into<Coord>) -> Self {
+        let face_name : Vec<u16> = "Consolas".encode_utf16().collect();
+        Self {
+            screen_buffer:      MemoryScreenBuffer::new(size),
+            input:              VecDeque::new(),
+            input_mode:         ENABLE_PROCESSED_INPUT | ENABLE_LINE_INPUT | ENABLE_ECHO_INPUT | ENABLE_MOUSE_INPUT | ENABLE_INSERT_MODE | ENABLE_QUICK_EDIT_MODE | ENABLE_EXTENDED_FLAGS,
+            title:              OsString::new(),
+            original_title:     OsString::new(),
+            font:               ConsoleFontInfoEx {
+                font_size:          Coord::new(8, 16),
+                font_family:        0x36, // FF_MODERN | TMPF_TRUETYPE | TMPF_VECTOR
+                font_weight:        400,  // FW_NORMAL
+                face_name:          abistr::CStrBuf::from_truncate(&face_name),
+                .. Default::default()
+            },
+            mouse_buttons:      3,
+        }
+    }
+
+    /// The emulated screen buffer.
+    pub fn screen_buffer(&self) -> &MemoryScreenBuffer { &self.screen_buffer }
+
+    /// The emulated screen buffer.
+    pub fn screen_buffer_mut(&mut self) -> &mut MemoryScreenBuffer { &mut self.screen_buffer }
+
+    /// Sets the title returned by [`get_console_original_title`](ConsoleBackend::get_console_original_title) and [`get_console_title`](ConsoleBackend::get_console_title).
+    pub fn set_original_title(&mut self, title: impl AsRef<OsStr>) {
+        self.original_title = title.as_ref().into();
+        self.title          = title.as_ref().into();
+    }
+
+    /// Sets the font returned by [`get_current_console_font_ex`](ConsoleBackend::get_current_console_font_ex).
+    pub fn set_font(&mut self, font: ConsoleFontInfoEx) { self.font = font; }
+
+    /// Sets the value returned by [`get_number_of_console_mouse_buttons`](ConsoleBackend::get_number_of_console_mouse_buttons).
+    pub fn set_number_of_mouse_buttons(&mut self, buttons: usize) { self.mouse_buttons = buttons; }
+}
+
+impl ConsoleBackend for MemoryConsole {
+    fn fill_console_output_attribute(&mut self, attribute: Attributes, length: u32, write_coord: Coord) -> io::Result<u32> { self.screen_buffer.fill_console_output_attribute(attribute, length, write_coord) }
+    fn fill_console_output_character(&mut self, character: char, length: u32, write_coord: Coord) -> io::Result<u32> { self.screen_buffer.fill_console_output_character(character, length, write_coord) }
+    fn flush_console_input_buffer(&mut self) -> io::Result<()> { self.input.clear(); Ok(()) }
+    fn get_console_cursor_info(&self) -> io::Result<ConsoleCursorInfo> { self.screen_buffer.get_console_cursor_info() }
+    fn get_console_display_mode(&self) -> io::Result<u32> { Ok(0) }
+    fn get_console_input_mode(&self) -> io::Result<InputMode> { Ok(self.input_mode) }
+    fn get_console_output_mode(&self) -> io::Result<OutputMode> { self.screen_buffer.get_console_mode() }
+    fn get_console_original_title(&self) -> io::Result<OsString> { Ok(self.original_title.clone()) }
+    fn get_console_screen_buffer_info(&self) -> io::Result<ConsoleScreenBufferInfo> { self.screen_buffer.get_console_screen_buffer_info() }
+    fn get_console_screen_buffer_info_ex(&self) -> io::Result<ConsoleScreenBufferInfoEx> { self.screen_buffer.get_console_screen_buffer_info_ex() }
+    fn get_console_title(&self) -> io::Result<OsString> { Ok(self.title.clone()) }
+    fn get_current_console_font(&self, _maximum_window: bool) -> io::Result<ConsoleFontInfo> { Ok(ConsoleFontInfo { font: self.font.font, font_size: self.font.font_size }) }
+    fn get_current_console_font_ex(&self, _maximum_window: bool) -> io::Result<ConsoleFontInfoEx> { Ok(self.font) }
+    fn get_largest_console_window_size(&self) -> io::Result<Coord> { Ok(self.screen_buffer.get_console_screen_buffer_info()?.maximum_window_size) }
+    fn get_number_of_console_input_events(&self) -> io::Result<usize> { Ok(self.input.len()) }
+    fn get_number_of_console_mouse_buttons(&self) -> io::Result<usize> { Ok(self.mouse_buttons) }
+    fn read_console_output(&self, buffer: &mut [CharInfo], buffer_size: Coord, buffer_coord: Coord, read_region: &mut InclusiveRect) -> io::Result<()> { self.screen_buffer.read_console_output(buffer, buffer_size, buffer_coord, read_region) }
+    fn read_console_output_attribute<'a>(&self, attributes: &'a mut [Attributes], read_coord: Coord) -> io::Result<&'a [Attributes]> { self.screen_buffer.read_console_output_attribute(attributes, read_coord) }
+    fn read_console_output_character<'a>(&self, characters: &'a mut [u16], read_coord: Coord) -> io::Result<&'a [u16]> { self.screen_buffer.read_console_output_character(characters, read_coord) }
+    fn scroll_console_screen_buffer(&mut self, scroll_rect: InclusiveRect, clip_rect: Option<InclusiveRect>, destination: Coord, fill: CharInfo) -> io::Result<()> { self.screen_buffer.scroll_console_screen_buffer(scroll_rect, clip_rect, destination, fill) }
+    fn set_console_cursor_info(&mut self, info: ConsoleCursorInfo) -> io::Result<()> { self.screen_buffer.set_console_cursor_info(info) }
+    fn set_console_cursor_position(&mut self, cursor_position: Coord) -> io::Result<()> { self.screen_buffer.set_console_cursor_position(cursor_position) }
+    fn set_console_input_mode(&mut self, mode: InputMode) -> io::Result<()> { self.input_mode = mode; Ok(()) }
+    fn set_console_output_mode(&mut self, mode: OutputMode) -> io::Result<()> { self.screen_buffer.set_console_mode(mode) }
+    fn set_console_screen_buffer_size(&mut self, size: Coord) -> io::Result<()> { self.screen_buffer.set_console_screen_buffer_size(size) }
+    fn set_console_text_attribute(&mut self, attributes: Attributes) -> io::Result<()> { self.screen_buffer.set_console_text_attribute(attributes) }
+    fn set_console_title(&mut self, title: &OsStr) -> io::Result<()> { self.title = title.into(); Ok(()) }
+    fn set_console_window_info(&mut self, absolute: bool, console_window: InclusiveRect) -> io::Result<()> { self.screen_buffer.set_console_window_info(absolute, console_window) }
+    fn write_console(&mut self, buffer: &[u16]) -> io::Result<usize> { self.screen_buffer.write_console(buffer) }
+    fn write_console_input(&mut self, buffer: &[InputRecord]) -> io::Result<usize> { self.input.extend(buffer.iter().copied()); Ok(buffer.len()) }
+    fn write_console_output(&mut self, buffer: &[CharInfo], buffer_size: Coord, buffer_coord: Coord, write_region: &mut InclusiveRect) -> io::Result<()> { self.screen_buffer.write_console_output(buffer, buffer_size, buffer_coord, write_region) }
+    fn write_console_output_attribute(&mut self, attributes: &[Attributes], write_coord: Coord) -> io::Result<usize> { self.screen_buffer.write_console_output_attribute(attributes, write_coord) }
+    fn write_console_output_character(&mut self, characters: &[u16], write_coord: Coord) -> io::Result<usize> { self.screen_buffer.write_console_output_character(characters, write_coord) }
+
+    fn peek_console_input_with<'i>(&self, buffer: &'i mut [InputRecord]) -> io::Result<&'i [InputRecord]> {
+        let n = buffer.len().min(self.input.len());
+        for (dst, src) in buffer.iter_mut().zip(self.input.iter()) { *dst = *src; }
+        Ok(&buffer[..n])
+    }
+
+    fn read_console_input_with<'i>(&mut self, buffer: &'i mut [InputRecord]) -> io::Result<&'i [InputRecord]> {
+        let n = buffer.len().min(self.input.len());
+        for (dst, src) in buffer.iter_mut().zip(self.input.drain(..n)) { *dst = src; }
+        Ok(&buffer[..n])
+    }
+
+    /// Converts queued key-down events into characters (honoring `repeat_count`), discarding all other records.
+    /// Line editing ([`ENABLE_LINE_INPUT`]) and echo ([`ENABLE_ECHO_INPUT`]) are not emulated.
+    fn read_console<'i>(&mut self, buffer: &'i mut [u16]) -> io::Result<&'i [u16]> {
+        let mut n = 0;
+        while n < buffer.len() {
+            let Some(record) = self.input.front_mut() else { break };
+            let key = match <&KeyEventRecord>::try_from(&*record) {
+                Ok(key) if bool::from(key.key_down) && key.char.unicode_char() != 0 => *key,
+                _ => { self.input.pop_front(); continue },
+            };
+            let repeat = usize::from(key.repeat_count.max(1));
+            let take = repeat.min(buffer.len() - n);
+            buffer[n .. n + take].fill(key.char.unicode_char());
+            n += take;
+            if take == repeat {
+                self.input.pop_front();
+            } else {
+                let mut rest = key;
+                rest.repeat_count = (repeat - take) as u16;
+                *record = rest.into();
+            }
+        }
+        Ok(&buffer[..n])
+    }
+}
+
+
+
+#[test] fn input_queue() {
+    let mut console = MemoryConsole::new((80, 25));
+    let down = KeyEventRecord { key_down: true.into(),  repeat_count: 3, virtual_key_code: VK_A, virtual_scan_code: 0x1E, char: u16::from(b'a').into(), control_key_state: Default::default() };
+    let up   = KeyEventRecord { key_down: false.into(), repeat_count: 1, .. down };
+    console.write_console_input(&[down.into(), up.into(), down.into()]).unwrap();
+
+    let mut records = [InputRecord::default(); 8];
+    assert_eq!(console.peek_console_input_with(&mut records).unwrap().len(), 3);
+    assert_eq!(console.get_number_of_console_input_events().unwrap(), 3);
+
+    let mut chars = [0u16; 2];
+    assert_eq!(console.read_console(&mut chars).unwrap(), &[b'a' as u16; 2]);
+    assert_eq!(console.read_console(&mut chars).unwrap(), &[b'a' as u16; 2]); // 1 remaining, skip the key up, 1 from the next
+    assert_eq!(console.read_console_input_with(&mut records).unwrap().len(), 1);
+    assert_eq!(console.read_console(&mut chars).unwrap(), &[]);
+}
@@ -0,0 +1,772 @@
+use crate::*;
+
+use std::io;
+
+
+
+/// \[~~microsoft.com~~\]
+/// A pure-Rust, in-memory emulation of a console screen buffer.
+///
+/// Mirrors conhost's behavior for the linear and rectangular I/O functions (clipping at the edges of the buffer,
+/// "cells written" counts, etc.) so code which draws through e.g. [`write_console_output`](https://learn.microsoft.com/en-us/windows/console/writeconsoleoutput)
+/// can be exercised without a real console, including on non-Windows targets.
+///
+/// Rectangles (`write_region`, `read_region`, [`ConsoleScreenBufferInfo::window`]) are inclusive, as they are for Win32.
+///
+/// ### Example
+/// ```
+/// # use maulingmonkey_console_winapi_wrappers::*;
+/// let mut buffer = MemoryScreenBuffer::new((80, 25));
+/// buffer.write_console_output_character(&[b'H' as u16, b'i' as u16], (0, 0)).unwrap();
+/// assert_eq!(buffer.cell((1, 0)).unwrap().char.unicode_char(), b'i' as u16);
+/// ```
+///
+#[derive(Clone, Debug)]
+pub struct MemoryScreenBuffer {
+    size:                   Coord,
+    cells:                  Vec<CharInfo>,
+    cursor_position:        Coord,
+    cursor_info:            ConsoleCursorInfo,
+    attributes:             Attributes,
+    popup_attributes:       Attributes,
+    window:                 InclusiveRect,
+    maximum_window_size:    Coord,
+    color_table:            [ColorRef; 16],
+    mode:                   OutputMode,
+}
+
+impl MemoryScreenBuffer {
+    /// Creates a buffer of `size` cells, filled with spaces using conhost's default attributes (light gray on black.)
+    /// The window covers the entire buffer.  Negative dimensions are treated as `0`.
+    ///
+    /// Other state matches a freshly created conhost screen buffer: a visible 25% cursor at `(0, 0)`,
+    /// [`ENABLE_PROCESSED_OUTPUT`] | [`ENABLE_WRAP_AT_EOL_OUTPUT`], and the [Campbell](ColorScheme::CAMPBELL) color table.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let buffer = MemoryScreenBuffer::new((80, 25));
+    /// assert_eq!(buffer.size(), Coord::new(80, 25));
+    /// assert_eq!(buffer.cells().len(), 80 * 25);
+    /// ```
+    ///
+    pub fn new(size: impl Into<Coord>) -> Self {
+        let size = size.into();
+        let size = Coord::new(size.x.max(0), size.y.max(0));
+        let scheme = ColorScheme::CAMPBELL;
+        let attributes = scheme.attributes;
+        Self {
+            size,
+            cells:                  vec![CharInfo::new(b' '.into(), attributes); (size.x as usize) * (size.y as usize)],
+            cursor_position:        Coord::new(0, 0),
+            cursor_info:            ConsoleCursorInfo::new(25, true),
+            attributes,
+            popup_attributes:       scheme.popup_attributes,
+            window:                 InclusiveRect::new(0, 0, size.x - 1, size.y - 1),
+            maximum_window_size:    size,
+            color_table:            scheme.color_table,
+            mode:                   ENABLE_PROCESSED_OUTPUT | ENABLE_WRAP_AT_EOL_OUTPUT,
+        }
+    }
+
+    /// The size of the buffer, in cells.
+    pub fn size(&self) -> Coord { self.size }
+
+    /// All cells of the buffer, in row-major order.
+    pub fn cells(&self) -> &[CharInfo] { &self.cells }
+
+    /// All cells of the buffer, in row-major order.
+    pub fn cells_mut(&mut self) -> &mut [CharInfo] { &mut self.cells }
+
+    /// Returns the cell at `coord`, or [`None`] if `coord` is outside of the buffer.
+    pub fn cell(&self, coord: impl Into<Coord>) -> Option<CharInfo> {
+        self.index(coord.into()).map(|i| self.cells[i])
+    }
+
+    fn index(&self, coord: Coord) -> Option<usize> {
+        if !(0 .. self.size.x).contains(&coord.x) { return None }
+        if !(0 .. self.size.y).contains(&coord.y) { return None }
+        Some((coord.y as usize) * (self.size.x as usize) + (coord.x as usize))
+    }
+
+    /// The cells from `coord` (inclusive) through the end of the buffer, wrapping from row to row.
+    /// Empty if `coord` is outside of the buffer.
+    fn linear_mut(&mut self, coord: Coord) -> &mut [CharInfo] {
+        match self.index(coord) {
+            Some(i) => &mut self.cells[i..],
+            None    => &mut [],
+        }
+    }
+
+    fn linear(&self, coord: Coord) -> &[CharInfo] {
+        match self.index(coord) {
+            Some(i) => &self.cells[i..],
+            None    => &[],
+        }
+    }
+
+
+
+    #[doc(alias = "FillConsoleOutputAttribute")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/fillconsoleoutputattribute)\]
+    /// Alters `length` cells starting at `coord` to have the same [`Attributes`].
+    ///
+    /// Wraps from row to row, stops at the end of the buffer, and returns the number of cells modified.
+    /// Like conhost, a `write_coord` outside of the buffer modifies nothing and returns `Ok(0)`.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let mut buffer = MemoryScreenBuffer::new((10, 2));
+    /// assert_eq!(12, buffer.fill_console_output_attribute(FOREGROUND_GREEN, 12, (0,0)).unwrap());
+    /// assert_eq!( 5, buffer.fill_console_output_attribute(FOREGROUND_GREEN, 12, (5,1)).unwrap()); // clipped
+    /// assert_eq!( 0, buffer.fill_console_output_attribute(FOREGROUND_GREEN, 12, (0,2)).unwrap()); // out of bounds
+    /// ```
+    ///
+    pub fn fill_console_output_attribute(&mut self, attribute: impl Into<Attributes>, length: u32, write_coord: impl Into<Coord>) -> io::Result<u32> {
+        let attribute = attribute.into();
+        let cells = self.linear_mut(write_coord.into());
+        let n = cells.len().min(length as usize);
+        for cell in &mut cells[..n] { cell.attributes = attribute; }
+        Ok(n as _)
+    }
+
+    #[doc(alias = "FillConsoleOutputCharacter")]
+    #[doc(alias = "FillConsoleOutputCharacterW")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/fillconsoleoutputcharacter)\]
+    /// Alters `length` cells starting at `coord` to have the same `character`.
+    ///
+    /// Wraps from row to row, stops at the end of the buffer, and returns the number of cells modified.
+    /// Like conhost, a `write_coord` outside of the buffer modifies nothing and returns `Ok(0)`.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let mut buffer = MemoryScreenBuffer::new((10, 2));
+    /// assert_eq!(12, buffer.fill_console_output_character('X', 12, (0,0)).unwrap());
+    /// assert_eq!( 5, buffer.fill_console_output_character('X', 12, (5,1)).unwrap()); // clipped
+    /// assert!(buffer.fill_console_output_character('\u{10000}', 1, (0,0)).is_err());
+    /// ```
+    ///
+    pub fn fill_console_output_character(&mut self, character: char, length: u32, write_coord: impl Into<Coord>) -> io::Result<u32> {
+        let character = u16::try_from(character as u32).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "MemoryScreenBuffer::fill_console_output_character: `character` exceeds maximum codepoint U+FFFF"))?;
+        let cells = self.linear_mut(write_coord.into());
+        let n = cells.len().min(length as usize);
+        for cell in &mut cells[..n] { cell.char = character.into(); }
+        Ok(n as _)
+    }
+
+    #[doc(alias = "GetConsoleCursorInfo")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/getconsolecursorinfo)\]
+    /// Retrieves the size and visibility of the cursor.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let screen = MemoryScreenBuffer::new((80, 25));
+    /// let info = screen.get_console_cursor_info().unwrap();
+    /// assert_eq!(info.size, 25);
+    /// assert!(bool::from(info.visible));
+    /// ```
+    ///
+    pub fn get_console_cursor_info(&self) -> io::Result<ConsoleCursorInfo> {
+        Ok(self.cursor_info)
+    }
+
+    #[doc(alias = "GetConsoleMode")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/getconsolemode)\]
+    /// Retrieves the current [`OutputMode`] of the screen buffer.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let screen = MemoryScreenBuffer::new((80, 25));
+    /// assert_eq!(screen.get_console_mode().unwrap(), ENABLE_PROCESSED_OUTPUT | ENABLE_WRAP_AT_EOL_OUTPUT);
+    /// ```
+    ///
+    pub fn get_console_mode(&self) -> io::Result<OutputMode> {
+        Ok(self.mode)
+    }
+
+    #[doc(alias = "GetConsoleScreenBufferInfo")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/getconsolescreenbufferinfo)\]
+    /// Retrieves information about the screen buffer.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let buffer = MemoryScreenBuffer::new((80, 25));
+    /// let info = buffer.get_console_screen_buffer_info().unwrap();
+    /// assert_eq!(info.size, Coord::new(80, 25));
+    /// assert_eq!(info.window, InclusiveRect::new(0, 0, 79, 24));
+    /// ```
+    ///
+    pub fn get_console_screen_buffer_info(&self) -> io::Result<ConsoleScreenBufferInfo> {
+        Ok(ConsoleScreenBufferInfo {
+            size:                   self.size,
+            cursor_position:        self.cursor_position,
+            attributes:             self.attributes,
+            window:                 self.window,
+            maximum_window_size:    self.maximum_window_size,
+        })
+    }
+
+    #[doc(alias = "GetConsoleScreenBufferInfoEx")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/getconsolescreenbufferinfoex)\]
+    /// Retrieves extended information about the screen buffer.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let screen = MemoryScreenBuffer::new((80, 25));
+    /// let info = screen.get_console_screen_buffer_info_ex().unwrap();
+    /// assert_eq!(info.window, InclusiveRect::new(0, 0, 79, 24));
+    /// assert_eq!(info.color_table[0], ColorRef::from_rgb([12, 12, 12]));
+    /// ```
+    ///
+    pub fn get_console_screen_buffer_info_ex(&self) -> io::Result<ConsoleScreenBufferInfoEx> {
+        Ok(ConsoleScreenBufferInfoEx {
+            size:                   self.size,
+            cursor_position:        self.cursor_position,
+            attributes:             self.attributes,
+            window:                 self.window,
+            maximum_window_size:    self.maximum_window_size,
+            popup_attributes:       self.popup_attributes,
+            color_table:            self.color_table,
+            .. Default::default()
+        })
+    }
+
+    #[doc(alias = "ReadConsoleOutput")]
+    #[doc(alias = "ReadConsoleOutputW")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/readconsoleoutput)\]
+    /// Reads a rectangular block of characters and [Attributes] from the screen buffer.
+    ///
+    /// `read_region` is inclusive.  It's clipped to both the screen buffer and the space available in `buffer` after `buffer_coord`,
+    /// and updated to the region actually read.  If nothing remains after clipping, this fails like conhost does.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let screen = MemoryScreenBuffer::new((80, 25));
+    /// let mut buffer = [CharInfo::default(); 10 * 10];
+    /// let mut region = InclusiveRect::new(75, 20, 84, 29);
+    /// screen.read_console_output(&mut buffer, (10,10), (0,0), &mut region).unwrap();
+    /// assert_eq!(region, InclusiveRect::new(75, 20, 79, 24));
+    /// ```
+    ///
+    pub fn read_console_output(&self, buffer: &mut [CharInfo], buffer_size: impl Into<Coord>, buffer_coord: impl Into<Coord>, read_region: &mut InclusiveRect) -> io::Result<()> {
+        let (buffer_size, buffer_coord) = (buffer_size.into(), buffer_coord.into());
+        check_buffer("MemoryScreenBuffer::read_console_output", buffer.len(), buffer_size, buffer_coord)?;
+        let (screen, source) = self.clip_rect(buffer_size, buffer_coord, read_region, "MemoryScreenBuffer::read_console_output")?;
+        for dy in 0 .. screen.bottom - screen.top + 1 {
+            let w = (screen.right - screen.left + 1) as usize;
+            let src = self.index(Coord::new(screen.left, screen.top + dy)).unwrap();
+            let dst = (source.y + dy) as usize * buffer_size.x as usize + source.x as usize;
+            buffer[dst .. dst + w].copy_from_slice(&self.cells[src .. src + w]);
+        }
+        Ok(())
+    }
+
+    #[doc(alias = "ReadConsoleOutput")]
+    #[doc(alias = "ReadConsoleOutputW")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/readconsoleoutput)\]
+    /// [`read_console_output`](Self::read_console_output) into `grid`, with its top left corner at `grid_coord`.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let screen = MemoryScreenBuffer::new((80, 25));
+    /// let mut grid = CharInfoGrid::new((80, 25), CharInfo::default());
+    /// let mut region = InclusiveRect::from((0, 0) .. (80, 25));
+    /// screen.read_console_output_grid(&mut grid, (0, 0), &mut region).unwrap();
+    /// assert_eq!(grid.cells(), screen.cells());
+    /// ```
+    ///
+    pub fn read_console_output_grid(&self, grid: &mut CharInfoGrid, grid_coord: impl Into<Coord>, read_region: &mut InclusiveRect) -> io::Result<()> {
+        let size = grid.size();
+        self.read_console_output(grid.cells_mut(), size, grid_coord, read_region)
+    }
+
+    #[doc(alias = "ReadConsoleOutputAttribute")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/readconsoleoutputattribute)\]
+    /// Reads a linear line of [Attributes] from the screen buffer, wrapping from row to row and stopping at the end of the buffer.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let screen = MemoryScreenBuffer::new((10, 2));
+    /// let mut buffer = [Attributes::default(); 80];
+    /// assert_eq!(screen.read_console_output_attribute(&mut buffer, (5,1)).unwrap().len(), 5);
+    /// ```
+    ///
+    pub fn read_console_output_attribute<'a>(&self, attributes: &'a mut [Attributes], read_coord: impl Into<Coord>) -> io::Result<&'a [Attributes]> {
+        let cells = self.linear(read_coord.into());
+        let n = cells.len().min(attributes.len());
+        for (a, cell) in attributes.iter_mut().zip(cells) { *a = cell.attributes; }
+        Ok(&attributes[..n])
+    }
+
+    #[doc(alias = "ReadConsoleOutputCharacter")]
+    #[doc(alias = "ReadConsoleOutputCharacterW")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/readconsoleoutputcharacter)\]
+    /// Reads a linear line of characters from the screen buffer, wrapping from row to row and stopping at the end of the buffer.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let screen = MemoryScreenBuffer::new((10, 2));
+    /// let mut buffer = [0u16; 80];
+    /// assert_eq!(screen.read_console_output_character(&mut buffer, (0,0)).unwrap(), &[b' ' as u16; 20]);
+    /// ```
+    ///
+    pub fn read_console_output_character<'a>(&self, characters: &'a mut [u16], read_coord: impl Into<Coord>) -> io::Result<&'a [u16]> {
+        let cells = self.linear(read_coord.into());
+        let n = cells.len().min(characters.len());
+        for (c, cell) in characters.iter_mut().zip(cells) { *c = cell.char.unicode_char(); }
+        Ok(&characters[..n])
+    }
+
+    #[doc(alias = "ScrollConsoleScreenBuffer")]
+    #[doc(alias = "ScrollConsoleScreenBufferW")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/scrollconsolescreenbuffer)\]
+    /// Moves the inclusive `scroll_rect` so its top left corner lands on `destination`, filling vacated cells with `fill`.
+    ///
+    /// Only cells within `clip_rect` (the entire buffer if [`None`]) are modified.  See [`scroll_char_info_grid`] for details.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let mut screen = MemoryScreenBuffer::new((80, 25));
+    /// screen.write_console_output_character(&[b'X' as u16], (0, 1)).unwrap();
+    /// screen.scroll_console_screen_buffer(InclusiveRect::new(0, 0, 79, 24), None, (0, -1), CharInfo::new(b' '.into(), Attributes::default())).unwrap();
+    /// assert_eq!(screen.cell((0, 0)).unwrap().char.unicode_char(), b'X' as u16);
+    /// ```
+    ///
+    pub fn scroll_console_screen_buffer(&mut self, scroll_rect: impl Into<InclusiveRect>, clip_rect: Option<InclusiveRect>, destination: impl Into<Coord>, fill: impl Into<CharInfo>) -> io::Result<()> {
+        scroll_char_info_grid(&mut self.cells, self.size, scroll_rect.into(), clip_rect, destination.into(), fill.into())
+    }
+
+    #[doc(alias = "SetConsoleCursorInfo")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/setconsolecursorinfo)\]
+    /// Sets the size and visibility of the cursor.  Like [`set_console_cursor_info`](crate::set_console_cursor_info), `info.size` is clamped to `1 ..= 100`.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let mut screen = MemoryScreenBuffer::new((80, 25));
+    /// screen.set_console_cursor_info(ConsoleCursorInfo::new(200, false)).unwrap();
+    /// assert_eq!(screen.get_console_cursor_info().unwrap().size, 100);
+    /// ```
+    ///
+    pub fn set_console_cursor_info(&mut self, info: impl Into<ConsoleCursorInfo>) -> io::Result<()> {
+        let info = info.into();
+        self.cursor_info = ConsoleCursorInfo::new(info.size.clamp(1, 100), info.visible.into());
+        Ok(())
+    }
+
+    #[doc(alias = "SetConsoleCursorPosition")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/setconsolecursorposition)\]
+    /// Sets the cursor position, scrolling the window if necessary to keep the cursor visible.
+    ///
+    /// Fails if `cursor_position` is outside of the screen buffer.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let mut screen = MemoryScreenBuffer::new((80, 25));
+    /// screen.set_console_cursor_position((1, 2)).unwrap();
+    /// assert_eq!(screen.get_console_screen_buffer_info().unwrap().cursor_position, Coord::new(1, 2));
+    /// assert!(screen.set_console_cursor_position((80, 0)).is_err());
+    /// ```
+    ///
+    pub fn set_console_cursor_position(&mut self, cursor_position: impl Into<Coord>) -> io::Result<()> {
+        let cursor_position = cursor_position.into();
+        if self.index(cursor_position).is_none() { return Err(io::Error::new(io::ErrorKind::InvalidInput, "MemoryScreenBuffer::set_console_cursor_position(): `cursor_position` is outside of the screen buffer")) }
+        self.cursor_position = cursor_position;
+        self.scroll_window_to_cursor();
+        Ok(())
+    }
+
+    #[doc(alias = "SetConsoleMode")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/setconsolemode)\]
+    /// Sets the [`OutputMode`] of the screen buffer, which affects [`write_console`](Self::write_console).
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let mut screen = MemoryScreenBuffer::new((80, 25));
+    /// screen.set_console_mode(ENABLE_PROCESSED_OUTPUT).unwrap();
+    /// assert_eq!(screen.get_console_mode().unwrap(), ENABLE_PROCESSED_OUTPUT);
+    /// ```
+    ///
+    pub fn set_console_mode(&mut self, mode: OutputMode) -> io::Result<()> {
+        self.mode = mode;
+        Ok(())
+    }
+
+    #[doc(alias = "SetConsoleScreenBufferSize")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/setconsolescreenbuffersize)\]
+    /// Changes the size of the screen buffer, preserving the contents of the top left corner.
+    ///
+    /// Like conhost, this fails if `size` is smaller than the current window.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let mut screen = MemoryScreenBuffer::new((80, 25));
+    /// screen.set_console_screen_buffer_size((120, 9001)).unwrap();
+    /// assert_eq!(screen.size(), Coord::new(120, 9001));
+    /// assert!(screen.set_console_screen_buffer_size((40, 9001)).is_err());
+    /// ```
+    ///
+    pub fn set_console_screen_buffer_size(&mut self, size: impl Into<Coord>) -> io::Result<()> {
+        let size = size.into();
+        let w = &self.window;
+        if size.x <= w.right - w.left || size.y <= w.bottom - w.top {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "MemoryScreenBuffer::set_console_screen_buffer_size(): `size` is smaller than the window"));
+        }
+
+        let blank = CharInfo::new(b' '.into(), self.attributes);
+        let mut cells = vec![blank; (size.x as usize) * (size.y as usize)];
+        let copy_w = size.x.min(self.size.x) as usize;
+        for y in 0 .. size.y.min(self.size.y) as usize {
+            let src = y * self.size.x as usize;
+            let dst = y * size.x as usize;
+            cells[dst .. dst + copy_w].copy_from_slice(&self.cells[src .. src + copy_w]);
+        }
+
+        self.cells = cells;
+        self.size = size;
+        self.maximum_window_size = size;
+        self.cursor_position = Coord::new(self.cursor_position.x.min(size.x - 1), self.cursor_position.y.min(size.y - 1));
+
+        // Keep the window inside the buffer
+        let w = self.window;
+        let dx = (w.right  - (size.x - 1)).max(0);
+        let dy = (w.bottom - (size.y - 1)).max(0);
+        self.window = InclusiveRect::new(w.left - dx, w.top - dy, w.right - dx, w.bottom - dy);
+        Ok(())
+    }
+
+    #[doc(alias = "SetConsoleTextAttribute")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/setconsoletextattribute)\]
+    /// Sets the default [Attributes] reported by [`get_console_screen_buffer_info`](Self::get_console_screen_buffer_info).
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let mut screen = MemoryScreenBuffer::new((80, 25));
+    /// screen.set_console_text_attribute(FOREGROUND_GREEN).unwrap();
+    /// assert_eq!(screen.get_console_screen_buffer_info().unwrap().attributes, FOREGROUND_GREEN);
+    /// ```
+    ///
+    pub fn set_console_text_attribute(&mut self, attributes: impl Into<Attributes>) -> io::Result<()> {
+        self.attributes = attributes.into();
+        Ok(())
+    }
+
+    #[doc(alias = "SetConsoleWindowInfo")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/setconsolewindowinfo)\]
+    /// Sets the size and position of the screen buffer's window.
+    ///
+    /// If `absolute`, `console_window` is the new inclusive window.  Otherwise, its fields are added to the current window's edges.
+    /// Fails if the resulting window would be empty or extend outside of the screen buffer.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let mut screen = MemoryScreenBuffer::new((80, 100));
+    /// screen.set_console_window_info(true, InclusiveRect::new(0, 0, 79, 24)).unwrap();
+    /// screen.set_console_window_info(false, InclusiveRect::new(0, 10, 0, 10)).unwrap();
+    /// assert_eq!(screen.get_console_screen_buffer_info().unwrap().window, InclusiveRect::new(0, 10, 79, 34));
+    /// assert!(screen.set_console_window_info(true, InclusiveRect::new(0, 0, 80, 24)).is_err());
+    /// ```
+    ///
+    pub fn set_console_window_info(&mut self, absolute: bool, console_window: impl Into<InclusiveRect>) -> io::Result<()> {
+        let r = console_window.into();
+        let w = if absolute { r } else {
+            let w = self.window;
+            InclusiveRect::new(w.left + r.left, w.top + r.top, w.right + r.right, w.bottom + r.bottom)
+        };
+        if w.left < 0 || w.top < 0 || w.right >= self.size.x || w.bottom >= self.size.y || w.left > w.right || w.top > w.bottom {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "MemoryScreenBuffer::set_console_window_info(): window is empty or outside of the screen buffer"));
+        }
+        self.window = w;
+        Ok(())
+    }
+
+    #[doc(alias = "WriteConsole")]
+    #[doc(alias = "WriteConsoleW")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/writeconsole)\]
+    /// Writes a string to the screen buffer, beginning at and advancing the cursor.
+    ///
+    /// With [`ENABLE_PROCESSED_OUTPUT`], backspace, tab, bell, carriage return, and line feed are processed.
+    /// With [`ENABLE_WRAP_AT_EOL_OUTPUT`], the cursor wraps to the next row at the end of a row, scrolling the buffer contents up at the bottom.
+    /// Virtual terminal sequences are not interpreted.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let mut screen = MemoryScreenBuffer::new((80, 25));
+    /// let text : Vec<u16> = "Hello,\r\nworld!".encode_utf16().collect();
+    /// assert_eq!(screen.write_console(&text).unwrap(), text.len());
+    /// assert_eq!(screen.get_console_screen_buffer_info().unwrap().cursor_position, Coord::new(6, 1));
+    /// ```
+    ///
+    pub fn write_console(&mut self, buffer: &[u16]) -> io::Result<usize> {
+        let processed   = (self.mode & ENABLE_PROCESSED_OUTPUT  ) != OutputMode::default();
+        let wrap        = (self.mode & ENABLE_WRAP_AT_EOL_OUTPUT) != OutputMode::default();
+        if self.size.x <= 0 || self.size.y <= 0 { return Ok(buffer.len()) }
+
+        for &ch in buffer {
+            match ch {
+                0x07 if processed => {}, // bell
+                0x08 if processed => self.cursor_position.x = (self.cursor_position.x - 1).max(0),
+                0x09 if processed => {
+                    let next = ((self.cursor_position.x / 8) + 1) * 8;
+                    while self.cursor_position.x < next.min(self.size.x) {
+                        let x = self.cursor_position.x;
+                        self.put(b' '.into(), wrap);
+                        if self.cursor_position.x <= x { break } // wrapped to the next row, or stuck in the last column without wrap
+                    }
+                },
+                0x0A if processed => self.line_feed(),
+                0x0D if processed => self.cursor_position.x = 0,
+                _ => self.put(ch, wrap),
+            }
+        }
+        self.scroll_window_to_cursor();
+        Ok(buffer.len())
+    }
+
+    #[doc(alias = "WriteConsoleOutput")]
+    #[doc(alias = "WriteConsoleOutputW")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/writeconsoleoutput)\]
+    /// Copies a rectangular region of characters and [Attributes] into the screen buffer.
+    ///
+    /// `write_region` is inclusive.  It's clipped to both the screen buffer and the space available in `buffer` after `buffer_coord`,
+    /// and updated to the region actually written.  If nothing remains after clipping, this fails like conhost does.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let mut screen = MemoryScreenBuffer::new((80, 25));
+    /// let output = [CharInfo::new(u16::from(b'!'), Attributes::FOREGROUND_GREEN); 4];
+    /// let mut region = InclusiveRect::new(79, 24, 80, 25);
+    /// screen.write_console_output(&output, (2,2), (0,0), &mut region).unwrap();
+    /// assert_eq!(region, InclusiveRect::new(79, 24, 79, 24));
+    /// assert_eq!(screen.cell((79, 24)), Some(output[0]));
+    /// ```
+    ///
+    pub fn write_console_output(&mut self, buffer: &[CharInfo], buffer_size: impl Into<Coord>, buffer_coord: impl Into<Coord>, write_region: &mut InclusiveRect) -> io::Result<()> {
+        let (buffer_size, buffer_coord) = (buffer_size.into(), buffer_coord.into());
+        check_buffer("MemoryScreenBuffer::write_console_output", buffer.len(), buffer_size, buffer_coord)?;
+        let (screen, source) = self.clip_rect(buffer_size, buffer_coord, write_region, "MemoryScreenBuffer::write_console_output")?;
+        for dy in 0 .. screen.bottom - screen.top + 1 {
+            let w = (screen.right - screen.left + 1) as usize;
+            let dst = self.index(Coord::new(screen.left, screen.top + dy)).unwrap();
+            let src = (source.y + dy) as usize * buffer_size.x as usize + source.x as usize;
+            self.cells[dst .. dst + w].copy_from_slice(&buffer[src .. src + w]);
+        }
+        Ok(())
+    }
+
+    #[doc(alias = "WriteConsoleOutput")]
+    #[doc(alias = "WriteConsoleOutputW")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/writeconsoleoutput)\]
+    /// [`write_console_output`](Self::write_console_output) from `grid`, starting at `grid_coord`.
+    /// See [`CharInfoGrid`] for an example.
+    pub fn write_console_output_grid(&mut self, grid: &CharInfoGrid, grid_coord: impl Into<Coord>, write_region: &mut InclusiveRect) -> io::Result<()> {
+        self.write_console_output(grid.cells(), grid.size(), grid_coord, write_region)
+    }
+
+    #[doc(alias = "WriteConsoleOutputAttribute")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/writeconsoleoutputattribute)\]
+    /// Copies a line of [Attributes] into the screen buffer, starting at `write_coord`.
+    ///
+    /// Wraps from row to row, stops at the end of the buffer, and returns the number of cells modified.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let mut screen = MemoryScreenBuffer::new((10, 2));
+    /// assert_eq!(screen.write_console_output_attribute(&[FOREGROUND_GREEN; 4], (8,1)).unwrap(), 2);
+    /// ```
+    ///
+    pub fn write_console_output_attribute(&mut self, attributes: &[Attributes], write_coord: impl Into<Coord>) -> io::Result<usize> {
+        let cells = self.linear_mut(write_coord.into());
+        let n = cells.len().min(attributes.len());
+        for (cell, a) in cells.iter_mut().zip(attributes) { cell.attributes = *a; }
+        Ok(n)
+    }
+
+    #[doc(alias = "WriteConsoleOutputCharacter")]
+    #[doc(alias = "WriteConsoleOutputCharacterW")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/writeconsoleoutputcharacter)\]
+    /// Copies a string of wchar_t into the screen buffer, starting at `write_coord`.
+    ///
+    /// Wraps from row to row, stops at the end of the buffer, and returns the number of cells modified.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let mut screen = MemoryScreenBuffer::new((10, 2));
+    /// assert_eq!(screen.write_console_output_character(&[b'!' as u16; 4], (8,1)).unwrap(), 2);
+    /// ```
+    ///
+    pub fn write_console_output_character(&mut self, characters: &[u16], write_coord: impl Into<Coord>) -> io::Result<usize> {
+        let cells = self.linear_mut(write_coord.into());
+        let n = cells.len().min(characters.len());
+        for (cell, c) in cells.iter_mut().zip(characters) { cell.char = (*c).into(); }
+        Ok(n)
+    }
+
+    /// Writes `ch` at the cursor, then advances the cursor.
+    fn put(&mut self, ch: u16, wrap: bool) {
+        let attributes = self.attributes;
+        if let Some(i) = self.index(self.cursor_position) { self.cells[i] = CharInfo::new(ch, attributes); }
+        if self.cursor_position.x + 1 < self.size.x {
+            self.cursor_position.x += 1;
+        } else if wrap {
+            self.cursor_position.x = 0;
+            self.line_feed();
+        }
+    }
+
+    /// Moves the cursor to the start of the next row, scrolling the buffer contents up if the cursor was already on the last row.
+    fn line_feed(&mut self) {
+        self.cursor_position.x = 0;
+        if self.cursor_position.y + 1 < self.size.y {
+            self.cursor_position.y += 1;
+        } else {
+            let w = self.size.x as usize;
+            self.cells.copy_within(w.., 0);
+            let n = self.cells.len();
+            let blank = CharInfo::new(b' '.into(), self.attributes);
+            for cell in &mut self.cells[n - w ..] { *cell = blank; }
+        }
+    }
+
+    /// Moves the window the minimum distance necessary to contain the cursor.
+    fn scroll_window_to_cursor(&mut self) {
+        let c = self.cursor_position;
+        let w = &mut self.window;
+        let dx = if c.x < w.left { c.x - w.left } else if c.x > w.right  { c.x - w.right  } else { 0 };
+        let dy = if c.y < w.top  { c.y - w.top  } else if c.y > w.bottom { c.y - w.bottom } else { 0 };
+        *w = InclusiveRect::new(w.left + dx, w.top + dy, w.right + dx, w.bottom + dy);
+    }
+
+    /// Clips the inclusive `region` to both the screen buffer and the `buffer_size - buffer_coord` space available in the caller's buffer.
+    /// Updates `region` to the clipped region, and returns it alongside the matching top left corner within the caller's buffer.
+    fn clip_rect(&self, buffer_size: Coord, buffer_coord: Coord, region: &mut InclusiveRect, fn_name: &str) -> io::Result<(InclusiveRect, Coord)> {
+        let requested = *region;
+        *region = InclusiveRect::new(requested.left, requested.top, requested.left.wrapping_sub(1), requested.top.wrapping_sub(1));
+
+        // Clip to the caller's buffer
+        let w = (i32::from(requested.right) - i32::from(requested.left) + 1).min(i32::from(buffer_size.x) - i32::from(buffer_coord.x));
+        let h = (i32::from(requested.bottom) - i32::from(requested.top) + 1).min(i32::from(buffer_size.y) - i32::from(buffer_coord.y));
+        let mut screen = [
+            i32::from(requested.left),
+            i32::from(requested.top),
+            i32::from(requested.left) + w - 1,
+            i32::from(requested.top) + h - 1,
+        ];
+        let mut source = [i32::from(buffer_coord.x), i32::from(buffer_coord.y)];
+
+        // Clip to the screen buffer
+        if screen[0] < 0 { source[0] -= screen[0]; screen[0] = 0; }
+        if screen[1] < 0 { source[1] -= screen[1]; screen[1] = 0; }
+        screen[2] = screen[2].min(i32::from(self.size.x) - 1);
+        screen[3] = screen[3].min(i32::from(self.size.y) - 1);
+
+        if screen[0] > screen[2] || screen[1] > screen[3] {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("{fn_name}(): region does not overlap the screen buffer")));
+        }
+
+        let [left, top, right, bottom] = screen.map(|v| v as i16);
+        *region = InclusiveRect::new(left, top, right, bottom);
+        Ok((*region, Coord::new(source[0] as i16, source[1] as i16)))
+    }
+}
+
+fn check_buffer(fn_name: &str, buffer_len: usize, buffer_size: Coord, buffer_coord: Coord) -> io::Result<()> {
+    if buffer_coord.x > buffer_size.x { return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("{fn_name}(): buffer_coord.X > buffer_size.X"))); }
+    if buffer_coord.y > buffer_size.y { return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("{fn_name}(): buffer_coord.Y > buffer_size.Y"))); }
+    let buffer_w = usize::try_from(buffer_size.x).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, format!("{fn_name}(): buffer_size.X is out of SHORT bounds")))?;
+    let buffer_h = usize::try_from(buffer_size.y).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, format!("{fn_name}(): buffer_size.Y is out of SHORT bounds")))?;
+    if buffer_coord.x < 0 || buffer_coord.y < 0 { return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("{fn_name}(): buffer_coord is negative"))); }
+    if buffer_w * buffer_h > buffer_len { return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("{fn_name}(): buffer_size is larger than buffer"))); }
+    Ok(())
+}
+
+#[test] fn write_read_round_trip() {
+    let mut screen = MemoryScreenBuffer::new((4, 3));
+    let a = CharInfo::new(u16::from(b'a'), FOREGROUND_RED);
+    let b = CharInfo::new(u16::from(b'b'), FOREGROUND_BLUE);
+
+    // 3x3 source written at (-1,-1): only the bottom right 2x2 lands on screen
+    let source = [a, a, a, a, b, b, a, b, b];
+    let mut region = InclusiveRect::new(-1, -1, 1, 1);
+    screen.write_console_output(&source, (3,3), (0,0), &mut region).unwrap();
+    assert_eq!(region, InclusiveRect::new(0, 0, 1, 1));
+
+    let mut dest = [CharInfo::default(); 4 * 3];
+    let mut region = InclusiveRect::new(0, 0, 3, 2);
+    screen.read_console_output(&mut dest, (4,3), (0,0), &mut region).unwrap();
+    assert_eq!(region, InclusiveRect::new(0, 0, 3, 2));
+    assert_eq!(&dest[0..2], &[b, b]);
+    assert_eq!(&dest[4..6], &[b, b]);
+    assert_eq!(dest[2].char.unicode_char(), u16::from(b' '));
+    assert_eq!(dest[8].char.unicode_char(), u16::from(b' '));
+
+    // Entirely off screen
+    let mut region = InclusiveRect::new(4, 0, 5, 1);
+    assert!(screen.write_console_output(&source, (3,3), (0,0), &mut region).is_err());
+    assert_eq!(region, InclusiveRect::new(4, 0, 3, -1));
+}
+
+#[test] fn extreme_regions() {
+    let mut screen = MemoryScreenBuffer::new((4, 3));
+    let mut cells = [CharInfo::default(); 4];
+    assert!(screen.read_console_output(&mut cells, (2, 2), (0, 0), &mut InclusiveRect::new(i16::MIN, 0, i16::MIN + 1, 1)).is_err());
+    assert!(screen.write_console_output(&cells, (2, 2), (0, 0), &mut InclusiveRect::new(0, i16::MIN, 1, i16::MIN + 1)).is_err());
+    assert!(screen.read_console_output(&mut cells, (2, 2), (i16::MIN, i16::MIN), &mut InclusiveRect::new(0, 0, 1, 1)).is_err());
+    assert!(screen.write_console_output(&cells, (i16::MIN, 2), (i16::MAX, 0), &mut InclusiveRect::new(i16::MIN, 0, i16::MAX, 1)).is_err());
+}
+
+#[test] fn cursor_scrolls_window() {
+    let mut screen = MemoryScreenBuffer::new((10, 10));
+    screen.window = InclusiveRect::new(0, 0, 4, 4);
+    screen.set_console_cursor_position((7, 2)).unwrap();
+    assert_eq!(screen.window, InclusiveRect::new(3, 0, 7, 4));
+    screen.set_console_cursor_position((0, 9)).unwrap();
+    assert_eq!(screen.window, InclusiveRect::new(0, 5, 4, 9));
+}
+
+#[test] fn write_console_scrolls() {
+    let mut screen = MemoryScreenBuffer::new((4, 2));
+    let text : Vec<u16> = "abcdefghij".encode_utf16().collect();
+    screen.write_console(&text).unwrap();
+    let mut row = [0u16; 8];
+    assert_eq!(screen.read_console_output_character(&mut row, (0,0)).unwrap(), "efghij  ".encode_utf16().collect::<Vec<_>>());
+    assert_eq!(screen.cursor_position, Coord::new(2, 1));
+
+    screen.set_console_mode(ENABLE_PROCESSED_OUTPUT).unwrap(); // no wrap: overwrite the last column
+    screen.write_console(&"xyz".encode_utf16().collect::<Vec<_>>()).unwrap();
+    assert_eq!(screen.read_console_output_character(&mut row, (0,1)).unwrap(), "ijxz".encode_utf16().collect::<Vec<_>>());
+}
+
+#[test] fn write_console_tab_at_last_column() {
+    let mut screen = MemoryScreenBuffer::new((10, 2));
+    screen.set_console_mode(ENABLE_PROCESSED_OUTPUT).unwrap(); // no wrap
+    screen.write_console(&"abcdefghi\t".encode_utf16().collect::<Vec<_>>()).unwrap();
+    assert_eq!(screen.cursor_position, Coord::new(9, 0));
+    let mut row = [0u16; 10];
+    assert_eq!(screen.read_console_output_character(&mut row, (0,0)).unwrap(), "abcdefghi ".encode_utf16().collect::<Vec<_>>());
+
+    screen.set_console_mode(ENABLE_PROCESSED_OUTPUT | ENABLE_WRAP_AT_EOL_OUTPUT).unwrap();
+    screen.set_console_cursor_position((0, 0)).unwrap();
+    screen.write_console(&"abcdefghi\tj".encode_utf16().collect::<Vec<_>>()).unwrap();
+    assert_eq!(screen.cursor_position, Coord::new(1, 1));
+}
@@ -1,9 +1,8 @@
-use crate::*;
+#[cfg(windows)] use crate::*;
 
-use winapi::um::consoleapi::*;
-use winapi::um::wincon;
+#[cfg(windows)] use winapi::um::consoleapi::*;
 
-use std::io;
+#[cfg(windows)] use std::io;
 
 use core::ops::*;
 
@@ -28,6 +27,7 @@ use core::ops::*;
 /// * [InputMode]
 /// * [OutputMode]
 ///
+#[cfg(windows)]
 pub fn get_console_mode<CH: AsConsoleHandle>(handle: &CH) -> io::Result<CH::Mode> {
     let mut mode = 0;
     succeeded_to_result(unsafe { GetConsoleMode(handle.as_raw_handle().cast(), &mut mode) })?;
@@ -55,6 +55,7 @@ pub fn get_console_mode<CH: AsConsoleHandle>(handle: &CH) -> io::Result<CH::Mode
 /// * [InputMode]
 /// * [OutputMode]
 ///
+#[cfg(windows)]
 pub fn set_console_mode<CH: AsConsoleHandle>(handle: &mut CH, mode: CH::Mode) -> io::Result<()> {
     succeeded_to_result(unsafe { SetConsoleMode(handle.as_raw_handle().cast(), mode.into()) })
 }
@@ -79,6 +80,7 @@ pub fn set_console_mode<CH: AsConsoleHandle>(handle: &mut CH, mode: CH::Mode) ->
 /// * [InputMode]
 /// * [OutputMode]
 ///
+#[cfg(windows)]
 pub fn change_console_mode<CH: AsConsoleHandle>(handle: &mut CH, map: impl FnOnce(CH::Mode) -> CH::Mode) -> io::Result<()> {
     let mode = get_console_mode(handle)?;
     let mode = map(mode);
@@ -127,12 +129,12 @@ impl BitOrAssign  for OutputMode { fn bitor_assign (&mut self, rhs: Self) { self
 /// [ReadFile]:     https://learn.microsoft.com/en-us/windows/win32/api/fileapi/nf-fileapi-readfile
 /// [ReadConsole]:  https://learn.microsoft.com/en-us/windows/console/readconsole
 #[doc(hidden)]
-pub const ENABLE_ECHO_INPUT : InputMode = InputMode(wincon::ENABLE_ECHO_INPUT);
+pub const ENABLE_ECHO_INPUT : InputMode = InputMode(0x0004);
 
 /// When enabled, text entered in a console window will be inserted at the current cursor location and all
 /// text following that location will not be overwritten. When disabled, all following text will be overwritten.
 #[doc(hidden)]
-pub const ENABLE_INSERT_MODE : InputMode = InputMode(wincon::ENABLE_INSERT_MODE);
+pub const ENABLE_INSERT_MODE : InputMode = InputMode(0x0020);
 
 /// The [ReadFile] or [ReadConsole] function returns only when a carriage return character is read. If this
 /// mode is disabled, the functions return when one or more characters are available.
@@ -140,7 +142,7 @@ pub const ENABLE_INSERT_MODE : InputMode = InputMode(wincon::ENABLE_INSERT_MODE)
 /// [ReadFile]:     https://learn.microsoft.com/en-us/windows/win32/api/fileapi/nf-fileapi-readfile
 /// [ReadConsole]:  https://learn.microsoft.com/en-us/windows/console/readconsole
 #[doc(hidden)]
-pub const ENABLE_LINE_INPUT : InputMode = InputMode(wincon::ENABLE_LINE_INPUT);
+pub const ENABLE_LINE_INPUT : InputMode = InputMode(0x0002);
 
 /// If the mouse pointer is within the borders of the console window and the window has the keyboard
 /// focus, mouse events generated by mouse movement and button presses are placed in the input buffer.
@@ -149,7 +151,7 @@ pub const ENABLE_LINE_INPUT : InputMode = InputMode(wincon::ENABLE_LINE_INPUT);
 /// [ReadFile]:     https://learn.microsoft.com/en-us/windows/win32/api/fileapi/nf-fileapi-readfile
 /// [ReadConsole]:  https://learn.microsoft.com/en-us/windows/console/readconsole
 #[doc(hidden)]
-pub const ENABLE_MOUSE_INPUT : InputMode = InputMode(wincon::ENABLE_MOUSE_INPUT);
+pub const ENABLE_MOUSE_INPUT : InputMode = InputMode(0x0010);
 
 /// CTRL+C is processed by the system and is not placed in the input buffer. If the input buffer is being
 /// read by [ReadFile] or [ReadConsole], other control keys are processed by the system and are not returned
@@ -159,17 +161,17 @@ pub const ENABLE_MOUSE_INPUT : InputMode = InputMode(wincon::ENABLE_MOUSE_INPUT)
 /// [ReadFile]:     https://learn.microsoft.com/en-us/windows/win32/api/fileapi/nf-fileapi-readfile
 /// [ReadConsole]:  https://learn.microsoft.com/en-us/windows/console/readconsole
 #[doc(hidden)]
-pub const ENABLE_PROCESSED_INPUT : InputMode = InputMode(wincon::ENABLE_PROCESSED_INPUT);
+pub const ENABLE_PROCESSED_INPUT : InputMode = InputMode(0x0001);
 
 /// This flag enables the user to use the mouse to select and edit text. To enable this mode, use
 /// <code>[ENABLE_QUICK_EDIT_MODE] | [ENABLE_EXTENDED_FLAGS]</code>. To disable this mode, use
 /// [`ENABLE_EXTENDED_FLAGS`] without this flag.
 #[doc(hidden)]
-pub const ENABLE_QUICK_EDIT_MODE : InputMode = InputMode(wincon::ENABLE_QUICK_EDIT_MODE);
+pub const ENABLE_QUICK_EDIT_MODE : InputMode = InputMode(0x0040);
 
 /// ???
 #[doc(hidden)]
-pub const ENABLE_EXTENDED_FLAGS : InputMode = InputMode(wincon::ENABLE_EXTENDED_FLAGS);
+pub const ENABLE_EXTENDED_FLAGS : InputMode = InputMode(0x0080);
 
 /// User interactions that change the size of the console screen buffer are reported in the console's input
 /// buffer. Information about these events can be read from the input buffer by applications using the
@@ -179,7 +181,7 @@ pub const ENABLE_EXTENDED_FLAGS : InputMode = InputMode(wincon::ENABLE_EXTENDED_
 /// [ReadFile]:         https://learn.microsoft.com/en-us/windows/win32/api/fileapi/nf-fileapi-readfile
 /// [ReadConsole]:      https://learn.microsoft.com/en-us/windows/console/readconsole
 #[doc(hidden)]
-pub const ENABLE_WINDOW_INPUT : InputMode = InputMode(wincon::ENABLE_WINDOW_INPUT);
+pub const ENABLE_WINDOW_INPUT : InputMode = InputMode(0x0008);
 
 /// Setting this flag directs the Virtual Terminal processing engine to convert user input received by the
 /// console window into [Console Virtual Terminal Sequences] that can be retrieved by a supporting
@@ -193,7 +195,20 @@ pub const ENABLE_WINDOW_INPUT : InputMode = InputMode(wincon::ENABLE_WINDOW_INPU
 /// [WriteFile]:                            https://learn.microsoft.com/en-us/windows/win32/api/fileapi/nf-fileapi-writefile
 /// [WriteConsole]:                         https://learn.microsoft.com/en-us/windows/console/writeconsole
 #[doc(hidden)]
-pub const ENABLE_VIRTUAL_TERMINAL_INPUT : InputMode = InputMode(wincon::ENABLE_VIRTUAL_TERMINAL_INPUT);
+pub const ENABLE_VIRTUAL_TERMINAL_INPUT : InputMode = InputMode(0x0200);
+
+#[cfg(windows)] const _ : () = {
+    use winapi::um::wincon;
+    assert!(ENABLE_ECHO_INPUT.0                   == wincon::ENABLE_ECHO_INPUT                  );
+    assert!(ENABLE_INSERT_MODE.0                  == wincon::ENABLE_INSERT_MODE                 );
+    assert!(ENABLE_LINE_INPUT.0                   == wincon::ENABLE_LINE_INPUT                  );
+    assert!(ENABLE_MOUSE_INPUT.0                  == wincon::ENABLE_MOUSE_INPUT                 );
+    assert!(ENABLE_PROCESSED_INPUT.0              == wincon::ENABLE_PROCESSED_INPUT             );
+    assert!(ENABLE_QUICK_EDIT_MODE.0              == wincon::ENABLE_QUICK_EDIT_MODE             );
+    assert!(ENABLE_EXTENDED_FLAGS.0               == wincon::ENABLE_EXTENDED_FLAGS              );
+    assert!(ENABLE_WINDOW_INPUT.0                 == wincon::ENABLE_WINDOW_INPUT                );
+    assert!(ENABLE_VIRTUAL_TERMINAL_INPUT.0       == wincon::ENABLE_VIRTUAL_TERMINAL_INPUT      );
+};
 
 impl InputMode {
     /// Characters read by the [ReadFile] or [ReadConsole] function are written to the active screen buffer as they
@@ -274,7 +289,7 @@ impl InputMode {
 /// [ReadFile]:     https://learn.microsoft.com/en-us/windows/win32/api/fileapi/nf-fileapi-readfile
 /// [ReadConsole]:  https://learn.microsoft.com/en-us/windows/console/readconsole
 #[doc(hidden)]
-pub const ENABLE_PROCESSED_OUTPUT : OutputMode = OutputMode(wincon::ENABLE_PROCESSED_OUTPUT);
+pub const ENABLE_PROCESSED_OUTPUT : OutputMode = OutputMode(0x0001);
 
 /// When writing with [WriteFile] or [WriteConsole] or echoing with [ReadFile] or [ReadConsole], the
 /// cursor moves to the beginning of the next row when it reaches the end of the current row. This
@@ -289,7 +304,7 @@ pub const ENABLE_PROCESSED_OUTPUT : OutputMode = OutputMode(wincon::ENABLE_PROCE
 /// [ReadFile]:     https://learn.microsoft.com/en-us/windows/win32/api/fileapi/nf-fileapi-readfile
 /// [ReadConsole]:  https://learn.microsoft.com/en-us/windows/console/readconsole
 #[doc(hidden)]
-pub const ENABLE_WRAP_AT_EOL_OUTPUT : OutputMode = OutputMode(wincon::ENABLE_WRAP_AT_EOL_OUTPUT);
+pub const ENABLE_WRAP_AT_EOL_OUTPUT : OutputMode = OutputMode(0x0002);
 
 /// When writing with [WriteFile] or [WriteConsole], characters are parsed for VT100 and similar
 /// control character sequences that control cursor movement, color/font mode, and other
@@ -300,7 +315,7 @@ pub const ENABLE_WRAP_AT_EOL_OUTPUT : OutputMode = OutputMode(wincon::ENABLE_WRA
 /// [WriteConsole]:                         https://learn.microsoft.com/en-us/windows/console/writeconsole
 /// [Console Virtual Terminal Sequences]:   https://learn.microsoft.com/en-us/windows/console/console-virtual-terminal-sequences
 #[doc(hidden)]
-pub const ENABLE_VIRTUAL_TERMINAL_PROCESSING : OutputMode = OutputMode(wincon::ENABLE_VIRTUAL_TERMINAL_PROCESSING);
+pub const ENABLE_VIRTUAL_TERMINAL_PROCESSING : OutputMode = OutputMode(0x0004);
 
 /// When writing with [WriteFile] or [WriteConsole], this adds an additional state to end-of-line
 /// wrapping that can delay the cursor move and buffer scroll operations.
@@ -322,7 +337,7 @@ pub const ENABLE_VIRTUAL_TERMINAL_PROCESSING : OutputMode = OutputMode(wincon::E
 /// [WriteFile]:    https://learn.microsoft.com/en-us/windows/win32/api/fileapi/nf-fileapi-writefile
 /// [WriteConsole]: https://learn.microsoft.com/en-us/windows/console/writeconsole
 #[doc(hidden)]
-pub const DISABLE_NEWLINE_AUTO_RETURN : OutputMode = OutputMode(wincon::DISABLE_NEWLINE_AUTO_RETURN);
+pub const DISABLE_NEWLINE_AUTO_RETURN : OutputMode = OutputMode(0x0008);
 
 /// The APIs for writing character attributes including [WriteConsoleOutput] and
 /// [WriteConsoleOutputAttribute] allow the usage of flags from [character attributes] to adjust the
@@ -350,7 +365,16 @@ pub const DISABLE_NEWLINE_AUTO_RETURN : OutputMode = OutputMode(wincon::DISABLE_
 /// [character attributes]:                 https://learn.microsoft.com/en-us/windows/console/console-screen-buffers#character-attributes
 /// [Console Virtual Terminal Sequences]:   https://learn.microsoft.com/en-us/windows/console/console-virtual-terminal-sequences
 #[doc(hidden)]
-pub const ENABLE_LVB_GRID_WORLDWIDE : OutputMode = OutputMode(wincon::ENABLE_LVB_GRID_WORLDWIDE);
+pub const ENABLE_LVB_GRID_WORLDWIDE : OutputMode = OutputMode(0x0010);
+
+#[cfg(windows)] const _ : () = {
+    use winapi::um::wincon;
+    assert!(ENABLE_PROCESSED_OUTPUT.0             == wincon::ENABLE_PROCESSED_OUTPUT            );
+    assert!(ENABLE_WRAP_AT_EOL_OUTPUT.0           == wincon::ENABLE_WRAP_AT_EOL_OUTPUT          );
+    assert!(ENABLE_VIRTUAL_TERMINAL_PROCESSING.0  == wincon::ENABLE_VIRTUAL_TERMINAL_PROCESSING );
+    assert!(DISABLE_NEWLINE_AUTO_RETURN.0         == wincon::DISABLE_NEWLINE_AUTO_RETURN        );
+    assert!(ENABLE_LVB_GRID_WORLDWIDE.0           == wincon::ENABLE_LVB_GRID_WORLDWIDE          );
+};
 
 impl OutputMode {
     /// Characters written by the [WriteFile] or [WriteConsole] function or echoed by the [ReadFile] or
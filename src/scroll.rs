@@ -0,0 +1,114 @@
+use crate::*;
+
+use std::io;
+
+
+
+#[doc(alias = "ScrollConsoleScreenBuffer")]
+#[doc(alias = "ScrollConsoleScreenBufferW")]
+/// \[~~microsoft.com~~\]
+/// Applies [`ScrollConsoleScreenBuffer`](https://learn.microsoft.com/en-us/windows/console/scrollconsolescreenbuffer)'s semantics to a row-major `grid` of `grid_size` cells.
+///
+/// All rectangles are inclusive, as they are for Win32:
+/// *   `scroll_rect` is clipped to the grid.  If nothing remains, this fails without modifying `grid`.
+///     Clipping the top/left edge of `scroll_rect` offsets `destination` by the same amount, so cells keep their relative positions.
+/// *   Cells of `scroll_rect` are moved (as if through a temporary copy) so that its top left corner lands on `destination`.
+/// *   Cells of `scroll_rect` that aren't overwritten by the moved cells are set to `fill`.
+/// *   Only cells inside `clip_rect` (the entire grid if [`None`]) are ever modified.
+///
+/// ### Example
+/// ```
+/// # use maulingmonkey_console_winapi_wrappers::*;
+/// let cell = |ch: u8| CharInfo::new(ch.into(), Attributes::default());
+/// let mut grid : Vec<CharInfo> = b"abcdefghi".iter().map(|&ch| cell(ch)).collect(); // 3x3
+///
+/// // Scroll everything up a row
+/// scroll_char_info_grid(&mut grid, (3, 3), InclusiveRect::new(0, 0, 2, 2), None, Coord::new(0, -1), cell(b'.')).unwrap();
+/// let text : String = grid.iter().map(|c| c.char.unicode_char() as u8 as char).collect();
+/// assert_eq!(text, "defghi...");
+/// ```
+///
+pub fn scroll_char_info_grid(grid: &mut [CharInfo], grid_size: impl Into<Coord>, scroll_rect: InclusiveRect, clip_rect: Option<InclusiveRect>, destination: Coord, fill: CharInfo) -> io::Result<()> {
+    let grid_size = grid_size.into();
+    let (w, h) = (i32::from(grid_size.x), i32::from(grid_size.y));
+    if w < 0 || h < 0 { return Err(io::Error::new(io::ErrorKind::InvalidInput, "scroll_char_info_grid(): negative `grid_size`")); }
+    if grid.len() < (w * h) as usize { return Err(io::Error::new(io::ErrorKind::InvalidInput, "scroll_char_info_grid(): `grid_size` is larger than `grid`")); }
+
+    let Some(source) = Bounds::from(scroll_rect).intersect(Bounds { left: 0, top: 0, right: w - 1, bottom: h - 1 }) else {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "scroll_char_info_grid(): `scroll_rect` doesn't overlap the grid"));
+    };
+    let clip = Bounds::from(clip_rect.unwrap_or(InclusiveRect::new(0, 0, grid_size.x - 1, grid_size.y - 1)));
+    let Some(clip) = clip.intersect(Bounds { left: 0, top: 0, right: w - 1, bottom: h - 1 }) else { return Ok(()) };
+
+    let dx = i32::from(destination.x) - i32::from(scroll_rect.left);
+    let dy = i32::from(destination.y) - i32::from(scroll_rect.top );
+    let index = |x: i32, y: i32| (y * w + x) as usize;
+
+    let mut moved = Vec::with_capacity(((source.right - source.left + 1) * (source.bottom - source.top + 1)) as usize);
+    for y in source.top ..= source.bottom {
+        moved.extend_from_slice(&grid[index(source.left, y) ..= index(source.right, y)]);
+    }
+
+    if let Some(fill_area) = source.intersect(clip) {
+        for y in fill_area.top ..= fill_area.bottom {
+            grid[index(fill_area.left, y) ..= index(fill_area.right, y)].fill(fill);
+        }
+    }
+
+    let mut moved = moved.into_iter();
+    for y in source.top ..= source.bottom {
+        for x in source.left ..= source.right {
+            let cell = moved.next().unwrap();
+            let (x, y) = (x + dx, y + dy);
+            if clip.contains(x, y) { grid[index(x, y)] = cell; }
+        }
+    }
+
+    Ok(())
+}
+
+
+
+/// An inclusive rectangle, widened to avoid overflow.
+#[derive(Clone, Copy)] struct Bounds { left: i32, top: i32, right: i32, bottom: i32 }
+
+impl From<InclusiveRect> for Bounds {
+    fn from(r: InclusiveRect) -> Self { Self { left: r.left.into(), top: r.top.into(), right: r.right.into(), bottom: r.bottom.into() } }
+}
+
+impl Bounds {
+    fn intersect(self, other: Self) -> Option<Self> {
+        let r = Self { left: self.left.max(other.left), top: self.top.max(other.top), right: self.right.min(other.right), bottom: self.bottom.min(other.bottom) };
+        (r.left <= r.right && r.top <= r.bottom).then_some(r)
+    }
+
+    fn contains(self, x: i32, y: i32) -> bool {
+        (self.left ..= self.right).contains(&x) && (self.top ..= self.bottom).contains(&y)
+    }
+}
+
+
+
+#[test] fn clipped_scroll() {
+    let initial : Vec<CharInfo> = b"abcdefghijklmnop".iter().map(|&ch| cell(ch)).collect(); // 4x4
+
+    // Scroll right by one, clipped to the middle two columns
+    let mut grid = initial.clone();
+    scroll_char_info_grid(&mut grid, (4, 4), InclusiveRect::new(0, 0, 3, 3), Some(InclusiveRect::new(1, 0, 2, 3)), Coord::new(1, 0), cell(b'.')).unwrap();
+    assert_eq!(grid_text(&grid), "aabdeefhiijlmmnp");
+
+    // Scroll rect partially outside the grid: top left clipping offsets the destination too
+    let mut grid = initial.clone();
+    scroll_char_info_grid(&mut grid, (4, 4), InclusiveRect::new(-1, -1, 1, 1), None, Coord::new(1, 1), cell(b'.')).unwrap();
+    assert_eq!(grid_text(&grid), "..cd..ghijabmnef");
+
+    // Destination partially outside the grid
+    let mut grid = initial.clone();
+    scroll_char_info_grid(&mut grid, (4, 4), InclusiveRect::new(0, 0, 3, 3), None, Coord::new(-2, 3), cell(b'.')).unwrap();
+    assert_eq!(grid_text(&grid), "............cd..");
+
+    // Entirely outside the grid
+    let mut grid = initial.clone();
+    assert!(scroll_char_info_grid(&mut grid, (4, 4), InclusiveRect::new(4, 0, 5, 3), None, Coord::new(0, 0), cell(b'.')).is_err());
+    assert_eq!(grid_text(&grid), grid_text(&initial));
+}
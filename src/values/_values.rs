@@ -8,7 +8,29 @@ mod console_font_info;                  pub use console_font_info::*;
 mod console_font_info_ex;               pub use console_font_info_ex::*;
 mod console_screen_buffer_info;         pub use console_screen_buffer_info::*;
 mod console_screen_buffer_info_ex;      pub use console_screen_buffer_info_ex::*;
+mod control_key_state;                  pub use control_key_state::*;
 mod coord;                              pub use coord::*;
+mod exclusive_rect;                     pub use exclusive_rect::*;
+mod inclusive_rect;                     pub use inclusive_rect::*;
 mod input_record;                       pub use input_record::*;
 mod input_record_event_type;            pub use input_record_event_type::*;
-mod small_rect;                         pub use small_rect::*;
+mod mouse_buttons;                      pub use mouse_buttons::*;
+mod mouse_event_flags;                  pub use mouse_event_flags::*;
+mod virtual_key;                        pub use virtual_key::*;
+
+
+
+/// Formats `bits` as `NAME_A | NAME_B | 0x00000100` using `names` (in order), or as `0` if no bits are set.
+fn fmt_flags(fmt: &mut core::fmt::Formatter, bits: u32, names: impl Iterator<Item = (&'static str, u32)>) -> core::fmt::Result {
+    if bits == 0 { return write!(fmt, "0") }
+    let mut remaining = bits;
+    let mut separator = "";
+    for (name, flag) in names {
+        if remaining & flag == 0 { continue }
+        remaining &= !flag;
+        write!(fmt, "{separator}{name}")?;
+        separator = " | ";
+    }
+    if remaining != 0 { write!(fmt, "{separator}0x{remaining:08x}")?; }
+    Ok(())
+}
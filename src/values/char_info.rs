@@ -2,7 +2,7 @@ use crate::*;
 
 use bytemuck::{Pod, Zeroable};
 
-use winapi::um::wincontypes::CHAR_INFO;
+#[cfg(windows)] use winapi::um::wincontypes::CHAR_INFO;
 
 
 
@@ -25,10 +25,10 @@ impl CharInfo {
     }
 }
 
-impl From<CharInfo> for CHAR_INFO { fn from(value: CharInfo ) -> Self { unsafe { core::mem::transmute(value) } } }
-impl From<CHAR_INFO> for CharInfo { fn from(value: CHAR_INFO) -> Self { unsafe { core::mem::transmute(value) } } }
+#[cfg(windows)] impl From<CharInfo> for CHAR_INFO { fn from(value: CharInfo ) -> Self { unsafe { core::mem::transmute(value) } } }
+#[cfg(windows)] impl From<CHAR_INFO> for CharInfo { fn from(value: CHAR_INFO) -> Self { unsafe { core::mem::transmute(value) } } }
 
-const _ : () = {
+#[cfg(windows)] const _ : () = {
     use core::mem::offset_of;
     assert!(align_of::<CHAR_INFO>() == align_of::<CharInfo>());
     assert!(size_of ::<CHAR_INFO>() == size_of ::<CharInfo>());
@@ -36,7 +36,7 @@ const _ : () = {
 };
 
 #[allow(deprecated)]
-#[test] fn layout() {
+#[cfg(windows)] #[test] fn layout() {
     let a = CharInfo::new(0x1234, Zeroable::zeroed());
     let b : CHAR_INFO = a.into();
     assert_eq!(a.char.ascii_char(),     unsafe { *b.Char.AsciiChar() } as u8);
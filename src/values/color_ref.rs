@@ -1,6 +1,15 @@
 #[doc(alias = "COLORREF")]
 /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/gdi/colorref)\]
-/// 0x00RRGGBB color.
+/// 0x00BBGGRR color.
+///
+/// ### Example
+/// ```
+/// # use maulingmonkey_console_winapi_wrappers::*;
+/// let orange = ColorRef::from_rgb([0xFF, 0x80, 0x00]);
+/// assert_eq!(u32::from(orange), 0x00_00_80_FF);
+/// assert_eq!(orange.red(), 0xFF);
+/// assert_eq!(orange.to_rgb(), [0xFF, 0x80, 0x00]);
+/// ```
 ///
 #[derive(Clone, Copy, bytemuck::Pod, Default, bytemuck::Zeroable, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)] pub struct ColorRef(u32);
@@ -16,9 +25,9 @@ impl ColorRef {
     ///
     pub const fn from_rgb([red, green, blue]: [u8; 3]) -> Self {
         Self(
-            ((red   as u32) << 16) |
+            ((red   as u32) <<  0) |
             ((green as u32) <<  8) |
-            ((blue  as u32) <<  0)
+            ((blue  as u32) << 16)
         )
     }
 
@@ -33,7 +42,7 @@ impl ColorRef {
     /// GetRValue
     /// &mdash; get the red component (0 ..= 255) of the color.
     ///
-    pub const fn red(self) -> u8 { (self.0 >> 16) as u8 }
+    pub const fn red(self) -> u8 { (self.0 >>  0) as u8 }
 
     #[doc(alias = "GetGValue")]
     /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/api/wingdi/nf-wingdi-getgvalue)\]
@@ -47,5 +56,5 @@ impl ColorRef {
     /// GetBValue
     /// &mdash; get the blue component (0 ..= 255) of the color.
     ///
-    pub const fn blue(self) -> u8 { (self.0 >>  0) as u8 }
+    pub const fn blue(self) -> u8 { (self.0 >> 16) as u8 }
 }
@@ -1,4 +1,4 @@
-use winapi::um::wincon::CONSOLE_CURSOR_INFO;
+#[cfg(windows)] use winapi::um::wincon::CONSOLE_CURSOR_INFO;
 
 
 
@@ -21,10 +21,10 @@ impl ConsoleCursorInfo {
     }
 }
 
-impl From<ConsoleCursorInfo> for CONSOLE_CURSOR_INFO { fn from(value: ConsoleCursorInfo  ) -> Self { unsafe { core::mem::transmute(value) } } }
-impl From<CONSOLE_CURSOR_INFO> for ConsoleCursorInfo { fn from(value: CONSOLE_CURSOR_INFO) -> Self { unsafe { core::mem::transmute(value) } } }
+#[cfg(windows)] impl From<ConsoleCursorInfo> for CONSOLE_CURSOR_INFO { fn from(value: ConsoleCursorInfo  ) -> Self { unsafe { core::mem::transmute(value) } } }
+#[cfg(windows)] impl From<CONSOLE_CURSOR_INFO> for ConsoleCursorInfo { fn from(value: CONSOLE_CURSOR_INFO) -> Self { unsafe { core::mem::transmute(value) } } }
 
-const _ : () = {
+#[cfg(windows)] const _ : () = {
     use core::mem::offset_of;
     assert!(align_of::<CONSOLE_CURSOR_INFO>() == align_of::<ConsoleCursorInfo>());
     assert!(size_of ::<CONSOLE_CURSOR_INFO>() == size_of ::<ConsoleCursorInfo>());
@@ -32,7 +32,7 @@ const _ : () = {
     assert!(offset_of!(ConsoleCursorInfo, visible) == offset_of!(CONSOLE_CURSOR_INFO, bVisible  ));
 };
 
-#[test] fn layout() {
+#[cfg(windows)] #[test] fn layout() {
     let a = ConsoleCursorInfo::new(100, true);
     let b : CONSOLE_CURSOR_INFO = a.into();
     assert_eq!(a.size,      b.dwSize        );
@@ -1,5 +1,5 @@
 use crate::Coord;
-use winapi::um::wincon::CONSOLE_FONT_INFO;
+#[cfg(windows)] use winapi::um::wincon::CONSOLE_FONT_INFO;
 
 
 
@@ -12,10 +12,10 @@ use winapi::um::wincon::CONSOLE_FONT_INFO;
     pub font_size:  Coord,
 }
 
-impl From<CONSOLE_FONT_INFO> for ConsoleFontInfo { fn from(value: CONSOLE_FONT_INFO ) -> Self { unsafe { core::mem::transmute(value) } } }
-impl From<ConsoleFontInfo> for CONSOLE_FONT_INFO { fn from(value: ConsoleFontInfo   ) -> Self { unsafe { core::mem::transmute(value) } } }
+#[cfg(windows)] impl From<CONSOLE_FONT_INFO> for ConsoleFontInfo { fn from(value: CONSOLE_FONT_INFO ) -> Self { unsafe { core::mem::transmute(value) } } }
+#[cfg(windows)] impl From<ConsoleFontInfo> for CONSOLE_FONT_INFO { fn from(value: ConsoleFontInfo   ) -> Self { unsafe { core::mem::transmute(value) } } }
 
-const _ : () = {
+#[cfg(windows)] const _ : () = {
     use core::mem::offset_of;
     assert!(align_of::<CONSOLE_FONT_INFO>() == align_of::<ConsoleFontInfo>());
     assert!(size_of ::<CONSOLE_FONT_INFO>() == size_of ::<ConsoleFontInfo>());
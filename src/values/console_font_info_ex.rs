@@ -1,6 +1,7 @@
 use crate::{size_of_32, Coord};
-use winapi::um::wincon::CONSOLE_FONT_INFOEX;
-use winapi::um::wingdi::LF_FACESIZE;
+#[cfg(windows)] use winapi::um::wincon::CONSOLE_FONT_INFOEX;
+
+const LF_FACESIZE : usize = 32; // winapi::um::wingdi::LF_FACESIZE
 
 
 
@@ -34,6 +35,7 @@ impl Default for ConsoleFontInfoEx {
     }
 }
 
+#[cfg(windows)]
 impl From<CONSOLE_FONT_INFOEX> for ConsoleFontInfoEx {
     fn from(value: CONSOLE_FONT_INFOEX) -> Self {
         #[allow(deprecated)] Self {
@@ -48,6 +50,7 @@ impl From<CONSOLE_FONT_INFOEX> for ConsoleFontInfoEx {
     }
 }
 
+#[cfg(windows)]
 impl From<ConsoleFontInfoEx> for CONSOLE_FONT_INFOEX {
     fn from(value: ConsoleFontInfoEx) -> Self {
         Self {
@@ -62,9 +65,11 @@ impl From<ConsoleFontInfoEx> for CONSOLE_FONT_INFOEX {
     }
 }
 
-const _ : () = {
+#[cfg(windows)] const _ : () = {
     use core::mem::offset_of;
 
+    assert!(LF_FACESIZE == winapi::um::wingdi::LF_FACESIZE);
+
     assert!(size_of ::<CONSOLE_FONT_INFOEX>() >= size_of ::<ConsoleFontInfoEx>());
     if      size_of ::<CONSOLE_FONT_INFOEX>() == size_of ::<ConsoleFontInfoEx>() {
         assert!(align_of::<CONSOLE_FONT_INFOEX>() == align_of::<ConsoleFontInfoEx>());
@@ -78,6 +83,6 @@ const _ : () = {
     assert!(offset_of!(ConsoleFontInfoEx, face_name           ) == offset_of!(CONSOLE_FONT_INFOEX, FaceName            ));
 };
 
-#[test] fn layout() {
+#[cfg(windows)] #[test] fn layout() {
     assert_eq!(size_of::<CONSOLE_FONT_INFOEX>(), size_of::<ConsoleFontInfoEx>(), "`CONSOLE_FONT_INFOEX` has gained new fields, update `ConsoleFontInfoEx`");
 }
@@ -1,5 +1,5 @@
-use crate::{Attributes, Coord, SmallRect};
-use winapi::um::wincon::CONSOLE_SCREEN_BUFFER_INFO;
+use crate::{Attributes, Coord, InclusiveRect};
+#[cfg(windows)] use winapi::um::wincon::CONSOLE_SCREEN_BUFFER_INFO;
 
 
 
@@ -11,14 +11,14 @@ use winapi::um::wincon::CONSOLE_SCREEN_BUFFER_INFO;
     pub size:                   Coord,
     pub cursor_position:        Coord,
     pub attributes:             Attributes,
-    pub window:                 SmallRect,
+    pub window:                 InclusiveRect,
     pub maximum_window_size:    Coord,
 }
 
-impl From<CONSOLE_SCREEN_BUFFER_INFO> for ConsoleScreenBufferInfo { fn from(value: CONSOLE_SCREEN_BUFFER_INFO   ) -> Self { unsafe { core::mem::transmute(value) } } }
-impl From<ConsoleScreenBufferInfo> for CONSOLE_SCREEN_BUFFER_INFO { fn from(value: ConsoleScreenBufferInfo      ) -> Self { unsafe { core::mem::transmute(value) } } }
+#[cfg(windows)] impl From<CONSOLE_SCREEN_BUFFER_INFO> for ConsoleScreenBufferInfo { fn from(value: CONSOLE_SCREEN_BUFFER_INFO   ) -> Self { unsafe { core::mem::transmute(value) } } }
+#[cfg(windows)] impl From<ConsoleScreenBufferInfo> for CONSOLE_SCREEN_BUFFER_INFO { fn from(value: ConsoleScreenBufferInfo      ) -> Self { unsafe { core::mem::transmute(value) } } }
 
-const _ : () = {
+#[cfg(windows)] const _ : () = {
     use core::mem::offset_of;
     assert!(align_of::<CONSOLE_SCREEN_BUFFER_INFO>() == align_of::<ConsoleScreenBufferInfo>());
     assert!(size_of ::<CONSOLE_SCREEN_BUFFER_INFO>() == size_of ::<ConsoleScreenBufferInfo>());
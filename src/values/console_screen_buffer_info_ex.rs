@@ -1,5 +1,5 @@
-use crate::{size_of_32, Attributes, ColorRef, Coord, SmallRect};
-use winapi::um::wincon::CONSOLE_SCREEN_BUFFER_INFOEX;
+use crate::{size_of_32, Attributes, ColorRef, Coord, InclusiveRect};
+#[cfg(windows)] use winapi::um::wincon::CONSOLE_SCREEN_BUFFER_INFOEX;
 
 
 
@@ -12,7 +12,7 @@ use winapi::um::wincon::CONSOLE_SCREEN_BUFFER_INFOEX;
     pub size:                   Coord,
     pub cursor_position:        Coord,
     pub attributes:             Attributes,
-    pub window:                 SmallRect,
+    pub window:                 InclusiveRect,
     pub maximum_window_size:    Coord,
     pub popup_attributes:       Attributes, // ???
     pub fullscreen_supported:   abibool::bool32,
@@ -32,6 +32,7 @@ impl Default for ConsoleScreenBufferInfoEx {
     }
 }
 
+#[cfg(windows)]
 impl From<CONSOLE_SCREEN_BUFFER_INFOEX> for ConsoleScreenBufferInfoEx {
     fn from(value: CONSOLE_SCREEN_BUFFER_INFOEX) -> Self {
         #[allow(deprecated)] Self {
@@ -49,6 +50,7 @@ impl From<CONSOLE_SCREEN_BUFFER_INFOEX> for ConsoleScreenBufferInfoEx {
     }
 }
 
+#[cfg(windows)]
 impl From<ConsoleScreenBufferInfoEx> for CONSOLE_SCREEN_BUFFER_INFOEX {
     fn from(value: ConsoleScreenBufferInfoEx) -> Self {
         Self {
@@ -66,7 +68,7 @@ impl From<ConsoleScreenBufferInfoEx> for CONSOLE_SCREEN_BUFFER_INFOEX {
     }
 }
 
-const _ : () = {
+#[cfg(windows)] const _ : () = {
     use core::mem::offset_of;
 
     assert!(size_of ::<CONSOLE_SCREEN_BUFFER_INFOEX>() >= size_of ::<ConsoleScreenBufferInfoEx>());
@@ -85,6 +87,6 @@ const _ : () = {
     assert!(offset_of!(ConsoleScreenBufferInfoEx, color_table           ) == offset_of!(CONSOLE_SCREEN_BUFFER_INFOEX, ColorTable            ));
 };
 
-#[test] fn layout() {
+#[cfg(windows)] #[test] fn layout() {
     assert_eq!(size_of::<CONSOLE_SCREEN_BUFFER_INFOEX>(), size_of::<ConsoleScreenBufferInfoEx>(), "`CONSOLE_SCREEN_BUFFER_INFOEX` has gained new fields, update `ConsoleScreenBufferInfoEx`");
 }
@@ -0,0 +1,196 @@
+#[allow(unused_imports)] use crate::*;
+
+use core::fmt::{self, Debug, Formatter};
+use core::ops::*;
+
+
+
+#[doc(alias = "dwControlKeyState")]
+/// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/key-event-record-str)\]
+/// [KeyEventRecord]::[control_key_state](KeyEventRecord::control_key_state) / [MouseEventRecord]::[control_key_state](MouseEventRecord::control_key_state):
+/// the state of the modifier and lock keys
+///
+/// ### Example
+/// ```
+/// # use maulingmonkey_console_winapi_wrappers::*;
+/// let state = LEFT_CTRL_PRESSED | ENHANCED_KEY;
+/// assert!(state.ctrl() && !state.alt() && !state.shift());
+/// assert_eq!(format!("{state:?}"), "LEFT_CTRL_PRESSED | ENHANCED_KEY");
+///
+/// let altgr = RIGHT_ALT_PRESSED | LEFT_CTRL_PRESSED; // how Windows reports AltGr
+/// assert!(altgr.is_altgr() && altgr.ctrl() && altgr.alt());
+/// assert_eq!(u32::from(altgr), 0x0009);
+/// ```
+///
+#[repr(transparent)] #[derive(Clone, Copy, bytemuck::Pod, Default, bytemuck::Zeroable, PartialEq, Eq, PartialOrd, Ord, Hash)] pub struct ControlKeyState(u32);
+
+impl From<u32> for ControlKeyState { fn from(value: u32) -> Self { Self(value) } }
+impl From<ControlKeyState> for u32 { fn from(value: ControlKeyState) -> Self { value.0 } }
+
+impl BitAnd for ControlKeyState { type Output = Self; fn bitand(self, rhs: Self) -> Self::Output { Self(self.0 & rhs.0) } }
+impl BitXor for ControlKeyState { type Output = Self; fn bitxor(self, rhs: Self) -> Self::Output { Self(self.0 ^ rhs.0) } }
+impl BitOr  for ControlKeyState { type Output = Self; fn bitor (self, rhs: Self) -> Self::Output { Self(self.0 | rhs.0) } }
+impl Not    for ControlKeyState { type Output = Self; fn not   (self)            -> Self::Output { Self(!self.0) } }
+
+impl BitAndAssign for ControlKeyState { fn bitand_assign(&mut self, rhs: Self) { self.0 &= rhs.0; } }
+impl BitXorAssign for ControlKeyState { fn bitxor_assign(&mut self, rhs: Self) { self.0 ^= rhs.0; } }
+impl BitOrAssign  for ControlKeyState { fn bitor_assign (&mut self, rhs: Self) { self.0 |= rhs.0; } }
+
+
+
+/// The right <kbd>Alt</kbd> key is pressed.
+#[doc(hidden)] pub const RIGHT_ALT_PRESSED     : ControlKeyState = ControlKeyState(0x0001);
+
+/// The left <kbd>Alt</kbd> key is pressed.
+#[doc(hidden)] pub const LEFT_ALT_PRESSED      : ControlKeyState = ControlKeyState(0x0002);
+
+/// The right <kbd>Ctrl</kbd> key is pressed.
+#[doc(hidden)] pub const RIGHT_CTRL_PRESSED    : ControlKeyState = ControlKeyState(0x0004);
+
+/// The left <kbd>Ctrl</kbd> key is pressed.
+#[doc(hidden)] pub const LEFT_CTRL_PRESSED     : ControlKeyState = ControlKeyState(0x0008);
+
+/// The <kbd>Shift</kbd> key is pressed.
+#[doc(hidden)] pub const SHIFT_PRESSED         : ControlKeyState = ControlKeyState(0x0010);
+
+/// The <kbd>Num Lock</kbd> light is on.
+#[doc(hidden)] pub const NUMLOCK_ON            : ControlKeyState = ControlKeyState(0x0020);
+
+/// The <kbd>Scroll Lock</kbd> light is on.
+#[doc(hidden)] pub const SCROLLLOCK_ON         : ControlKeyState = ControlKeyState(0x0040);
+
+/// The <kbd>Caps Lock</kbd> light is on.
+#[doc(hidden)] pub const CAPSLOCK_ON           : ControlKeyState = ControlKeyState(0x0080);
+
+/// The key is enhanced: the cursor and editing keys outside the numeric keypad, <kbd>Numpad /</kbd>, <kbd>Numpad Enter</kbd>, right <kbd>Alt</kbd> and <kbd>Ctrl</kbd>, ...
+#[doc(hidden)] pub const ENHANCED_KEY          : ControlKeyState = ControlKeyState(0x0100);
+
+/// The character is part of a double-byte character set.
+#[doc(hidden)] pub const NLS_DBCSCHAR          : ControlKeyState = ControlKeyState(0x0001_0000);
+
+/// The IME is in Katakana mode.
+#[doc(hidden)] pub const NLS_KATAKANA          : ControlKeyState = ControlKeyState(0x0002_0000);
+
+/// The IME is in Hiragana mode.
+#[doc(hidden)] pub const NLS_HIRAGANA          : ControlKeyState = ControlKeyState(0x0004_0000);
+
+/// The IME is in Roman mode.
+#[doc(hidden)] pub const NLS_ROMAN             : ControlKeyState = ControlKeyState(0x0040_0000);
+
+/// The IME is in conversion mode.
+#[doc(hidden)] pub const NLS_IME_CONVERSION    : ControlKeyState = ControlKeyState(0x0080_0000);
+
+/// The IME is disabled.
+#[doc(hidden)] pub const NLS_IME_DISABLE       : ControlKeyState = ControlKeyState(0x2000_0000);
+
+#[cfg(windows)] const _ : () = {
+    use winapi::um::wincon;
+    assert!(RIGHT_ALT_PRESSED.0     == wincon::RIGHT_ALT_PRESSED    );
+    assert!(LEFT_ALT_PRESSED.0      == wincon::LEFT_ALT_PRESSED     );
+    assert!(RIGHT_CTRL_PRESSED.0    == wincon::RIGHT_CTRL_PRESSED   );
+    assert!(LEFT_CTRL_PRESSED.0     == wincon::LEFT_CTRL_PRESSED    );
+    assert!(SHIFT_PRESSED.0         == wincon::SHIFT_PRESSED        );
+    assert!(NUMLOCK_ON.0            == wincon::NUMLOCK_ON           );
+    assert!(SCROLLLOCK_ON.0         == wincon::SCROLLLOCK_ON        );
+    assert!(CAPSLOCK_ON.0           == wincon::CAPSLOCK_ON          );
+    assert!(ENHANCED_KEY.0          == wincon::ENHANCED_KEY         );
+    assert!(NLS_DBCSCHAR.0          == wincon::NLS_DBCSCHAR         );
+    assert!(NLS_KATAKANA.0          == wincon::NLS_KATAKANA         );
+    assert!(NLS_HIRAGANA.0          == wincon::NLS_HIRAGANA         );
+    assert!(NLS_ROMAN.0             == wincon::NLS_ROMAN            );
+    assert!(NLS_IME_CONVERSION.0    == wincon::NLS_IME_CONVERSION   );
+    assert!(NLS_IME_DISABLE.0       == wincon::NLS_IME_DISABLE      );
+};
+
+const NAMES : &[(&str, ControlKeyState)] = &[
+    ("RIGHT_ALT_PRESSED",   RIGHT_ALT_PRESSED   ),
+    ("LEFT_ALT_PRESSED",    LEFT_ALT_PRESSED    ),
+    ("RIGHT_CTRL_PRESSED",  RIGHT_CTRL_PRESSED  ),
+    ("LEFT_CTRL_PRESSED",   LEFT_CTRL_PRESSED   ),
+    ("SHIFT_PRESSED",       SHIFT_PRESSED       ),
+    ("NUMLOCK_ON",          NUMLOCK_ON          ),
+    ("SCROLLLOCK_ON",       SCROLLLOCK_ON       ),
+    ("CAPSLOCK_ON",         CAPSLOCK_ON         ),
+    ("ENHANCED_KEY",        ENHANCED_KEY        ),
+    ("NLS_DBCSCHAR",        NLS_DBCSCHAR        ),
+    ("NLS_KATAKANA",        NLS_KATAKANA        ),
+    ("NLS_HIRAGANA",        NLS_HIRAGANA        ),
+    ("NLS_ROMAN",           NLS_ROMAN           ),
+    ("NLS_IME_CONVERSION",  NLS_IME_CONVERSION  ),
+    ("NLS_IME_DISABLE",     NLS_IME_DISABLE     ),
+];
+
+impl ControlKeyState {
+    /// The right <kbd>Alt</kbd> key is pressed.
+    pub const RIGHT_ALT_PRESSED     : ControlKeyState = RIGHT_ALT_PRESSED;
+
+    /// The left <kbd>Alt</kbd> key is pressed.
+    pub const LEFT_ALT_PRESSED      : ControlKeyState = LEFT_ALT_PRESSED;
+
+    /// The right <kbd>Ctrl</kbd> key is pressed.
+    pub const RIGHT_CTRL_PRESSED    : ControlKeyState = RIGHT_CTRL_PRESSED;
+
+    /// The left <kbd>Ctrl</kbd> key is pressed.
+    pub const LEFT_CTRL_PRESSED     : ControlKeyState = LEFT_CTRL_PRESSED;
+
+    /// The <kbd>Shift</kbd> key is pressed.
+    pub const SHIFT_PRESSED         : ControlKeyState = SHIFT_PRESSED;
+
+    /// The <kbd>Num Lock</kbd> light is on.
+    pub const NUMLOCK_ON            : ControlKeyState = NUMLOCK_ON;
+
+    /// The <kbd>Scroll Lock</kbd> light is on.
+    pub const SCROLLLOCK_ON         : ControlKeyState = SCROLLLOCK_ON;
+
+    /// The <kbd>Caps Lock</kbd> light is on.
+    pub const CAPSLOCK_ON           : ControlKeyState = CAPSLOCK_ON;
+
+    /// The key is enhanced: the cursor and editing keys outside the numeric keypad, <kbd>Numpad /</kbd>, <kbd>Numpad Enter</kbd>, right <kbd>Alt</kbd> and <kbd>Ctrl</kbd>, ...
+    pub const ENHANCED_KEY          : ControlKeyState = ENHANCED_KEY;
+
+    /// The character is part of a double-byte character set.
+    pub const NLS_DBCSCHAR          : ControlKeyState = NLS_DBCSCHAR;
+
+    /// The IME is in Katakana mode.
+    pub const NLS_KATAKANA          : ControlKeyState = NLS_KATAKANA;
+
+    /// The IME is in Hiragana mode.
+    pub const NLS_HIRAGANA          : ControlKeyState = NLS_HIRAGANA;
+
+    /// The IME is in Roman mode.
+    pub const NLS_ROMAN             : ControlKeyState = NLS_ROMAN;
+
+    /// The IME is in conversion mode.
+    pub const NLS_IME_CONVERSION    : ControlKeyState = NLS_IME_CONVERSION;
+
+    /// The IME is disabled.
+    pub const NLS_IME_DISABLE       : ControlKeyState = NLS_IME_DISABLE;
+
+    /// Returns `true` if all bits of `flags` are set in `self`.
+    pub const fn contains(self, flags: ControlKeyState) -> bool { self.0 & flags.0 == flags.0 }
+
+    /// Returns `true` if either <kbd>Ctrl</kbd> key is pressed.
+    pub const fn ctrl(self) -> bool { self.0 & (LEFT_CTRL_PRESSED.0 | RIGHT_CTRL_PRESSED.0) != 0 }
+
+    /// Returns `true` if either <kbd>Alt</kbd> key is pressed.
+    pub const fn alt(self) -> bool { self.0 & (LEFT_ALT_PRESSED.0 | RIGHT_ALT_PRESSED.0) != 0 }
+
+    /// Returns `true` if <kbd>Shift</kbd> is pressed.
+    pub const fn shift(self) -> bool { self.0 & SHIFT_PRESSED.0 != 0 }
+
+    #[doc(alias = "AltGr")]
+    /// Returns `true` for <code>[RIGHT_ALT_PRESSED] | [LEFT_CTRL_PRESSED]</code>, which is how Windows reports <kbd>AltGr</kbd> on layouts that have it.
+    ///
+    /// Physically holding right <kbd>Alt</kbd> and left <kbd>Ctrl</kbd> is indistinguishable from <kbd>AltGr</kbd>.
+    ///
+    /// [RIGHT_ALT_PRESSED]: Self::RIGHT_ALT_PRESSED
+    /// [LEFT_CTRL_PRESSED]: Self::LEFT_CTRL_PRESSED
+    pub const fn is_altgr(self) -> bool { self.contains(ControlKeyState(RIGHT_ALT_PRESSED.0 | LEFT_CTRL_PRESSED.0)) }
+}
+
+/// Lists the set flags (e.g. `LEFT_CTRL_PRESSED | ENHANCED_KEY`), any unnamed bits in hex, or `0` if none are set.
+impl Debug for ControlKeyState {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        super::fmt_flags(fmt, self.0, NAMES.iter().map(|&(name, flag)| (name, flag.0)))
+    }
+}
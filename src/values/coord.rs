@@ -1,20 +1,35 @@
-use winapi::um::wincon::COORD;
+#[cfg(windows)] use winapi::um::wincon::COORD;
+use core::cmp::Ordering;
+use core::num::TryFromIntError;
+use core::ops::*;
 
 
 
 #[doc(alias = "COORD")]
 /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/coord-str)\]
 /// struct { x: [i16], y: [i16] }
-#[derive(Clone, Copy, bytemuck::Pod, Default, bytemuck::Zeroable, Debug, PartialEq, Eq)] // PartialOrd, Ord, Hash?
+///
+/// Ordered row-major (by `y`, then by `x`), matching the order of cells in a screen buffer.
+///
+/// ### Example
+/// ```
+/// # use maulingmonkey_console_winapi_wrappers::*;
+/// assert!(Coord::new(79, 0) < Coord::new(0, 1));
+/// assert_eq!(Coord::new(1, 2) + Coord::new(3, 4), Coord::new(4, 6));
+/// assert_eq!(Coord::new(5, 1).to_index(80), Some(85));
+/// assert_eq!(Coord::from_index(85, 80), Some(Coord::new(5, 1)));
+/// ```
+///
+#[derive(Clone, Copy, bytemuck::Pod, Default, bytemuck::Zeroable, Debug, PartialEq, Eq, Hash)]
 #[repr(C)] pub struct Coord {
     pub x: i16,
     pub y: i16,
 }
 
-impl From<Coord> for COORD { fn from(value: Coord) -> Self { unsafe { core::mem::transmute(value) } } }
-impl From<COORD> for Coord { fn from(value: COORD) -> Self { unsafe { core::mem::transmute(value) } } }
+#[cfg(windows)] impl From<Coord> for COORD { fn from(value: Coord) -> Self { unsafe { core::mem::transmute(value) } } }
+#[cfg(windows)] impl From<COORD> for Coord { fn from(value: COORD) -> Self { unsafe { core::mem::transmute(value) } } }
 
-const _ : () = {
+#[cfg(windows)] const _ : () = {
     use core::mem::offset_of;
     assert!(align_of::<COORD>() == align_of::<Coord>());
     assert!(size_of ::<COORD>() == size_of ::<Coord>());
@@ -22,7 +37,7 @@ const _ : () = {
     assert!(offset_of!(Coord, y) == offset_of!(COORD, Y));
 };
 
-#[test] fn layout() {
+#[cfg(windows)] #[test] fn layout() {
     let a = Coord::new(1, 2);
     let b : COORD = a.into();
     assert_eq!(a.x, b.X);
@@ -33,7 +48,137 @@ impl Coord {
     pub const fn new(x: i16, y: i16) -> Self {
         Self { x, y }
     }
+
+    /// Adds `rhs` to `self` component-wise, returning [`None`] if either component overflows.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// assert_eq!(Coord::new(1, 2).checked_add((3, -4)), Some(Coord::new(4, -2)));
+    /// assert_eq!(Coord::new(1, i16::MAX).checked_add((0, 1)), None);
+    /// ```
+    ///
+    pub fn checked_add(self, rhs: impl Into<Coord>) -> Option<Self> {
+        let rhs = rhs.into();
+        Some(Self::new(self.x.checked_add(rhs.x)?, self.y.checked_add(rhs.y)?))
+    }
+
+    /// Subtracts `rhs` from `self` component-wise, returning [`None`] if either component overflows.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// assert_eq!(Coord::new(1, 2).checked_sub((3, -4)), Some(Coord::new(-2, 6)));
+    /// assert_eq!(Coord::new(i16::MIN, 0).checked_sub((1, 0)), None);
+    /// ```
+    ///
+    pub fn checked_sub(self, rhs: impl Into<Coord>) -> Option<Self> {
+        let rhs = rhs.into();
+        Some(Self::new(self.x.checked_sub(rhs.x)?, self.y.checked_sub(rhs.y)?))
+    }
+
+    /// Adds `rhs` to `self` component-wise, clamping each component to [`i16::MIN`] ..= [`i16::MAX`].
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// assert_eq!(Coord::new(1, i16::MAX).saturating_add((1, 1)), Coord::new(2, i16::MAX));
+    /// ```
+    ///
+    pub fn saturating_add(self, rhs: impl Into<Coord>) -> Self {
+        let rhs = rhs.into();
+        Self::new(self.x.saturating_add(rhs.x), self.y.saturating_add(rhs.y))
+    }
+
+    /// Subtracts `rhs` from `self` component-wise, clamping each component to [`i16::MIN`] ..= [`i16::MAX`].
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// assert_eq!(Coord::new(i16::MIN, 5).saturating_sub((1, 1)), Coord::new(i16::MIN, 4));
+    /// ```
+    ///
+    pub fn saturating_sub(self, rhs: impl Into<Coord>) -> Self {
+        let rhs = rhs.into();
+        Self::new(self.x.saturating_sub(rhs.x), self.y.saturating_sub(rhs.y))
+    }
+
+    /// Returns the offset of `self` within a flat, row-major buffer (e.g. of [`CharInfo`](crate::CharInfo)s) `width` cells wide: `y * width + x`.
+    ///
+    /// Returns [`None`] if `self` is negative, if `x >= width`, or if the offset overflows [`usize`].
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// assert_eq!(Coord::new( 0, 0).to_index(80), Some(0));
+    /// assert_eq!(Coord::new(79, 1).to_index(80), Some(159));
+    /// assert_eq!(Coord::new(80, 1).to_index(80), None);
+    /// assert_eq!(Coord::new(-1, 1).to_index(80), None);
+    /// ```
+    ///
+    pub fn to_index(self, width: usize) -> Option<usize> {
+        let x = usize::try_from(self.x).ok()?;
+        let y = usize::try_from(self.y).ok()?;
+        if x >= width { return None }
+        y.checked_mul(width)?.checked_add(x)
+    }
+
+    /// Returns the [`Coord`] of offset `index` within a flat, row-major buffer `width` cells wide.  The inverse of [`to_index`](Self::to_index).
+    ///
+    /// Returns [`None`] if `width` is `0`, or if either component wouldn't fit in an [`i16`].
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// assert_eq!(Coord::from_index(159, 80), Some(Coord::new(79, 1)));
+    /// assert_eq!(Coord::from_index(159,  0), None);
+    /// assert_eq!(Coord::from_index(usize::MAX, 80), None);
+    /// ```
+    ///
+    pub fn from_index(index: usize, width: usize) -> Option<Self> {
+        if width == 0 { return None }
+        Some(Self::new(i16::try_from(index % width).ok()?, i16::try_from(index / width).ok()?))
+    }
 }
 
+impl PartialOrd for Coord { fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) } }
+impl Ord        for Coord { fn cmp(&self, other: &Self) -> Ordering { (self.y, self.x).cmp(&(other.y, other.x)) } }
+
+impl Add        for Coord { type Output = Self; fn add(self, rhs: Self) -> Self { Self::new(self.x + rhs.x, self.y + rhs.y) } }
+impl Sub        for Coord { type Output = Self; fn sub(self, rhs: Self) -> Self { Self::new(self.x - rhs.x, self.y - rhs.y) } }
+impl Neg        for Coord { type Output = Self; fn neg(self) -> Self { Self::new(-self.x, -self.y) } }
+impl AddAssign  for Coord { fn add_assign(&mut self, rhs: Self) { *self = *self + rhs; } }
+impl SubAssign  for Coord { fn sub_assign(&mut self, rhs: Self) { *self = *self - rhs; } }
+
 impl From<(i16, i16)> for Coord { fn from(value: (i16, i16) ) -> Self { let (x, y) = value; Self::new(x, y) } }
 impl From<[i16; 2]  > for Coord { fn from(value: [i16; 2]   ) -> Self { let [x, y] = value; Self::new(x, y) } }
+
+/// Fails if either component doesn't fit in an [`i16`].
+impl TryFrom<(usize, usize)> for Coord {
+    type Error = TryFromIntError;
+    fn try_from(value: (usize, usize)) -> Result<Self, Self::Error> { let (x, y) = value; Ok(Self::new(x.try_into()?, y.try_into()?)) }
+}
+
+/// Fails if either component doesn't fit in an [`i16`].
+impl TryFrom<[usize; 2]> for Coord {
+    type Error = TryFromIntError;
+    fn try_from(value: [usize; 2]) -> Result<Self, Self::Error> { let [x, y] = value; Ok(Self::new(x.try_into()?, y.try_into()?)) }
+}
+
+
+
+#[test] fn index_round_trip() {
+    for width in [1, 7, 80] {
+        for index in 0 .. 1000 {
+            let coord = Coord::from_index(index, width).unwrap();
+            assert_eq!(coord.to_index(width), Some(index));
+        }
+    }
+    assert!(Coord::try_from((32767usize, 0usize)).is_ok());
+    assert!(Coord::try_from((32768usize, 0usize)).is_err());
+    assert!(Coord::try_from([0usize, usize::MAX]).is_err());
+
+    let mut sorted = [Coord::new(1, 1), Coord::new(0, 1), Coord::new(5, 0)];
+    sorted.sort();
+    assert_eq!(sorted, [Coord::new(5, 0), Coord::new(0, 1), Coord::new(1, 1)]);
+}
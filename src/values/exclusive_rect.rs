@@ -0,0 +1,304 @@
+use crate::{Coord, InclusiveRect};
+#[cfg(windows)] use winapi::um::wincon::COORD;
+use core::ops::Range;
+
+
+
+/// \[~~microsoft.com~~\]
+/// struct { left: [i16], top: [i16], right: [i16], bottom: [i16] } with exclusive `right` / `bottom` edges
+///
+/// Unlike Win32's inclusive [`SMALL_RECT`](InclusiveRect), `right - left` is the width, an empty rectangle is simply `left == right`,
+/// and [`Range`]s convert directly.  This is the type to do rectangle math with ([`intersect`](Self::intersect), [`split_at_row`](Self::split_at_row), ...)
+/// Converts to and from [`InclusiveRect`] without loss, except at the very edge of the [`i16`] range (see the [`From`] impls.)
+///
+/// ### Example
+/// ```
+/// # use maulingmonkey_console_winapi_wrappers::*;
+/// let window = ExclusiveRect::from((0, 0) .. (80, 25));
+/// assert_eq!(window.size(), Some((80, 25)));
+/// assert_eq!(InclusiveRect::from(window), InclusiveRect::new(0, 0, 79, 24));
+/// ```
+///
+#[derive(Clone, Copy, bytemuck::Pod, Default, bytemuck::Zeroable, Debug, PartialEq, Eq)] // PartialOrd, Ord, Hash?
+#[repr(C)] pub struct ExclusiveRect {
+    /// The left side of the rectangle, inclusive.
+    /// Generally, `left <= right`.
+    pub left:   i16,
+
+    /// The top side of the rectangle, inclusive.
+    /// Generally, `top <= bottom`.
+    pub top:    i16,
+
+    /// The right side of the rectangle, exclusive.
+    /// Generally, `left <= right`.
+    pub right:  i16,
+
+    /// The bottom side of the rectangle, exclusive.
+    /// Generally, `top <= bottom`.
+    pub bottom: i16,
+}
+
+/// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/small-rect-str)\]
+/// The old name of [`ExclusiveRect`], from before the split into [`ExclusiveRect`] and [`InclusiveRect`].
+///
+/// Its `right` / `bottom` edges were documented as exclusive.  APIs which pass regions to or from Win32 `SMALL_RECT`s now take [`InclusiveRect`]s.
+#[deprecated = "use ExclusiveRect or InclusiveRect"] pub type SmallRect = ExclusiveRect;
+
+/// Adds one to `right` and `bottom`, saturating.
+///
+/// An [`ExclusiveRect`] can't cover column or row [`i16::MAX`], so an inclusive edge of [`i16::MAX`] stays [`i16::MAX`], dropping that column or row
+/// (rather than wrapping to [`i16::MIN`], which would turn e.g. `0 ..= i16::MAX` into an empty rectangle.)
+impl From<InclusiveRect> for ExclusiveRect {
+    fn from(value: InclusiveRect) -> Self {
+        let InclusiveRect { left, top, right, bottom } = value;
+        Self { left, top, right: right.saturating_add(1), bottom: bottom.saturating_add(1) }
+    }
+}
+
+impl ExclusiveRect {
+    pub const fn new(left: i16, top: i16, right: i16, bottom: i16) -> Self {
+        Self { left, top, right, bottom }
+    }
+
+    /// Returns the positive width of `self`, or [`None`] if the width was negative.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// assert_eq!(Some(10), ExclusiveRect::new(-5, 0,  5, 0).width());
+    /// assert_eq!(None,     ExclusiveRect::new( 5, 0, -5, 0).width());
+    /// assert_eq!(Some(0),  ExclusiveRect::new( 0, 0,  0, 0).width());
+    /// ```
+    ///
+    pub const fn width(&self) -> Option<u16> {
+        let Self { left, right, .. } = *self;
+        if left > right { return None }
+        Some(right.wrapping_sub(left) as u16)
+    }
+
+    /// Returns the positive height of `self`, or [`None`] if the height was negative.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// assert_eq!(Some(10), ExclusiveRect::new(0, -5, 0,  5).height());
+    /// assert_eq!(None,     ExclusiveRect::new(0,  5, 0, -5).height());
+    /// assert_eq!(Some(0),  ExclusiveRect::new(0,  0, 0,  0).height());
+    /// ```
+    ///
+    pub const fn height(&self) -> Option<u16> {
+        let Self { top, bottom, .. } = *self;
+        if top > bottom { return None }
+        Some(bottom.wrapping_sub(top) as u16)
+    }
+
+    /// Returns the positive width and height of `self`, or [`None`] if either dimension was negative.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// assert_eq!(Some((10, 10)),  ExclusiveRect::new(-5, -5,  5,  5).size());
+    /// assert_eq!(None,            ExclusiveRect::new(-5,  5,  5, -5).size()); // negative height
+    /// assert_eq!(None,            ExclusiveRect::new( 5, -5, -5,  5).size()); // negative width
+    /// assert_eq!(None,            ExclusiveRect::new( 5,  5, -5, -5).size()); // negative both
+    /// assert_eq!(Some((0, 0)),    ExclusiveRect::new( 0,  0,  0,  0).size());
+    /// ```
+    ///
+    pub const fn size(&self) -> Option<(u16, u16)> {
+        let Some(w) = self.width()  else { return None };
+        let Some(h) = self.height() else { return None };
+        Some((w, h))
+    }
+
+    /// Returns the total area of `self`, or [`None`] if either the width or height was negative.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// assert_eq!(Some(100),   ExclusiveRect::new(-5, -5,  5,  5).area());
+    /// assert_eq!(None,        ExclusiveRect::new(-5,  5,  5, -5).area()); // negative height
+    /// assert_eq!(None,        ExclusiveRect::new( 5, -5, -5,  5).area()); // negative width
+    /// assert_eq!(None,        ExclusiveRect::new( 5,  5, -5, -5).area()); // negative both
+    /// assert_eq!(Some(0),     ExclusiveRect::new( 0,  0,  0,  0).area());
+    /// ```
+    ///
+    pub const fn area(&self) -> Option<u32> {
+        let Some((w, h)) = self.size() else { return None };
+        Some((w as u32).wrapping_mul(h as u32))
+    }
+
+    /// Returns `true` if `self` covers no cells (`right <= left` or `bottom <= top`.)
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// assert!(!ExclusiveRect::new(0, 0, 1, 1).is_empty());
+    /// assert!( ExclusiveRect::new(0, 0, 0, 1).is_empty());
+    /// assert!( ExclusiveRect::new(0, 0, 1, -1).is_empty());
+    /// ```
+    ///
+    pub const fn is_empty(&self) -> bool { self.left >= self.right || self.top >= self.bottom }
+
+    /// Returns the cells covered by both `self` and `other`, or [`None`] if they don't overlap.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let a = ExclusiveRect::new(0, 0, 10, 10);
+    /// assert_eq!(a.intersect(ExclusiveRect::new( 5, 5, 15, 15)), Some(ExclusiveRect::new(5, 5, 10, 10)));
+    /// assert_eq!(a.intersect(ExclusiveRect::new(10, 0, 20, 10)), None); // merely touching
+    /// ```
+    ///
+    pub fn intersect(&self, other: impl Into<ExclusiveRect>) -> Option<ExclusiveRect> {
+        let other = other.into();
+        let r = ExclusiveRect::new(self.left.max(other.left), self.top.max(other.top), self.right.min(other.right), self.bottom.min(other.bottom));
+        (!r.is_empty()).then_some(r)
+    }
+
+    /// Returns the smallest rectangle covering both `self` and `other`.  Empty rectangles are ignored.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let a = ExclusiveRect::new(0, 0, 2, 2);
+    /// assert_eq!(a.union(ExclusiveRect::new(5, 1, 8, 3)), ExclusiveRect::new(0, 0, 8, 3));
+    /// assert_eq!(a.union(ExclusiveRect::new(9, 9, 9, 9)), a); // empty
+    /// ```
+    ///
+    pub fn union(&self, other: impl Into<ExclusiveRect>) -> ExclusiveRect {
+        let other = other.into();
+        if other.is_empty() { return *self }
+        if self.is_empty() { return other }
+        ExclusiveRect::new(self.left.min(other.left), self.top.min(other.top), self.right.max(other.right), self.bottom.max(other.bottom))
+    }
+
+    /// Returns `true` if `coord` is one of the cells covered by `self`.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let r = ExclusiveRect::new(0, 0, 80, 25);
+    /// assert!( r.contains((0, 0)));
+    /// assert!( r.contains((79, 24)));
+    /// assert!(!r.contains((80, 24)));
+    /// assert!(!r.contains((-1, 0)));
+    /// ```
+    ///
+    pub fn contains(&self, coord: impl Into<Coord>) -> bool {
+        let Coord { x, y } = coord.into();
+        self.left <= x && x < self.right && self.top <= y && y < self.bottom
+    }
+
+    /// Returns `true` if every cell covered by `other` is also covered by `self`.  An empty `other` is contained by any rectangle.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let r = ExclusiveRect::new(0, 0, 80, 25);
+    /// assert!( r.contains_rect(ExclusiveRect::new(10, 10, 80, 25)));
+    /// assert!(!r.contains_rect(ExclusiveRect::new(10, 10, 81, 25)));
+    /// assert!( r.contains_rect(ExclusiveRect::new(90, 90, 90, 90))); // empty
+    /// ```
+    ///
+    pub fn contains_rect(&self, other: impl Into<ExclusiveRect>) -> bool {
+        let other = other.into();
+        other.is_empty() || (self.left <= other.left && self.top <= other.top && other.right <= self.right && other.bottom <= self.bottom)
+    }
+
+    /// Returns `self` moved by `offset`, or [`None`] if any edge would overflow an [`i16`].
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// assert_eq!(ExclusiveRect::new(0, 0, 10, 5).translate((3, -1)), Some(ExclusiveRect::new(3, -1, 13, 4)));
+    /// assert_eq!(ExclusiveRect::new(0, 0, i16::MAX, 5).translate((1, 0)), None);
+    /// ```
+    ///
+    pub fn translate(&self, offset: impl Into<Coord>) -> Option<ExclusiveRect> {
+        let Coord { x, y } = offset.into();
+        Some(ExclusiveRect::new(self.left.checked_add(x)?, self.top.checked_add(y)?, self.right.checked_add(x)?, self.bottom.checked_add(y)?))
+    }
+
+    /// Returns `self` with each edge clamped to a buffer of `buffer_size` cells (`0 ..= buffer_size.x`, `0 ..= buffer_size.y`.)
+    /// The result is empty if `self` lies entirely outside the buffer.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// assert_eq!(ExclusiveRect::new(-5, 20, 100, 30).clamp_to((80, 25)), ExclusiveRect::new(0, 20, 80, 25));
+    /// assert!(ExclusiveRect::new(90, 0, 100, 10).clamp_to((80, 25)).is_empty());
+    /// ```
+    ///
+    pub fn clamp_to(&self, buffer_size: impl Into<Coord>) -> ExclusiveRect {
+        let Coord { x: w, y: h } = buffer_size.into();
+        let (w, h) = (w.max(0), h.max(0));
+        ExclusiveRect::new(self.left.clamp(0, w), self.top.clamp(0, h), self.right.clamp(0, w), self.bottom.clamp(0, h))
+    }
+
+    /// Splits `self` into the rows above `row`, and the rows from `row` down.  `row` is clamped to `top ..= bottom`, so either half may be empty.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let (above, below) = ExclusiveRect::new(0, 0, 80, 25).split_at_row(24);
+    /// assert_eq!(above, ExclusiveRect::new(0,  0, 80, 24));
+    /// assert_eq!(below, ExclusiveRect::new(0, 24, 80, 25)); // status line
+    /// ```
+    ///
+    pub fn split_at_row(&self, row: i16) -> (ExclusiveRect, ExclusiveRect) {
+        let row = row.clamp(self.top, self.bottom.max(self.top));
+        (ExclusiveRect { bottom: row, .. *self }, ExclusiveRect { top: row, .. *self })
+    }
+
+    /// Splits `self` into the columns left of `col`, and the columns from `col` rightwards.  `col` is clamped to `left ..= right`, so either half may be empty.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let (sidebar, main) = ExclusiveRect::new(0, 0, 80, 25).split_at_col(20);
+    /// assert_eq!(sidebar, ExclusiveRect::new( 0, 0, 20, 25));
+    /// assert_eq!(main,    ExclusiveRect::new(20, 0, 80, 25));
+    /// ```
+    ///
+    pub fn split_at_col(&self, col: i16) -> (ExclusiveRect, ExclusiveRect) {
+        let col = col.clamp(self.left, self.right.max(self.left));
+        (ExclusiveRect { right: col, .. *self }, ExclusiveRect { left: col, .. *self })
+    }
+
+    /// Returns every [`Coord`] covered by `self`, in row-major order (left to right, then top to bottom.)
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let coords : Vec<Coord> = ExclusiveRect::new(1, 1, 3, 3).coords().collect();
+    /// assert_eq!(coords, [Coord::new(1, 1), Coord::new(2, 1), Coord::new(1, 2), Coord::new(2, 2)]);
+    /// assert_eq!(ExclusiveRect::new(0, 0, 0, 5).coords().count(), 0);
+    /// ```
+    ///
+    pub fn coords(&self) -> impl Iterator<Item = Coord> + Clone + use<> {
+        let ExclusiveRect { left, top, right, bottom } = *self;
+        (top .. bottom).flat_map(move |y| (left .. right).map(move |x| Coord::new(x, y)))
+    }
+}
+
+impl From<Range<Coord>              > for ExclusiveRect { fn from(value: Range<Coord>               ) -> Self { Self { left: value.start.x, top: value.start.y, right: value.end.x, bottom: value.end.y } } }
+#[cfg(windows)]
+impl From<Range<COORD>              > for ExclusiveRect { fn from(value: Range<COORD>               ) -> Self { Self { left: value.start.X, top: value.start.Y, right: value.end.X, bottom: value.end.Y } } }
+impl From<Range<(i16, i16)>         > for ExclusiveRect { fn from(value: Range<(i16, i16)>          ) -> Self { Self { left: value.start.0, top: value.start.1, right: value.end.0, bottom: value.end.1 } } }
+impl From<(Range<i16>, Range<i16>)  > for ExclusiveRect { fn from(value: (Range<i16>, Range<i16>)   ) -> Self { Self { left: value.0.start, top: value.1.start, right: value.0.end, bottom: value.1.end } } }
+impl From<Range<[i16; 2]>           > for ExclusiveRect { fn from(value: Range<[i16; 2]>            ) -> Self { let [left, top] = value.start; let [right, bottom] = value.end; Self { left, top, right, bottom } } }
+impl From<[Range<i16>; 2]           > for ExclusiveRect { fn from(value: [Range<i16>; 2]            ) -> Self { let [x, y] = value; Self { left: x.start, top: y.start, right: x.end, bottom: y.end } } }
@@ -0,0 +1,177 @@
+use crate::{Coord, ExclusiveRect};
+#[cfg(windows)] use winapi::um::wincon::{COORD, SMALL_RECT};
+use core::ops::{Range, RangeInclusive};
+
+
+
+#[doc(alias = "SMALL_RECT")]
+/// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/small-rect-str)\]
+/// struct { left: [i16], top: [i16], right: [i16], bottom: [i16] } with inclusive `right` / `bottom` edges, as Win32 uses [`SMALL_RECT`]
+///
+/// This is the type of [`ConsoleScreenBufferInfo::window`], and of the regions passed to
+/// [`read_console_output`](crate::MemoryScreenBuffer::read_console_output), [`write_console_output`](crate::MemoryScreenBuffer::write_console_output),
+/// [`scroll_console_screen_buffer`](crate::MemoryScreenBuffer::scroll_console_screen_buffer), and [`set_console_window_info`](crate::MemoryScreenBuffer::set_console_window_info).
+/// A single cell has `left == right`.  Convert to [`ExclusiveRect`] for rectangle math (without loss, unless an edge is [`i16::MAX`].)
+///
+/// [`Range`]s convert by treating their end as exclusive, [`RangeInclusive`]s convert directly:
+///
+/// ```
+/// # use maulingmonkey_console_winapi_wrappers::*;
+/// let a = InclusiveRect::from((0, 0) ..  (80, 25));
+/// let b = InclusiveRect::from((0, 0) ..= (79, 24));
+/// assert_eq!(a, InclusiveRect::new(0, 0, 79, 24));
+/// assert_eq!(a, b);
+/// assert_eq!(a.size(), Some((80, 25)));
+/// ```
+///
+/// [`SMALL_RECT`]: https://learn.microsoft.com/en-us/windows/console/small-rect-str
+#[derive(Clone, Copy, bytemuck::Pod, Default, bytemuck::Zeroable, Debug, PartialEq, Eq)] // PartialOrd, Ord, Hash?
+#[repr(C)] pub struct InclusiveRect {
+    /// The left side of the rectangle, inclusive.
+    /// Generally, `left <= right`.
+    pub left:   i16,
+
+    /// The top side of the rectangle, inclusive.
+    /// Generally, `top <= bottom`.
+    pub top:    i16,
+
+    /// The right side of the rectangle, inclusive.
+    /// Generally, `left <= right`.
+    pub right:  i16,
+
+    /// The bottom side of the rectangle, inclusive.
+    /// Generally, `top <= bottom`.
+    pub bottom: i16,
+}
+
+#[cfg(windows)] impl From<InclusiveRect> for SMALL_RECT { fn from(value: InclusiveRect) -> Self { unsafe { core::mem::transmute(value) } } }
+#[cfg(windows)] impl From<SMALL_RECT> for InclusiveRect { fn from(value: SMALL_RECT   ) -> Self { unsafe { core::mem::transmute(value) } } }
+
+#[cfg(windows)] const _ : () = {
+    use core::mem::offset_of;
+    assert!(align_of::<SMALL_RECT>() == align_of::<InclusiveRect>());
+    assert!(size_of ::<SMALL_RECT>() == size_of ::<InclusiveRect>());
+    assert!(offset_of!(InclusiveRect, left  ) == offset_of!(SMALL_RECT, Left  ));
+    assert!(offset_of!(InclusiveRect, top   ) == offset_of!(SMALL_RECT, Top   ));
+    assert!(offset_of!(InclusiveRect, right ) == offset_of!(SMALL_RECT, Right ));
+    assert!(offset_of!(InclusiveRect, bottom) == offset_of!(SMALL_RECT, Bottom));
+};
+
+#[cfg(windows)] #[test] fn layout() {
+    let a = InclusiveRect::new(1, 2, 3, 4);
+    let b : SMALL_RECT = a.into();
+    assert_eq!(a.left,   b.Left  );
+    assert_eq!(a.top,    b.Top   );
+    assert_eq!(a.right,  b.Right );
+    assert_eq!(a.bottom, b.Bottom);
+}
+
+/// Subtracts one from `right` and `bottom`.
+///
+/// An exclusive edge of [`i16::MIN`] can't be decremented, but covers no cells anyway:
+/// it stays [`i16::MIN`], and `left` / `top` are raised past it if needed, so empty rectangles stay empty (rather than wrapping to cover everything.)
+impl From<ExclusiveRect> for InclusiveRect {
+    fn from(value: ExclusiveRect) -> Self {
+        let ExclusiveRect { left, top, right, bottom } = value;
+        let edge = |start: i16, end: i16| match end.checked_sub(1) {
+            Some(end)   => (start, end),
+            None        => (start.max(i16::MIN + 1), i16::MIN),
+        };
+        let ((left, right), (top, bottom)) = (edge(left, right), edge(top, bottom));
+        Self { left, top, right, bottom }
+    }
+}
+
+impl InclusiveRect {
+    pub const fn new(left: i16, top: i16, right: i16, bottom: i16) -> Self {
+        Self { left, top, right, bottom }
+    }
+
+    /// Returns the number of columns covered by `self` (`right - left + 1`), or [`None`] if that's negative or doesn't fit in a [`u16`].
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// assert_eq!(Some(80), InclusiveRect::new(0, 0, 79, 0).width());
+    /// assert_eq!(Some(1),  InclusiveRect::new(5, 0,  5, 0).width());
+    /// assert_eq!(Some(0),  InclusiveRect::new(5, 0,  4, 0).width());
+    /// assert_eq!(None,     InclusiveRect::new(5, 0,  0, 0).width());
+    /// ```
+    ///
+    pub fn width(&self) -> Option<u16> { u16::try_from(i32::from(self.right) - i32::from(self.left) + 1).ok() }
+
+    /// Returns the number of rows covered by `self` (`bottom - top + 1`), or [`None`] if that's negative or doesn't fit in a [`u16`].
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// assert_eq!(Some(25), InclusiveRect::new(0, 0, 0, 24).height());
+    /// assert_eq!(None,     InclusiveRect::new(0, 5, 0,  0).height());
+    /// ```
+    ///
+    pub fn height(&self) -> Option<u16> { u16::try_from(i32::from(self.bottom) - i32::from(self.top) + 1).ok() }
+
+    /// Returns the [`width`](Self::width) and [`height`](Self::height) of `self`, or [`None`] if either is invalid.
+    pub fn size(&self) -> Option<(u16, u16)> { Some((self.width()?, self.height()?)) }
+
+    /// Returns `true` if `self` covers no cells (`right < left` or `bottom < top`.)
+    pub const fn is_empty(&self) -> bool { self.left > self.right || self.top > self.bottom }
+
+    /// Returns `true` if `coord` is one of the cells covered by `self`.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let window = InclusiveRect::new(0, 0, 79, 24);
+    /// assert!( window.contains((79, 24)));
+    /// assert!(!window.contains((80, 24)));
+    /// ```
+    ///
+    pub fn contains(&self, coord: impl Into<Coord>) -> bool {
+        let Coord { x, y } = coord.into();
+        self.left <= x && x <= self.right && self.top <= y && y <= self.bottom
+    }
+}
+
+impl From<RangeInclusive<Coord>             > for InclusiveRect { fn from(value: RangeInclusive<Coord>              ) -> Self { let (start, end) = value.into_inner(); Self { left: start.x, top: start.y, right: end.x, bottom: end.y } } }
+#[cfg(windows)]
+impl From<RangeInclusive<COORD>             > for InclusiveRect { fn from(value: RangeInclusive<COORD>              ) -> Self { let (start, end) = value.into_inner(); Self { left: start.X, top: start.Y, right: end.X, bottom: end.Y } } }
+impl From<RangeInclusive<(i16, i16)>        > for InclusiveRect { fn from(value: RangeInclusive<(i16, i16)>         ) -> Self { let (start, end) = value.into_inner(); Self { left: start.0, top: start.1, right: end.0, bottom: end.1 } } }
+impl From<(RangeInclusive<i16>, RangeInclusive<i16>)> for InclusiveRect { fn from(value: (RangeInclusive<i16>, RangeInclusive<i16>)) -> Self { let (x, y) = value; Self { left: *x.start(), top: *y.start(), right: *x.end(), bottom: *y.end() } } }
+impl From<RangeInclusive<[i16; 2]>          > for InclusiveRect { fn from(value: RangeInclusive<[i16; 2]>           ) -> Self { let ([left, top], [right, bottom]) = value.into_inner(); Self { left, top, right, bottom } } }
+impl From<[RangeInclusive<i16>; 2]          > for InclusiveRect { fn from(value: [RangeInclusive<i16>; 2]           ) -> Self { let [x, y] = value; Self { left: *x.start(), top: *y.start(), right: *x.end(), bottom: *y.end() } } }
+
+impl From<Range<Coord>                      > for InclusiveRect { fn from(value: Range<Coord>                       ) -> Self { ExclusiveRect::from(value).into() } }
+#[cfg(windows)]
+impl From<Range<COORD>                      > for InclusiveRect { fn from(value: Range<COORD>                       ) -> Self { ExclusiveRect::from(value).into() } }
+impl From<Range<(i16, i16)>                 > for InclusiveRect { fn from(value: Range<(i16, i16)>                  ) -> Self { ExclusiveRect::from(value).into() } }
+impl From<(Range<i16>, Range<i16>)          > for InclusiveRect { fn from(value: (Range<i16>, Range<i16>)           ) -> Self { ExclusiveRect::from(value).into() } }
+impl From<Range<[i16; 2]>                   > for InclusiveRect { fn from(value: Range<[i16; 2]>                    ) -> Self { ExclusiveRect::from(value).into() } }
+impl From<[Range<i16>; 2]                   > for InclusiveRect { fn from(value: [Range<i16>; 2]                    ) -> Self { ExclusiveRect::from(value).into() } }
+
+
+
+#[test] fn exclusive_round_trip() {
+    for r in [InclusiveRect::new(0, 0, 79, 24), InclusiveRect::new(5, 5, 4, 4), InclusiveRect::new(i16::MIN, i16::MIN, i16::MAX - 1, i16::MAX - 1)] {
+        assert_eq!(InclusiveRect::from(ExclusiveRect::from(r)), r);
+    }
+
+    // Edges of i16::MAX saturate instead of wrapping to an empty rectangle
+    let r = ExclusiveRect::from(InclusiveRect::new(0, 0, i16::MAX, 0));
+    assert_eq!(r, ExclusiveRect::new(0, 0, i16::MAX, 1));
+    assert!(!r.is_empty());
+    assert_eq!(r.size(), Some((i16::MAX as u16, 1)));
+    assert_eq!(ExclusiveRect::from(InclusiveRect::new(i16::MIN, i16::MIN, i16::MAX, i16::MAX)).size(), Some((u16::MAX, u16::MAX)));
+
+    // Edges of i16::MIN stay empty instead of wrapping to cover everything
+    for r in [ExclusiveRect::new(i16::MIN, 0, i16::MIN, 1), ExclusiveRect::new(0, i16::MIN, 1, i16::MIN), ExclusiveRect::new(5, 5, i16::MIN, i16::MIN)] {
+        assert!(r.is_empty());
+        assert!(InclusiveRect::from(r).is_empty(), "{r:?}");
+        assert!(ExclusiveRect::from(InclusiveRect::from(r)).is_empty(), "{r:?}");
+    }
+    assert_eq!(ExclusiveRect::from(InclusiveRect::new(0, 0, 79, 24)), ExclusiveRect::new(0, 0, 80, 25));
+    assert!(InclusiveRect::new(5, 5, 4, 4).is_empty() && ExclusiveRect::from(InclusiveRect::new(5, 5, 4, 4)).is_empty());
+}
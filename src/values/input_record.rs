@@ -1,6 +1,6 @@
-use crate::{AsciiOrUnicodeChar, Coord, InputRecordEventType};
-use crate::{FOCUS_EVENT, KEY_EVENT, MENU_EVENT, MOUSE_EVENT, WINDOW_BUFFER_SIZE_EVENT};
-use winapi::um::wincon::{FOCUS_EVENT_RECORD, INPUT_RECORD, KEY_EVENT_RECORD, MENU_EVENT_RECORD, MOUSE_EVENT_RECORD, WINDOW_BUFFER_SIZE_RECORD};
+use crate::{AsciiOrUnicodeChar, ControlKeyState, Coord, InputRecordEventType, MouseButtons, MouseEventFlags, VirtualKey};
+use crate::{FOCUS_EVENT, KEY_EVENT, MENU_EVENT, MOUSE_EVENT, MOUSE_HWHEELED, MOUSE_WHEELED, WINDOW_BUFFER_SIZE_EVENT};
+#[cfg(windows)] use winapi::um::wincon::{FOCUS_EVENT_RECORD, INPUT_RECORD, KEY_EVENT_RECORD, MENU_EVENT_RECORD, MOUSE_EVENT_RECORD, WINDOW_BUFFER_SIZE_RECORD};
 use bytemuck::Zeroable;
 
 
@@ -9,12 +9,34 @@ use bytemuck::Zeroable;
 /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/input-record-str)\]
 /// [KeyEventRecord] | [MouseEventRecord] | [WindowBufferSizeRecord] | ...
 ///
-#[derive(Clone, Copy, Default)]
-#[repr(transparent)] pub struct InputRecord(INPUT_RECORD);
+#[derive(Clone, Copy)]
+#[repr(C)] pub struct InputRecord {
+    event_type: InputRecordEventType,
+    event:      InputRecordEvent,
+}
+
+#[derive(Clone, Copy)]
+#[repr(C)] union InputRecordEvent {
+    key_event:                  KeyEventRecord,
+    mouse_event:                MouseEventRecord,
+    window_buffer_size_event:   WindowBufferSizeRecord,
+    menu_event:                 MenuEventRecord,
+    focus_event:                FocusEventRecord,
+}
 
 //safe impl bytemuck::Pod      for InputRecord {} // XXX: `InputRecord` can probably have uninit/padding bytes, depending on active union member
 unsafe impl bytemuck::Zeroable for InputRecord {}
 
+impl Default for InputRecord { fn default() -> Self { Self::zeroed() } }
+
+#[cfg(windows)] const _ : () = {
+    use core::mem::offset_of;
+    assert!(align_of::<INPUT_RECORD>() == align_of::<InputRecord>());
+    assert!(size_of ::<INPUT_RECORD>() == size_of ::<InputRecord>());
+    assert!(offset_of!(InputRecord, event_type  ) == offset_of!(INPUT_RECORD, EventType ));
+    assert!(offset_of!(InputRecord, event       ) == offset_of!(INPUT_RECORD, Event     ));
+};
+
 impl InputRecord {
     /// ### Safety
     /// `input_record.Event.*` must be properly initialized to match `input_record.EventType`.
@@ -33,10 +55,11 @@ impl InputRecord {
     /// let input_record : InputRecord = FocusEventRecord::default().into();
     /// ```
     ///
-    pub const unsafe fn from_unchecked(input_record: INPUT_RECORD) -> Self { Self(input_record) }
+    #[cfg(windows)]
+    pub const unsafe fn from_unchecked(input_record: INPUT_RECORD) -> Self { unsafe { core::mem::transmute(input_record) } }
 
     /// Returns one of [`KEY_EVENT`], [`MOUSE_EVENT`], [`WINDOW_BUFFER_SIZE_EVENT`], ...
-    pub fn event_type(&self) -> InputRecordEventType { InputRecordEventType::from(self.0.EventType) }
+    pub fn event_type(&self) -> InputRecordEventType { self.event_type }
 
     /// Returns <code>[Some]\(...\)</code> if <code>[event_type](Self::event_type)()</code> is [`KEY_EVENT`].
     pub fn as_key_event(&self)                  -> Option<&KeyEventRecord           > { self.try_into().ok() }
@@ -54,9 +77,9 @@ impl InputRecord {
     pub fn as_focus_event(&self)                -> Option<&FocusEventRecord         > { self.try_into().ok() }
 }
 
-impl AsRef<INPUT_RECORD> for InputRecord { fn as_ref(&self) -> &INPUT_RECORD { &self.0 } }
+#[cfg(windows)] impl AsRef<INPUT_RECORD> for InputRecord { fn as_ref(&self) -> &INPUT_RECORD { unsafe { core::mem::transmute(self) } } }
 //pl AsMut<INPUT_RECORD> for InputRecord { fn as_mut(&self) -> &INPUT_RECORD { &self.0 } } // XXX: do not implement: `Event` might not be fully initialized for a different `EventType`
-impl From<InputRecord> for INPUT_RECORD { fn from(value: InputRecord) -> Self { value.0 } }
+#[cfg(windows)] impl From<InputRecord> for INPUT_RECORD { fn from(value: InputRecord) -> Self { unsafe { core::mem::transmute(value) } } }
 
 impl core::fmt::Debug for InputRecord {
     fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
@@ -83,18 +106,18 @@ impl core::fmt::Debug for InputRecord {
 #[repr(C)] pub struct KeyEventRecord {
     pub key_down:           abibool::bool32,
     pub repeat_count:       u16,
-    pub virtual_key_code:   u16, // XXX: better types?
+    pub virtual_key_code:   VirtualKey,
     pub virtual_scan_code:  u16,
     pub char:               AsciiOrUnicodeChar,
-    pub control_key_state:  u32, // XXX: better types?
+    pub control_key_state:  ControlKeyState,
 }
 
-impl AsRef<KeyEventRecord> for KEY_EVENT_RECORD { fn as_ref(&self) -> &KeyEventRecord   { unsafe { core::mem::transmute(self) } } }
-impl AsRef<KEY_EVENT_RECORD> for KeyEventRecord { fn as_ref(&self) -> &KEY_EVENT_RECORD { unsafe { core::mem::transmute(self) } } }
-impl From<KeyEventRecord> for KEY_EVENT_RECORD { fn from(value: KeyEventRecord  ) -> Self { unsafe { core::mem::transmute(value) } } }
-impl From<KEY_EVENT_RECORD> for KeyEventRecord { fn from(value: KEY_EVENT_RECORD) -> Self { unsafe { core::mem::transmute(value) } } }
+#[cfg(windows)] impl AsRef<KeyEventRecord> for KEY_EVENT_RECORD { fn as_ref(&self) -> &KeyEventRecord   { unsafe { core::mem::transmute(self) } } }
+#[cfg(windows)] impl AsRef<KEY_EVENT_RECORD> for KeyEventRecord { fn as_ref(&self) -> &KEY_EVENT_RECORD { unsafe { core::mem::transmute(self) } } }
+#[cfg(windows)] impl From<KeyEventRecord> for KEY_EVENT_RECORD { fn from(value: KeyEventRecord  ) -> Self { unsafe { core::mem::transmute(value) } } }
+#[cfg(windows)] impl From<KEY_EVENT_RECORD> for KeyEventRecord { fn from(value: KEY_EVENT_RECORD) -> Self { unsafe { core::mem::transmute(value) } } }
 
-const _ : () = {
+#[cfg(windows)] const _ : () = {
     use core::mem::offset_of;
     assert!(align_of::<KEY_EVENT_RECORD>() == align_of::<KeyEventRecord>());
     assert!(size_of ::<KEY_EVENT_RECORD>() == size_of ::<KeyEventRecord>());
@@ -109,8 +132,8 @@ const _ : () = {
 impl From<KeyEventRecord> for InputRecord {
     fn from(value: KeyEventRecord) -> Self {
         let mut r = InputRecord::zeroed();
-        r.0.EventType = KEY_EVENT.into();
-        unsafe { *r.0.Event.KeyEvent_mut() = value.into() };
+        r.event_type = KEY_EVENT;
+        r.event.key_event = value;
         r
     }
 }
@@ -119,7 +142,7 @@ impl<'a> TryFrom<&'a InputRecord> for &'a KeyEventRecord {
     type Error = ();
     fn try_from(value: &'a InputRecord) -> Result<Self, Self::Error> {
         if value.event_type() == KEY_EVENT {
-            Ok(unsafe { value.0.Event.KeyEvent() }.as_ref())
+            Ok(unsafe { &value.event.key_event })
         } else {
             Err(())
         }
@@ -137,12 +160,12 @@ impl<'a> TryFrom<&'a InputRecord> for &'a KeyEventRecord {
     pub command_id:     u32,
 }
 
-impl AsRef<MenuEventRecord> for MENU_EVENT_RECORD { fn as_ref(&self) -> &MenuEventRecord   { unsafe { core::mem::transmute(self) } } }
-impl AsRef<MENU_EVENT_RECORD> for MenuEventRecord { fn as_ref(&self) -> &MENU_EVENT_RECORD { unsafe { core::mem::transmute(self) } } }
-impl From<MenuEventRecord> for MENU_EVENT_RECORD { fn from(value: MenuEventRecord  ) -> Self { unsafe { core::mem::transmute(value) } } }
-impl From<MENU_EVENT_RECORD> for MenuEventRecord { fn from(value: MENU_EVENT_RECORD) -> Self { unsafe { core::mem::transmute(value) } } }
+#[cfg(windows)] impl AsRef<MenuEventRecord> for MENU_EVENT_RECORD { fn as_ref(&self) -> &MenuEventRecord   { unsafe { core::mem::transmute(self) } } }
+#[cfg(windows)] impl AsRef<MENU_EVENT_RECORD> for MenuEventRecord { fn as_ref(&self) -> &MENU_EVENT_RECORD { unsafe { core::mem::transmute(self) } } }
+#[cfg(windows)] impl From<MenuEventRecord> for MENU_EVENT_RECORD { fn from(value: MenuEventRecord  ) -> Self { unsafe { core::mem::transmute(value) } } }
+#[cfg(windows)] impl From<MENU_EVENT_RECORD> for MenuEventRecord { fn from(value: MENU_EVENT_RECORD) -> Self { unsafe { core::mem::transmute(value) } } }
 
-const _ : () = {
+#[cfg(windows)] const _ : () = {
     use core::mem::offset_of;
     assert!(align_of::<MENU_EVENT_RECORD>() == align_of::<MenuEventRecord>());
     assert!(size_of ::<MENU_EVENT_RECORD>() == size_of ::<MenuEventRecord>());
@@ -152,8 +175,8 @@ const _ : () = {
 impl From<MenuEventRecord> for InputRecord {
     fn from(value: MenuEventRecord) -> Self {
         let mut r = InputRecord::zeroed();
-        r.0.EventType = MENU_EVENT.into();
-        unsafe { *r.0.Event.MenuEvent_mut() = value.into() };
+        r.event_type = MENU_EVENT;
+        r.event.menu_event = value;
         r
     }
 }
@@ -162,7 +185,7 @@ impl<'a> TryFrom<&'a InputRecord> for &'a MenuEventRecord {
     type Error = ();
     fn try_from(value: &'a InputRecord) -> Result<Self, Self::Error> {
         if value.event_type() == MENU_EVENT {
-            Ok(unsafe { value.0.Event.MenuEvent() }.as_ref())
+            Ok(unsafe { &value.event.menu_event })
         } else {
             Err(())
         }
@@ -178,17 +201,17 @@ impl<'a> TryFrom<&'a InputRecord> for &'a MenuEventRecord {
 #[derive(Clone, Copy, bytemuck::Pod, Debug, Default, bytemuck::Zeroable)]
 #[repr(C)] pub struct MouseEventRecord {
     pub mouse_position:     Coord,
-    pub button_state:       u32, // XXX: better type?
-    pub control_key_state:  u32, // XXX: better type?
-    pub event_flags:        u32, // XXX: better type?
+    pub button_state:       MouseButtons,
+    pub control_key_state:  ControlKeyState,
+    pub event_flags:        MouseEventFlags,
 }
 
-impl AsRef<MouseEventRecord> for MOUSE_EVENT_RECORD { fn as_ref(&self) -> &MouseEventRecord   { unsafe { core::mem::transmute(self) } } }
-impl AsRef<MOUSE_EVENT_RECORD> for MouseEventRecord { fn as_ref(&self) -> &MOUSE_EVENT_RECORD { unsafe { core::mem::transmute(self) } } }
-impl From<MouseEventRecord> for MOUSE_EVENT_RECORD { fn from(value: MouseEventRecord  ) -> Self { unsafe { core::mem::transmute(value) } } }
-impl From<MOUSE_EVENT_RECORD> for MouseEventRecord { fn from(value: MOUSE_EVENT_RECORD) -> Self { unsafe { core::mem::transmute(value) } } }
+#[cfg(windows)] impl AsRef<MouseEventRecord> for MOUSE_EVENT_RECORD { fn as_ref(&self) -> &MouseEventRecord   { unsafe { core::mem::transmute(self) } } }
+#[cfg(windows)] impl AsRef<MOUSE_EVENT_RECORD> for MouseEventRecord { fn as_ref(&self) -> &MOUSE_EVENT_RECORD { unsafe { core::mem::transmute(self) } } }
+#[cfg(windows)] impl From<MouseEventRecord> for MOUSE_EVENT_RECORD { fn from(value: MouseEventRecord  ) -> Self { unsafe { core::mem::transmute(value) } } }
+#[cfg(windows)] impl From<MOUSE_EVENT_RECORD> for MouseEventRecord { fn from(value: MOUSE_EVENT_RECORD) -> Self { unsafe { core::mem::transmute(value) } } }
 
-const _ : () = {
+#[cfg(windows)] const _ : () = {
     use core::mem::offset_of;
     assert!(align_of::<MOUSE_EVENT_RECORD>() == align_of::<MouseEventRecord>());
     assert!(size_of ::<MOUSE_EVENT_RECORD>() == size_of ::<MouseEventRecord>());
@@ -198,11 +221,28 @@ const _ : () = {
     assert!(offset_of!(MouseEventRecord, event_flags        ) == offset_of!(MOUSE_EVENT_RECORD, dwEventFlags        ));
 };
 
+impl MouseEventRecord {
+    /// The signed vertical wheel delta (positive = rotated forward, away from the user), or [`None`] if this isn't a [`MOUSE_WHEELED`] event.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let event = MouseEventRecord { button_state: 0xFF88_0000.into(), event_flags: MOUSE_WHEELED, .. Default::default() };
+    /// assert_eq!(event.wheel_delta(), Some(-120));
+    /// assert_eq!(event.hwheel_delta(), None);
+    /// ```
+    ///
+    pub fn wheel_delta(&self) -> Option<i16> { self.event_flags.contains(MOUSE_WHEELED).then(|| self.button_state.wheel_delta()) }
+
+    /// The signed horizontal wheel delta (positive = rotated right), or [`None`] if this isn't a [`MOUSE_HWHEELED`] event.
+    pub fn hwheel_delta(&self) -> Option<i16> { self.event_flags.contains(MOUSE_HWHEELED).then(|| self.button_state.wheel_delta()) }
+}
+
 impl From<MouseEventRecord> for InputRecord {
     fn from(value: MouseEventRecord) -> Self {
         let mut r = InputRecord::zeroed();
-        r.0.EventType = MOUSE_EVENT.into();
-        unsafe { *r.0.Event.MouseEvent_mut() = value.into() };
+        r.event_type = MOUSE_EVENT;
+        r.event.mouse_event = value;
         r
     }
 }
@@ -211,7 +251,7 @@ impl<'a> TryFrom<&'a InputRecord> for &'a MouseEventRecord {
     type Error = ();
     fn try_from(value: &'a InputRecord) -> Result<Self, Self::Error> {
         if value.event_type() == MOUSE_EVENT {
-            Ok(unsafe { value.0.Event.MouseEvent() }.as_ref())
+            Ok(unsafe { &value.event.mouse_event })
         } else {
             Err(())
         }
@@ -229,12 +269,12 @@ impl<'a> TryFrom<&'a InputRecord> for &'a MouseEventRecord {
     pub set_focus:  abibool::bool32,
 }
 
-impl AsRef<FocusEventRecord> for FOCUS_EVENT_RECORD { fn as_ref(&self) -> &FocusEventRecord   { unsafe { core::mem::transmute(self) } } }
-impl AsRef<FOCUS_EVENT_RECORD> for FocusEventRecord { fn as_ref(&self) -> &FOCUS_EVENT_RECORD { unsafe { core::mem::transmute(self) } } }
-impl From<FocusEventRecord> for FOCUS_EVENT_RECORD { fn from(value: FocusEventRecord  ) -> Self { unsafe { core::mem::transmute(value) } } }
-impl From<FOCUS_EVENT_RECORD> for FocusEventRecord { fn from(value: FOCUS_EVENT_RECORD) -> Self { unsafe { core::mem::transmute(value) } } }
+#[cfg(windows)] impl AsRef<FocusEventRecord> for FOCUS_EVENT_RECORD { fn as_ref(&self) -> &FocusEventRecord   { unsafe { core::mem::transmute(self) } } }
+#[cfg(windows)] impl AsRef<FOCUS_EVENT_RECORD> for FocusEventRecord { fn as_ref(&self) -> &FOCUS_EVENT_RECORD { unsafe { core::mem::transmute(self) } } }
+#[cfg(windows)] impl From<FocusEventRecord> for FOCUS_EVENT_RECORD { fn from(value: FocusEventRecord  ) -> Self { unsafe { core::mem::transmute(value) } } }
+#[cfg(windows)] impl From<FOCUS_EVENT_RECORD> for FocusEventRecord { fn from(value: FOCUS_EVENT_RECORD) -> Self { unsafe { core::mem::transmute(value) } } }
 
-const _ : () = {
+#[cfg(windows)] const _ : () = {
     use core::mem::offset_of;
     assert!(align_of::<FOCUS_EVENT_RECORD>() == align_of::<FocusEventRecord>());
     assert!(size_of ::<FOCUS_EVENT_RECORD>() == size_of ::<FocusEventRecord>());
@@ -244,8 +284,8 @@ const _ : () = {
 impl From<FocusEventRecord> for InputRecord {
     fn from(value: FocusEventRecord) -> Self {
         let mut r = InputRecord::zeroed();
-        r.0.EventType = FOCUS_EVENT.into();
-        unsafe { *r.0.Event.FocusEvent_mut() = value.into() };
+        r.event_type = FOCUS_EVENT;
+        r.event.focus_event = value;
         r
     }
 }
@@ -254,7 +294,7 @@ impl<'a> TryFrom<&'a InputRecord> for &'a FocusEventRecord {
     type Error = ();
     fn try_from(value: &'a InputRecord) -> Result<Self, Self::Error> {
         if value.event_type() == FOCUS_EVENT {
-            Ok(unsafe { value.0.Event.FocusEvent() }.as_ref())
+            Ok(unsafe { &value.event.focus_event })
         } else {
             Err(())
         }
@@ -273,12 +313,12 @@ impl<'a> TryFrom<&'a InputRecord> for &'a FocusEventRecord {
     pub size: Coord,
 }
 
-impl AsRef<WindowBufferSizeRecord> for WINDOW_BUFFER_SIZE_RECORD { fn as_ref(&self) -> &WindowBufferSizeRecord    { unsafe { core::mem::transmute(self) } } }
-impl AsRef<WINDOW_BUFFER_SIZE_RECORD> for WindowBufferSizeRecord { fn as_ref(&self) -> &WINDOW_BUFFER_SIZE_RECORD { unsafe { core::mem::transmute(self) } } }
-impl From<WindowBufferSizeRecord> for WINDOW_BUFFER_SIZE_RECORD  { fn from(value: WindowBufferSizeRecord   ) -> Self { unsafe { core::mem::transmute(value) } } }
-impl From<WINDOW_BUFFER_SIZE_RECORD> for WindowBufferSizeRecord  { fn from(value: WINDOW_BUFFER_SIZE_RECORD) -> Self { unsafe { core::mem::transmute(value) } } }
+#[cfg(windows)] impl AsRef<WindowBufferSizeRecord> for WINDOW_BUFFER_SIZE_RECORD { fn as_ref(&self) -> &WindowBufferSizeRecord    { unsafe { core::mem::transmute(self) } } }
+#[cfg(windows)] impl AsRef<WINDOW_BUFFER_SIZE_RECORD> for WindowBufferSizeRecord { fn as_ref(&self) -> &WINDOW_BUFFER_SIZE_RECORD { unsafe { core::mem::transmute(self) } } }
+#[cfg(windows)] impl From<WindowBufferSizeRecord> for WINDOW_BUFFER_SIZE_RECORD  { fn from(value: WindowBufferSizeRecord   ) -> Self { unsafe { core::mem::transmute(value) } } }
+#[cfg(windows)] impl From<WINDOW_BUFFER_SIZE_RECORD> for WindowBufferSizeRecord  { fn from(value: WINDOW_BUFFER_SIZE_RECORD) -> Self { unsafe { core::mem::transmute(value) } } }
 
-const _ : () = {
+#[cfg(windows)] const _ : () = {
     use core::mem::offset_of;
     assert!(align_of::<WINDOW_BUFFER_SIZE_RECORD>() == align_of::<WindowBufferSizeRecord>());
     assert!(size_of ::<WINDOW_BUFFER_SIZE_RECORD>() == size_of ::<WindowBufferSizeRecord>());
@@ -288,8 +328,8 @@ const _ : () = {
 impl From<WindowBufferSizeRecord> for InputRecord {
     fn from(value: WindowBufferSizeRecord) -> Self {
         let mut r = InputRecord::zeroed();
-        r.0.EventType = WINDOW_BUFFER_SIZE_EVENT.into();
-        unsafe { *r.0.Event.WindowBufferSizeEvent_mut() = value.into() };
+        r.event_type = WINDOW_BUFFER_SIZE_EVENT;
+        r.event.window_buffer_size_event = value;
         r
     }
 }
@@ -298,7 +338,7 @@ impl<'a> TryFrom<&'a InputRecord> for &'a WindowBufferSizeRecord {
     type Error = ();
     fn try_from(value: &'a InputRecord) -> Result<Self, Self::Error> {
         if value.event_type() == WINDOW_BUFFER_SIZE_EVENT {
-            Ok(unsafe { value.0.Event.WindowBufferSizeEvent() }.as_ref())
+            Ok(unsafe { &value.event.window_buffer_size_event })
         } else {
             Err(())
         }
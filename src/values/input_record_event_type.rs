@@ -37,7 +37,7 @@ pub const MENU_EVENT                : InputRecordEventType = InputRecordEventTyp
 ///
 pub const FOCUS_EVENT               : InputRecordEventType = InputRecordEventType(0x0010);
 
-const _ : () = {
+#[cfg(windows)] const _ : () = {
     assert!(KEY_EVENT.0                 == winapi::um::wincon::KEY_EVENT                );
     assert!(MOUSE_EVENT.0               == winapi::um::wincon::MOUSE_EVENT              );
     assert!(WINDOW_BUFFER_SIZE_EVENT.0  == winapi::um::wincon::WINDOW_BUFFER_SIZE_EVENT );
@@ -0,0 +1,106 @@
+#[allow(unused_imports)] use crate::*;
+
+use core::fmt::{self, Debug, Formatter};
+use core::ops::*;
+
+
+
+#[doc(alias = "dwButtonState")]
+/// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/mouse-event-record-str)\]
+/// [MouseEventRecord]::[button_state](MouseEventRecord::button_state): which mouse buttons are held, and (for [`MOUSE_WHEELED`] / [`MOUSE_HWHEELED`] events) the wheel delta
+///
+/// ### Example
+/// ```
+/// # use maulingmonkey_console_winapi_wrappers::*;
+/// let buttons = FROM_LEFT_1ST_BUTTON_PRESSED | RIGHTMOST_BUTTON_PRESSED;
+/// assert!(buttons.contains(RIGHTMOST_BUTTON_PRESSED));
+/// assert_eq!(format!("{buttons:?}"), "FROM_LEFT_1ST_BUTTON_PRESSED | RIGHTMOST_BUTTON_PRESSED");
+///
+/// let wheeled = MouseButtons::from(0xFF88_0000); // -120 (one notch toward the user)
+/// assert_eq!(wheeled.wheel_delta(), -120);
+/// assert_eq!(wheeled.pressed(), MouseButtons::default());
+/// ```
+///
+#[repr(transparent)] #[derive(Clone, Copy, bytemuck::Pod, Default, bytemuck::Zeroable, PartialEq, Eq, PartialOrd, Ord, Hash)] pub struct MouseButtons(u32);
+
+impl From<u32> for MouseButtons { fn from(value: u32) -> Self { Self(value) } }
+impl From<MouseButtons> for u32 { fn from(value: MouseButtons) -> Self { value.0 } }
+
+impl BitAnd for MouseButtons { type Output = Self; fn bitand(self, rhs: Self) -> Self::Output { Self(self.0 & rhs.0) } }
+impl BitXor for MouseButtons { type Output = Self; fn bitxor(self, rhs: Self) -> Self::Output { Self(self.0 ^ rhs.0) } }
+impl BitOr  for MouseButtons { type Output = Self; fn bitor (self, rhs: Self) -> Self::Output { Self(self.0 | rhs.0) } }
+impl Not    for MouseButtons { type Output = Self; fn not   (self)            -> Self::Output { Self(!self.0) } }
+
+impl BitAndAssign for MouseButtons { fn bitand_assign(&mut self, rhs: Self) { self.0 &= rhs.0; } }
+impl BitXorAssign for MouseButtons { fn bitxor_assign(&mut self, rhs: Self) { self.0 ^= rhs.0; } }
+impl BitOrAssign  for MouseButtons { fn bitor_assign (&mut self, rhs: Self) { self.0 |= rhs.0; } }
+
+
+
+/// The leftmost mouse button.
+#[doc(hidden)] pub const FROM_LEFT_1ST_BUTTON_PRESSED  : MouseButtons = MouseButtons(0x0001);
+
+/// The rightmost mouse button.
+#[doc(hidden)] pub const RIGHTMOST_BUTTON_PRESSED      : MouseButtons = MouseButtons(0x0002);
+
+/// The second button from the left (typically the middle button.)
+#[doc(hidden)] pub const FROM_LEFT_2ND_BUTTON_PRESSED  : MouseButtons = MouseButtons(0x0004);
+
+/// The third button from the left.
+#[doc(hidden)] pub const FROM_LEFT_3RD_BUTTON_PRESSED  : MouseButtons = MouseButtons(0x0008);
+
+/// The fourth button from the left.
+#[doc(hidden)] pub const FROM_LEFT_4TH_BUTTON_PRESSED  : MouseButtons = MouseButtons(0x0010);
+
+#[cfg(windows)] const _ : () = {
+    use winapi::um::wincon;
+    assert!(FROM_LEFT_1ST_BUTTON_PRESSED.0  == wincon::FROM_LEFT_1ST_BUTTON_PRESSED );
+    assert!(RIGHTMOST_BUTTON_PRESSED.0      == wincon::RIGHTMOST_BUTTON_PRESSED     );
+    assert!(FROM_LEFT_2ND_BUTTON_PRESSED.0  == wincon::FROM_LEFT_2ND_BUTTON_PRESSED );
+    assert!(FROM_LEFT_3RD_BUTTON_PRESSED.0  == wincon::FROM_LEFT_3RD_BUTTON_PRESSED );
+    assert!(FROM_LEFT_4TH_BUTTON_PRESSED.0  == wincon::FROM_LEFT_4TH_BUTTON_PRESSED );
+};
+
+const NAMES : &[(&str, MouseButtons)] = &[
+    ("FROM_LEFT_1ST_BUTTON_PRESSED",    FROM_LEFT_1ST_BUTTON_PRESSED    ),
+    ("RIGHTMOST_BUTTON_PRESSED",        RIGHTMOST_BUTTON_PRESSED        ),
+    ("FROM_LEFT_2ND_BUTTON_PRESSED",    FROM_LEFT_2ND_BUTTON_PRESSED    ),
+    ("FROM_LEFT_3RD_BUTTON_PRESSED",    FROM_LEFT_3RD_BUTTON_PRESSED    ),
+    ("FROM_LEFT_4TH_BUTTON_PRESSED",    FROM_LEFT_4TH_BUTTON_PRESSED    ),
+];
+
+impl MouseButtons {
+    /// The leftmost mouse button.
+    pub const FROM_LEFT_1ST_BUTTON_PRESSED  : MouseButtons = FROM_LEFT_1ST_BUTTON_PRESSED;
+
+    /// The rightmost mouse button.
+    pub const RIGHTMOST_BUTTON_PRESSED      : MouseButtons = RIGHTMOST_BUTTON_PRESSED;
+
+    /// The second button from the left (typically the middle button.)
+    pub const FROM_LEFT_2ND_BUTTON_PRESSED  : MouseButtons = FROM_LEFT_2ND_BUTTON_PRESSED;
+
+    /// The third button from the left.
+    pub const FROM_LEFT_3RD_BUTTON_PRESSED  : MouseButtons = FROM_LEFT_3RD_BUTTON_PRESSED;
+
+    /// The fourth button from the left.
+    pub const FROM_LEFT_4TH_BUTTON_PRESSED  : MouseButtons = FROM_LEFT_4TH_BUTTON_PRESSED;
+
+    /// Returns `true` if all bits of `buttons` are set in `self`.
+    pub const fn contains(self, buttons: MouseButtons) -> bool { self.0 & buttons.0 == buttons.0 }
+
+    /// The button bits (the low word), without any wheel delta.
+    pub const fn pressed(self) -> MouseButtons { MouseButtons(self.0 & 0xFFFF) }
+
+    /// The high word, reinterpreted as a signed wheel delta (typically a multiple of `WHEEL_DELTA` = `120`.)
+    ///
+    /// Only meaningful for [`MOUSE_WHEELED`] (positive = rotated forward, away from the user) and [`MOUSE_HWHEELED`] (positive = rotated right) events.
+    /// Prefer [`MouseEventRecord::wheel_delta`] / [`MouseEventRecord::hwheel_delta`], which check [`event_flags`](MouseEventRecord::event_flags).
+    pub const fn wheel_delta(self) -> i16 { (self.0 >> 16) as u16 as i16 }
+}
+
+/// Lists the pressed buttons (e.g. `FROM_LEFT_1ST_BUTTON_PRESSED | RIGHTMOST_BUTTON_PRESSED`), any other bits in hex, or `0` if none are set.
+impl Debug for MouseButtons {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        super::fmt_flags(fmt, self.0, NAMES.iter().map(|&(name, flag)| (name, flag.0)))
+    }
+}
@@ -0,0 +1,86 @@
+#[allow(unused_imports)] use crate::*;
+
+use core::fmt::{self, Debug, Formatter};
+use core::ops::*;
+
+
+
+#[doc(alias = "dwEventFlags")]
+/// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/mouse-event-record-str)\]
+/// [MouseEventRecord]::[event_flags](MouseEventRecord::event_flags): the kind of mouse event.  `0` (no flags) indicates a button was pressed or released.
+///
+/// ### Example
+/// ```
+/// # use maulingmonkey_console_winapi_wrappers::*;
+/// let flags = MOUSE_MOVED | DOUBLE_CLICK;
+/// assert!(flags.contains(DOUBLE_CLICK) && !flags.contains(MOUSE_WHEELED));
+/// assert_eq!(format!("{flags:?}"), "MOUSE_MOVED | DOUBLE_CLICK");
+/// assert_eq!(format!("{:?}", MouseEventFlags::default()), "0");
+/// ```
+///
+#[repr(transparent)] #[derive(Clone, Copy, bytemuck::Pod, Default, bytemuck::Zeroable, PartialEq, Eq, PartialOrd, Ord, Hash)] pub struct MouseEventFlags(u32);
+
+impl From<u32> for MouseEventFlags { fn from(value: u32) -> Self { Self(value) } }
+impl From<MouseEventFlags> for u32 { fn from(value: MouseEventFlags) -> Self { value.0 } }
+
+impl BitAnd for MouseEventFlags { type Output = Self; fn bitand(self, rhs: Self) -> Self::Output { Self(self.0 & rhs.0) } }
+impl BitXor for MouseEventFlags { type Output = Self; fn bitxor(self, rhs: Self) -> Self::Output { Self(self.0 ^ rhs.0) } }
+impl BitOr  for MouseEventFlags { type Output = Self; fn bitor (self, rhs: Self) -> Self::Output { Self(self.0 | rhs.0) } }
+impl Not    for MouseEventFlags { type Output = Self; fn not   (self)            -> Self::Output { Self(!self.0) } }
+
+impl BitAndAssign for MouseEventFlags { fn bitand_assign(&mut self, rhs: Self) { self.0 &= rhs.0; } }
+impl BitXorAssign for MouseEventFlags { fn bitxor_assign(&mut self, rhs: Self) { self.0 ^= rhs.0; } }
+impl BitOrAssign  for MouseEventFlags { fn bitor_assign (&mut self, rhs: Self) { self.0 |= rhs.0; } }
+
+
+
+/// A change in mouse position occurred.
+#[doc(hidden)] pub const MOUSE_MOVED       : MouseEventFlags = MouseEventFlags(0x0001);
+
+/// The second click (button press) of a double-click occurred.  The first click is returned as a regular button-press event.
+#[doc(hidden)] pub const DOUBLE_CLICK      : MouseEventFlags = MouseEventFlags(0x0002);
+
+/// The vertical mouse wheel was moved.  See [`MouseEventRecord::wheel_delta`].
+#[doc(hidden)] pub const MOUSE_WHEELED     : MouseEventFlags = MouseEventFlags(0x0004);
+
+/// The horizontal mouse wheel was moved.  See [`MouseEventRecord::hwheel_delta`].
+#[doc(hidden)] pub const MOUSE_HWHEELED    : MouseEventFlags = MouseEventFlags(0x0008);
+
+#[cfg(windows)] const _ : () = {
+    use winapi::um::wincon;
+    assert!(MOUSE_MOVED.0       == wincon::MOUSE_MOVED      );
+    assert!(DOUBLE_CLICK.0      == wincon::DOUBLE_CLICK     );
+    assert!(MOUSE_WHEELED.0     == wincon::MOUSE_WHEELED    );
+    assert!(MOUSE_HWHEELED.0    == wincon::MOUSE_HWHEELED   );
+};
+
+const NAMES : &[(&str, MouseEventFlags)] = &[
+    ("MOUSE_MOVED",     MOUSE_MOVED     ),
+    ("DOUBLE_CLICK",    DOUBLE_CLICK    ),
+    ("MOUSE_WHEELED",   MOUSE_WHEELED   ),
+    ("MOUSE_HWHEELED",  MOUSE_HWHEELED  ),
+];
+
+impl MouseEventFlags {
+    /// A change in mouse position occurred.
+    pub const MOUSE_MOVED       : MouseEventFlags = MOUSE_MOVED;
+
+    /// The second click (button press) of a double-click occurred.  The first click is returned as a regular button-press event.
+    pub const DOUBLE_CLICK      : MouseEventFlags = DOUBLE_CLICK;
+
+    /// The vertical mouse wheel was moved.  See [`MouseEventRecord::wheel_delta`].
+    pub const MOUSE_WHEELED     : MouseEventFlags = MOUSE_WHEELED;
+
+    /// The horizontal mouse wheel was moved.  See [`MouseEventRecord::hwheel_delta`].
+    pub const MOUSE_HWHEELED    : MouseEventFlags = MOUSE_HWHEELED;
+
+    /// Returns `true` if all bits of `flags` are set in `self`.
+    pub const fn contains(self, flags: MouseEventFlags) -> bool { self.0 & flags.0 == flags.0 }
+}
+
+/// Lists the set flags (e.g. `MOUSE_MOVED | DOUBLE_CLICK`), any unnamed bits in hex, or `0` if none are set.
+impl Debug for MouseEventFlags {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        super::fmt_flags(fmt, self.0, NAMES.iter().map(|&(name, flag)| (name, flag.0)))
+    }
+}
@@ -0,0 +1,780 @@
+#[allow(unused_imports)] use crate::*;
+
+use core::fmt::{self, Debug, Display, Formatter};
+use core::str::FromStr;
+use std::io;
+
+
+
+#[doc(alias = "VK_*")]
+/// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/inputdev/virtual-key-codes)\]
+/// [KeyEventRecord]::[virtual_key_code](KeyEventRecord::virtual_key_code): a layout-independent key identifier ([`VK_SPACE`](Self::VK_SPACE), [`VK_F5`](Self::VK_F5), ...)
+///
+/// Letter and digit keys have no `VK_*` constants in the Windows SDK - their codes are the uppercase ASCII character.
+/// [`VK_A`](Self::VK_A) ..= [`VK_Z`](Self::VK_Z) and [`VK_0`](Self::VK_0) ..= [`VK_9`](Self::VK_9) are provided for completeness.
+///
+/// ### Example
+/// ```
+/// # use maulingmonkey_console_winapi_wrappers::*;
+/// let vk = VirtualKey::from(0x74);
+/// assert_eq!(vk, VirtualKey::VK_F5);
+/// assert_eq!(format!("{vk:?}"), "VK_F5");
+/// assert_eq!(format!("{vk}"), "F5");
+/// assert_eq!("OEM_PLUS".parse::<VirtualKey>().unwrap(), VirtualKey::VK_OEM_PLUS);
+/// assert_eq!("vk_oem_plus".parse::<VirtualKey>().unwrap(), VirtualKey::VK_OEM_PLUS);
+/// assert_eq!(u16::from(VirtualKey::VK_A), u16::from(b'A'));
+/// assert!(vk.is_function_key() && !vk.is_modifier() && !vk.is_numpad());
+/// ```
+///
+#[derive(Clone, Copy, bytemuck::Pod, Default, bytemuck::Zeroable, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)] pub struct VirtualKey(u16);
+
+impl From<VirtualKey> for u16 { fn from(value: VirtualKey) -> Self { value.0 } }
+impl From<u16> for VirtualKey { fn from(value: u16) -> Self { Self(value) } }
+
+macro_rules! virtual_keys {
+    ( $( $(#[$meta:meta])* $name:ident = $value:literal, )* ) => {
+        $(
+            $(#[$meta])*
+            #[doc(hidden)]
+            pub const $name : VirtualKey = VirtualKey($value);
+        )*
+
+        impl VirtualKey {
+            $(
+                $(#[$meta])*
+                pub const $name : VirtualKey = $name;
+            )*
+        }
+
+        /// All named virtual keys.  Where several names share a code (e.g. [`VK_KANA`] / [`VK_HANGUL`]), the first is the canonical one.
+        const NAMES : &[(&str, VirtualKey)] = &[$( (stringify!($name), $name), )*];
+    };
+}
+
+virtual_keys! {
+    /// Left mouse button
+    VK_LBUTTON                         = 0x01,
+    /// Right mouse button
+    VK_RBUTTON                         = 0x02,
+    /// Control-break processing
+    VK_CANCEL                          = 0x03,
+    /// Middle mouse button
+    VK_MBUTTON                         = 0x04,
+    /// X1 mouse button
+    VK_XBUTTON1                        = 0x05,
+    /// X2 mouse button
+    VK_XBUTTON2                        = 0x06,
+    /// <kbd>Backspace</kbd>
+    VK_BACK                            = 0x08,
+    /// <kbd>Tab</kbd>
+    VK_TAB                             = 0x09,
+    /// <kbd>Clear</kbd> (<kbd>Numpad 5</kbd> without <kbd>Num Lock</kbd>)
+    VK_CLEAR                           = 0x0C,
+    /// <kbd>Enter</kbd>
+    VK_RETURN                          = 0x0D,
+    /// <kbd>Shift</kbd>
+    VK_SHIFT                           = 0x10,
+    /// <kbd>Ctrl</kbd>
+    VK_CONTROL                         = 0x11,
+    /// <kbd>Alt</kbd>
+    VK_MENU                            = 0x12,
+    /// <kbd>Pause</kbd>
+    VK_PAUSE                           = 0x13,
+    /// <kbd>Caps Lock</kbd>
+    VK_CAPITAL                         = 0x14,
+    /// IME Kana mode
+    VK_KANA                            = 0x15,
+    /// IME Hangul mode
+    VK_HANGEUL                         = 0x15,
+    /// IME Hangul mode
+    VK_HANGUL                          = 0x15,
+    /// IME On
+    VK_IME_ON                          = 0x16,
+    /// IME Junja mode
+    VK_JUNJA                           = 0x17,
+    /// IME final mode
+    VK_FINAL                           = 0x18,
+    /// IME Hanja mode
+    VK_HANJA                           = 0x19,
+    /// IME Kanji mode
+    VK_KANJI                           = 0x19,
+    /// IME Off
+    VK_IME_OFF                         = 0x1A,
+    /// <kbd>Esc</kbd>
+    VK_ESCAPE                          = 0x1B,
+    /// IME convert
+    VK_CONVERT                         = 0x1C,
+    /// IME nonconvert
+    VK_NONCONVERT                      = 0x1D,
+    /// IME accept
+    VK_ACCEPT                          = 0x1E,
+    /// IME mode change request
+    VK_MODECHANGE                      = 0x1F,
+    /// <kbd>Space</kbd>
+    VK_SPACE                           = 0x20,
+    /// <kbd>Page Up</kbd>
+    VK_PRIOR                           = 0x21,
+    /// <kbd>Page Down</kbd>
+    VK_NEXT                            = 0x22,
+    /// <kbd>End</kbd>
+    VK_END                             = 0x23,
+    /// <kbd>Home</kbd>
+    VK_HOME                            = 0x24,
+    /// <kbd>←</kbd>
+    VK_LEFT                            = 0x25,
+    /// <kbd>↑</kbd>
+    VK_UP                              = 0x26,
+    /// <kbd>→</kbd>
+    VK_RIGHT                           = 0x27,
+    /// <kbd>↓</kbd>
+    VK_DOWN                            = 0x28,
+    /// <kbd>Select</kbd>
+    VK_SELECT                          = 0x29,
+    /// <kbd>Print</kbd>
+    VK_PRINT                           = 0x2A,
+    /// <kbd>Execute</kbd>
+    VK_EXECUTE                         = 0x2B,
+    /// <kbd>Print Screen</kbd>
+    VK_SNAPSHOT                        = 0x2C,
+    /// <kbd>Insert</kbd>
+    VK_INSERT                          = 0x2D,
+    /// <kbd>Delete</kbd>
+    VK_DELETE                          = 0x2E,
+    /// <kbd>Help</kbd>
+    VK_HELP                            = 0x2F,
+    /// <kbd>0</kbd>
+    VK_0                               = 0x30,
+    /// <kbd>1</kbd>
+    VK_1                               = 0x31,
+    /// <kbd>2</kbd>
+    VK_2                               = 0x32,
+    /// <kbd>3</kbd>
+    VK_3                               = 0x33,
+    /// <kbd>4</kbd>
+    VK_4                               = 0x34,
+    /// <kbd>5</kbd>
+    VK_5                               = 0x35,
+    /// <kbd>6</kbd>
+    VK_6                               = 0x36,
+    /// <kbd>7</kbd>
+    VK_7                               = 0x37,
+    /// <kbd>8</kbd>
+    VK_8                               = 0x38,
+    /// <kbd>9</kbd>
+    VK_9                               = 0x39,
+    /// <kbd>A</kbd>
+    VK_A                               = 0x41,
+    /// <kbd>B</kbd>
+    VK_B                               = 0x42,
+    /// <kbd>C</kbd>
+    VK_C                               = 0x43,
+    /// <kbd>D</kbd>
+    VK_D                               = 0x44,
+    /// <kbd>E</kbd>
+    VK_E                               = 0x45,
+    /// <kbd>F</kbd>
+    VK_F                               = 0x46,
+    /// <kbd>G</kbd>
+    VK_G                               = 0x47,
+    /// <kbd>H</kbd>
+    VK_H                               = 0x48,
+    /// <kbd>I</kbd>
+    VK_I                               = 0x49,
+    /// <kbd>J</kbd>
+    VK_J                               = 0x4A,
+    /// <kbd>K</kbd>
+    VK_K                               = 0x4B,
+    /// <kbd>L</kbd>
+    VK_L                               = 0x4C,
+    /// <kbd>M</kbd>
+    VK_M                               = 0x4D,
+    /// <kbd>N</kbd>
+    VK_N                               = 0x4E,
+    /// <kbd>O</kbd>
+    VK_O                               = 0x4F,
+    /// <kbd>P</kbd>
+    VK_P                               = 0x50,
+    /// <kbd>Q</kbd>
+    VK_Q                               = 0x51,
+    /// <kbd>R</kbd>
+    VK_R                               = 0x52,
+    /// <kbd>S</kbd>
+    VK_S                               = 0x53,
+    /// <kbd>T</kbd>
+    VK_T                               = 0x54,
+    /// <kbd>U</kbd>
+    VK_U                               = 0x55,
+    /// <kbd>V</kbd>
+    VK_V                               = 0x56,
+    /// <kbd>W</kbd>
+    VK_W                               = 0x57,
+    /// <kbd>X</kbd>
+    VK_X                               = 0x58,
+    /// <kbd>Y</kbd>
+    VK_Y                               = 0x59,
+    /// <kbd>Z</kbd>
+    VK_Z                               = 0x5A,
+    /// Left <kbd>Windows</kbd>
+    VK_LWIN                            = 0x5B,
+    /// Right <kbd>Windows</kbd>
+    VK_RWIN                            = 0x5C,
+    /// <kbd>Applications</kbd>
+    VK_APPS                            = 0x5D,
+    /// <kbd>Sleep</kbd>
+    VK_SLEEP                           = 0x5F,
+    /// <kbd>Numpad 0</kbd>
+    VK_NUMPAD0                         = 0x60,
+    /// <kbd>Numpad 1</kbd>
+    VK_NUMPAD1                         = 0x61,
+    /// <kbd>Numpad 2</kbd>
+    VK_NUMPAD2                         = 0x62,
+    /// <kbd>Numpad 3</kbd>
+    VK_NUMPAD3                         = 0x63,
+    /// <kbd>Numpad 4</kbd>
+    VK_NUMPAD4                         = 0x64,
+    /// <kbd>Numpad 5</kbd>
+    VK_NUMPAD5                         = 0x65,
+    /// <kbd>Numpad 6</kbd>
+    VK_NUMPAD6                         = 0x66,
+    /// <kbd>Numpad 7</kbd>
+    VK_NUMPAD7                         = 0x67,
+    /// <kbd>Numpad 8</kbd>
+    VK_NUMPAD8                         = 0x68,
+    /// <kbd>Numpad 9</kbd>
+    VK_NUMPAD9                         = 0x69,
+    /// <kbd>Numpad *</kbd>
+    VK_MULTIPLY                        = 0x6A,
+    /// <kbd>Numpad +</kbd>
+    VK_ADD                             = 0x6B,
+    /// Numpad separator
+    VK_SEPARATOR                       = 0x6C,
+    /// <kbd>Numpad -</kbd>
+    VK_SUBTRACT                        = 0x6D,
+    /// <kbd>Numpad .</kbd>
+    VK_DECIMAL                         = 0x6E,
+    /// <kbd>Numpad /</kbd>
+    VK_DIVIDE                          = 0x6F,
+    /// <kbd>F1</kbd>
+    VK_F1                              = 0x70,
+    /// <kbd>F2</kbd>
+    VK_F2                              = 0x71,
+    /// <kbd>F3</kbd>
+    VK_F3                              = 0x72,
+    /// <kbd>F4</kbd>
+    VK_F4                              = 0x73,
+    /// <kbd>F5</kbd>
+    VK_F5                              = 0x74,
+    /// <kbd>F6</kbd>
+    VK_F6                              = 0x75,
+    /// <kbd>F7</kbd>
+    VK_F7                              = 0x76,
+    /// <kbd>F8</kbd>
+    VK_F8                              = 0x77,
+    /// <kbd>F9</kbd>
+    VK_F9                              = 0x78,
+    /// <kbd>F10</kbd>
+    VK_F10                             = 0x79,
+    /// <kbd>F11</kbd>
+    VK_F11                             = 0x7A,
+    /// <kbd>F12</kbd>
+    VK_F12                             = 0x7B,
+    /// <kbd>F13</kbd>
+    VK_F13                             = 0x7C,
+    /// <kbd>F14</kbd>
+    VK_F14                             = 0x7D,
+    /// <kbd>F15</kbd>
+    VK_F15                             = 0x7E,
+    /// <kbd>F16</kbd>
+    VK_F16                             = 0x7F,
+    /// <kbd>F17</kbd>
+    VK_F17                             = 0x80,
+    /// <kbd>F18</kbd>
+    VK_F18                             = 0x81,
+    /// <kbd>F19</kbd>
+    VK_F19                             = 0x82,
+    /// <kbd>F20</kbd>
+    VK_F20                             = 0x83,
+    /// <kbd>F21</kbd>
+    VK_F21                             = 0x84,
+    /// <kbd>F22</kbd>
+    VK_F22                             = 0x85,
+    /// <kbd>F23</kbd>
+    VK_F23                             = 0x86,
+    /// <kbd>F24</kbd>
+    VK_F24                             = 0x87,
+    /// Navigation View
+    VK_NAVIGATION_VIEW                 = 0x88,
+    /// Navigation Menu
+    VK_NAVIGATION_MENU                 = 0x89,
+    /// Navigation Up
+    VK_NAVIGATION_UP                   = 0x8A,
+    /// Navigation Down
+    VK_NAVIGATION_DOWN                 = 0x8B,
+    /// Navigation Left
+    VK_NAVIGATION_LEFT                 = 0x8C,
+    /// Navigation Right
+    VK_NAVIGATION_RIGHT                = 0x8D,
+    /// Navigation Accept
+    VK_NAVIGATION_ACCEPT               = 0x8E,
+    /// Navigation Cancel
+    VK_NAVIGATION_CANCEL               = 0x8F,
+    /// <kbd>Num Lock</kbd>
+    VK_NUMLOCK                         = 0x90,
+    /// <kbd>Scroll Lock</kbd>
+    VK_SCROLL                          = 0x91,
+    /// <kbd>Numpad =</kbd> (NEC PC-9800)
+    VK_OEM_NEC_EQUAL                   = 0x92,
+    /// 'Dictionary' (Fujitsu/OASYS)
+    VK_OEM_FJ_JISHO                    = 0x92,
+    /// 'Unregister word' (Fujitsu/OASYS)
+    VK_OEM_FJ_MASSHOU                  = 0x93,
+    /// 'Register word' (Fujitsu/OASYS)
+    VK_OEM_FJ_TOUROKU                  = 0x94,
+    /// 'Left OYAYUBI' (Fujitsu/OASYS)
+    VK_OEM_FJ_LOYA                     = 0x95,
+    /// 'Right OYAYUBI' (Fujitsu/OASYS)
+    VK_OEM_FJ_ROYA                     = 0x96,
+    /// Left <kbd>Shift</kbd>
+    VK_LSHIFT                          = 0xA0,
+    /// Right <kbd>Shift</kbd>
+    VK_RSHIFT                          = 0xA1,
+    /// Left <kbd>Ctrl</kbd>
+    VK_LCONTROL                        = 0xA2,
+    /// Right <kbd>Ctrl</kbd>
+    VK_RCONTROL                        = 0xA3,
+    /// Left <kbd>Alt</kbd>
+    VK_LMENU                           = 0xA4,
+    /// Right <kbd>Alt</kbd>
+    VK_RMENU                           = 0xA5,
+    /// Browser Back
+    VK_BROWSER_BACK                    = 0xA6,
+    /// Browser Forward
+    VK_BROWSER_FORWARD                 = 0xA7,
+    /// Browser Refresh
+    VK_BROWSER_REFRESH                 = 0xA8,
+    /// Browser Stop
+    VK_BROWSER_STOP                    = 0xA9,
+    /// Browser Search
+    VK_BROWSER_SEARCH                  = 0xAA,
+    /// Browser Favorites
+    VK_BROWSER_FAVORITES               = 0xAB,
+    /// Browser Start and Home
+    VK_BROWSER_HOME                    = 0xAC,
+    /// Volume Mute
+    VK_VOLUME_MUTE                     = 0xAD,
+    /// Volume Down
+    VK_VOLUME_DOWN                     = 0xAE,
+    /// Volume Up
+    VK_VOLUME_UP                       = 0xAF,
+    /// Next Track
+    VK_MEDIA_NEXT_TRACK                = 0xB0,
+    /// Previous Track
+    VK_MEDIA_PREV_TRACK                = 0xB1,
+    /// Stop Media
+    VK_MEDIA_STOP                      = 0xB2,
+    /// Play/Pause Media
+    VK_MEDIA_PLAY_PAUSE                = 0xB3,
+    /// Start Mail
+    VK_LAUNCH_MAIL                     = 0xB4,
+    /// Select Media
+    VK_LAUNCH_MEDIA_SELECT             = 0xB5,
+    /// Start Application 1
+    VK_LAUNCH_APP1                     = 0xB6,
+    /// Start Application 2
+    VK_LAUNCH_APP2                     = 0xB7,
+    /// <kbd>;</kbd> / <kbd>:</kbd> on a US keyboard
+    VK_OEM_1                           = 0xBA,
+    /// <kbd>=</kbd> / <kbd>+</kbd> on any keyboard
+    VK_OEM_PLUS                        = 0xBB,
+    /// <kbd>,</kbd> / <kbd>&lt;</kbd> on any keyboard
+    VK_OEM_COMMA                       = 0xBC,
+    /// <kbd>-</kbd> / <kbd>_</kbd> on any keyboard
+    VK_OEM_MINUS                       = 0xBD,
+    /// <kbd>.</kbd> / <kbd>&gt;</kbd> on any keyboard
+    VK_OEM_PERIOD                      = 0xBE,
+    /// <kbd>/</kbd> / <kbd>?</kbd> on a US keyboard
+    VK_OEM_2                           = 0xBF,
+    /// <kbd>`</kbd> / <kbd>~</kbd> on a US keyboard
+    VK_OEM_3                           = 0xC0,
+    /// Gamepad A
+    VK_GAMEPAD_A                       = 0xC3,
+    /// Gamepad B
+    VK_GAMEPAD_B                       = 0xC4,
+    /// Gamepad X
+    VK_GAMEPAD_X                       = 0xC5,
+    /// Gamepad Y
+    VK_GAMEPAD_Y                       = 0xC6,
+    /// Gamepad Right Shoulder
+    VK_GAMEPAD_RIGHT_SHOULDER          = 0xC7,
+    /// Gamepad Left Shoulder
+    VK_GAMEPAD_LEFT_SHOULDER           = 0xC8,
+    /// Gamepad Left Trigger
+    VK_GAMEPAD_LEFT_TRIGGER            = 0xC9,
+    /// Gamepad Right Trigger
+    VK_GAMEPAD_RIGHT_TRIGGER           = 0xCA,
+    /// Gamepad D-pad Up
+    VK_GAMEPAD_DPAD_UP                 = 0xCB,
+    /// Gamepad D-pad Down
+    VK_GAMEPAD_DPAD_DOWN               = 0xCC,
+    /// Gamepad D-pad Left
+    VK_GAMEPAD_DPAD_LEFT               = 0xCD,
+    /// Gamepad D-pad Right
+    VK_GAMEPAD_DPAD_RIGHT              = 0xCE,
+    /// Gamepad Menu
+    VK_GAMEPAD_MENU                    = 0xCF,
+    /// Gamepad View
+    VK_GAMEPAD_VIEW                    = 0xD0,
+    /// Gamepad Left Thumbstick Button
+    VK_GAMEPAD_LEFT_THUMBSTICK_BUTTON  = 0xD1,
+    /// Gamepad Right Thumbstick Button
+    VK_GAMEPAD_RIGHT_THUMBSTICK_BUTTON = 0xD2,
+    /// Gamepad Left Thumbstick Up
+    VK_GAMEPAD_LEFT_THUMBSTICK_UP      = 0xD3,
+    /// Gamepad Left Thumbstick Down
+    VK_GAMEPAD_LEFT_THUMBSTICK_DOWN    = 0xD4,
+    /// Gamepad Left Thumbstick Right
+    VK_GAMEPAD_LEFT_THUMBSTICK_RIGHT   = 0xD5,
+    /// Gamepad Left Thumbstick Left
+    VK_GAMEPAD_LEFT_THUMBSTICK_LEFT    = 0xD6,
+    /// Gamepad Right Thumbstick Up
+    VK_GAMEPAD_RIGHT_THUMBSTICK_UP     = 0xD7,
+    /// Gamepad Right Thumbstick Down
+    VK_GAMEPAD_RIGHT_THUMBSTICK_DOWN   = 0xD8,
+    /// Gamepad Right Thumbstick Right
+    VK_GAMEPAD_RIGHT_THUMBSTICK_RIGHT  = 0xD9,
+    /// Gamepad Right Thumbstick Left
+    VK_GAMEPAD_RIGHT_THUMBSTICK_LEFT   = 0xDA,
+    /// <kbd>[</kbd> / <kbd>{</kbd> on a US keyboard
+    VK_OEM_4                           = 0xDB,
+    /// <kbd>\\</kbd> / <kbd>|</kbd> on a US keyboard
+    VK_OEM_5                           = 0xDC,
+    /// <kbd>]</kbd> / <kbd>}</kbd> on a US keyboard
+    VK_OEM_6                           = 0xDD,
+    /// <kbd>'</kbd> / <kbd>"</kbd> on a US keyboard
+    VK_OEM_7                           = 0xDE,
+    /// Miscellaneous (varies by keyboard)
+    VK_OEM_8                           = 0xDF,
+    /// <kbd>AX</kbd> (Japanese AX keyboard)
+    VK_OEM_AX                          = 0xE1,
+    /// <kbd>&lt;</kbd> / <kbd>&gt;</kbd> or <kbd>\\</kbd> / <kbd>|</kbd> on a 102-key keyboard
+    VK_OEM_102                         = 0xE2,
+    /// <kbd>Help</kbd> (Olivetti ICO)
+    VK_ICO_HELP                        = 0xE3,
+    /// <kbd>00</kbd> (Olivetti ICO)
+    VK_ICO_00                          = 0xE4,
+    /// IME PROCESS
+    VK_PROCESSKEY                      = 0xE5,
+    /// <kbd>Clear</kbd> (Olivetti ICO)
+    VK_ICO_CLEAR                       = 0xE6,
+    /// Unicode characters without a key (see [`KeyEventRecord::char`])
+    VK_PACKET                          = 0xE7,
+    /// OEM specific (Nokia/Ericsson: Reset)
+    VK_OEM_RESET                       = 0xE9,
+    /// OEM specific (Nokia/Ericsson: Jump)
+    VK_OEM_JUMP                        = 0xEA,
+    /// OEM specific (Nokia/Ericsson: PA1)
+    VK_OEM_PA1                         = 0xEB,
+    /// OEM specific (Nokia/Ericsson: PA2)
+    VK_OEM_PA2                         = 0xEC,
+    /// OEM specific (Nokia/Ericsson: PA3)
+    VK_OEM_PA3                         = 0xED,
+    /// OEM specific (Nokia/Ericsson: WsCtrl)
+    VK_OEM_WSCTRL                      = 0xEE,
+    /// OEM specific (Nokia/Ericsson: CuSel)
+    VK_OEM_CUSEL                       = 0xEF,
+    /// OEM specific (Nokia/Ericsson: Attn)
+    VK_OEM_ATTN                        = 0xF0,
+    /// OEM specific (Nokia/Ericsson: Finish)
+    VK_OEM_FINISH                      = 0xF1,
+    /// OEM specific (Nokia/Ericsson: Copy)
+    VK_OEM_COPY                        = 0xF2,
+    /// OEM specific (Nokia/Ericsson: Auto)
+    VK_OEM_AUTO                        = 0xF3,
+    /// OEM specific (Nokia/Ericsson: EnlW)
+    VK_OEM_ENLW                        = 0xF4,
+    /// OEM specific (Nokia/Ericsson: BackTab)
+    VK_OEM_BACKTAB                     = 0xF5,
+    /// <kbd>Attn</kbd>
+    VK_ATTN                            = 0xF6,
+    /// <kbd>CrSel</kbd>
+    VK_CRSEL                           = 0xF7,
+    /// <kbd>ExSel</kbd>
+    VK_EXSEL                           = 0xF8,
+    /// <kbd>Erase EOF</kbd>
+    VK_EREOF                           = 0xF9,
+    /// <kbd>Play</kbd>
+    VK_PLAY                            = 0xFA,
+    /// <kbd>Zoom</kbd>
+    VK_ZOOM                            = 0xFB,
+    /// Reserved
+    VK_NONAME                          = 0xFC,
+    /// <kbd>PA1</kbd>
+    VK_PA1                             = 0xFD,
+    /// <kbd>Clear</kbd>
+    VK_OEM_CLEAR                       = 0xFE,
+}
+
+#[cfg(windows)] const _ : () = {
+    use winapi::um::winuser;
+    assert!(VK_LBUTTON.0                         == winuser::VK_LBUTTON                         as u16);
+    assert!(VK_RBUTTON.0                         == winuser::VK_RBUTTON                         as u16);
+    assert!(VK_CANCEL.0                          == winuser::VK_CANCEL                          as u16);
+    assert!(VK_MBUTTON.0                         == winuser::VK_MBUTTON                         as u16);
+    assert!(VK_XBUTTON1.0                        == winuser::VK_XBUTTON1                        as u16);
+    assert!(VK_XBUTTON2.0                        == winuser::VK_XBUTTON2                        as u16);
+    assert!(VK_BACK.0                            == winuser::VK_BACK                            as u16);
+    assert!(VK_TAB.0                             == winuser::VK_TAB                             as u16);
+    assert!(VK_CLEAR.0                           == winuser::VK_CLEAR                           as u16);
+    assert!(VK_RETURN.0                          == winuser::VK_RETURN                          as u16);
+    assert!(VK_SHIFT.0                           == winuser::VK_SHIFT                           as u16);
+    assert!(VK_CONTROL.0                         == winuser::VK_CONTROL                         as u16);
+    assert!(VK_MENU.0                            == winuser::VK_MENU                            as u16);
+    assert!(VK_PAUSE.0                           == winuser::VK_PAUSE                           as u16);
+    assert!(VK_CAPITAL.0                         == winuser::VK_CAPITAL                         as u16);
+    assert!(VK_KANA.0                            == winuser::VK_KANA                            as u16);
+    assert!(VK_HANGEUL.0                         == winuser::VK_HANGEUL                         as u16);
+    assert!(VK_HANGUL.0                          == winuser::VK_HANGUL                          as u16);
+    assert!(VK_JUNJA.0                           == winuser::VK_JUNJA                           as u16);
+    assert!(VK_FINAL.0                           == winuser::VK_FINAL                           as u16);
+    assert!(VK_HANJA.0                           == winuser::VK_HANJA                           as u16);
+    assert!(VK_KANJI.0                           == winuser::VK_KANJI                           as u16);
+    assert!(VK_ESCAPE.0                          == winuser::VK_ESCAPE                          as u16);
+    assert!(VK_CONVERT.0                         == winuser::VK_CONVERT                         as u16);
+    assert!(VK_NONCONVERT.0                      == winuser::VK_NONCONVERT                      as u16);
+    assert!(VK_ACCEPT.0                          == winuser::VK_ACCEPT                          as u16);
+    assert!(VK_MODECHANGE.0                      == winuser::VK_MODECHANGE                      as u16);
+    assert!(VK_SPACE.0                           == winuser::VK_SPACE                           as u16);
+    assert!(VK_PRIOR.0                           == winuser::VK_PRIOR                           as u16);
+    assert!(VK_NEXT.0                            == winuser::VK_NEXT                            as u16);
+    assert!(VK_END.0                             == winuser::VK_END                             as u16);
+    assert!(VK_HOME.0                            == winuser::VK_HOME                            as u16);
+    assert!(VK_LEFT.0                            == winuser::VK_LEFT                            as u16);
+    assert!(VK_UP.0                              == winuser::VK_UP                              as u16);
+    assert!(VK_RIGHT.0                           == winuser::VK_RIGHT                           as u16);
+    assert!(VK_DOWN.0                            == winuser::VK_DOWN                            as u16);
+    assert!(VK_SELECT.0                          == winuser::VK_SELECT                          as u16);
+    assert!(VK_PRINT.0                           == winuser::VK_PRINT                           as u16);
+    assert!(VK_EXECUTE.0                         == winuser::VK_EXECUTE                         as u16);
+    assert!(VK_SNAPSHOT.0                        == winuser::VK_SNAPSHOT                        as u16);
+    assert!(VK_INSERT.0                          == winuser::VK_INSERT                          as u16);
+    assert!(VK_DELETE.0                          == winuser::VK_DELETE                          as u16);
+    assert!(VK_HELP.0                            == winuser::VK_HELP                            as u16);
+    assert!(VK_LWIN.0                            == winuser::VK_LWIN                            as u16);
+    assert!(VK_RWIN.0                            == winuser::VK_RWIN                            as u16);
+    assert!(VK_APPS.0                            == winuser::VK_APPS                            as u16);
+    assert!(VK_SLEEP.0                           == winuser::VK_SLEEP                           as u16);
+    assert!(VK_NUMPAD0.0                         == winuser::VK_NUMPAD0                         as u16);
+    assert!(VK_NUMPAD1.0                         == winuser::VK_NUMPAD1                         as u16);
+    assert!(VK_NUMPAD2.0                         == winuser::VK_NUMPAD2                         as u16);
+    assert!(VK_NUMPAD3.0                         == winuser::VK_NUMPAD3                         as u16);
+    assert!(VK_NUMPAD4.0                         == winuser::VK_NUMPAD4                         as u16);
+    assert!(VK_NUMPAD5.0                         == winuser::VK_NUMPAD5                         as u16);
+    assert!(VK_NUMPAD6.0                         == winuser::VK_NUMPAD6                         as u16);
+    assert!(VK_NUMPAD7.0                         == winuser::VK_NUMPAD7                         as u16);
+    assert!(VK_NUMPAD8.0                         == winuser::VK_NUMPAD8                         as u16);
+    assert!(VK_NUMPAD9.0                         == winuser::VK_NUMPAD9                         as u16);
+    assert!(VK_MULTIPLY.0                        == winuser::VK_MULTIPLY                        as u16);
+    assert!(VK_ADD.0                             == winuser::VK_ADD                             as u16);
+    assert!(VK_SEPARATOR.0                       == winuser::VK_SEPARATOR                       as u16);
+    assert!(VK_SUBTRACT.0                        == winuser::VK_SUBTRACT                        as u16);
+    assert!(VK_DECIMAL.0                         == winuser::VK_DECIMAL                         as u16);
+    assert!(VK_DIVIDE.0                          == winuser::VK_DIVIDE                          as u16);
+    assert!(VK_F1.0                              == winuser::VK_F1                              as u16);
+    assert!(VK_F2.0                              == winuser::VK_F2                              as u16);
+    assert!(VK_F3.0                              == winuser::VK_F3                              as u16);
+    assert!(VK_F4.0                              == winuser::VK_F4                              as u16);
+    assert!(VK_F5.0                              == winuser::VK_F5                              as u16);
+    assert!(VK_F6.0                              == winuser::VK_F6                              as u16);
+    assert!(VK_F7.0                              == winuser::VK_F7                              as u16);
+    assert!(VK_F8.0                              == winuser::VK_F8                              as u16);
+    assert!(VK_F9.0                              == winuser::VK_F9                              as u16);
+    assert!(VK_F10.0                             == winuser::VK_F10                             as u16);
+    assert!(VK_F11.0                             == winuser::VK_F11                             as u16);
+    assert!(VK_F12.0                             == winuser::VK_F12                             as u16);
+    assert!(VK_F13.0                             == winuser::VK_F13                             as u16);
+    assert!(VK_F14.0                             == winuser::VK_F14                             as u16);
+    assert!(VK_F15.0                             == winuser::VK_F15                             as u16);
+    assert!(VK_F16.0                             == winuser::VK_F16                             as u16);
+    assert!(VK_F17.0                             == winuser::VK_F17                             as u16);
+    assert!(VK_F18.0                             == winuser::VK_F18                             as u16);
+    assert!(VK_F19.0                             == winuser::VK_F19                             as u16);
+    assert!(VK_F20.0                             == winuser::VK_F20                             as u16);
+    assert!(VK_F21.0                             == winuser::VK_F21                             as u16);
+    assert!(VK_F22.0                             == winuser::VK_F22                             as u16);
+    assert!(VK_F23.0                             == winuser::VK_F23                             as u16);
+    assert!(VK_F24.0                             == winuser::VK_F24                             as u16);
+    assert!(VK_NAVIGATION_VIEW.0                 == winuser::VK_NAVIGATION_VIEW                 as u16);
+    assert!(VK_NAVIGATION_MENU.0                 == winuser::VK_NAVIGATION_MENU                 as u16);
+    assert!(VK_NAVIGATION_UP.0                   == winuser::VK_NAVIGATION_UP                   as u16);
+    assert!(VK_NAVIGATION_DOWN.0                 == winuser::VK_NAVIGATION_DOWN                 as u16);
+    assert!(VK_NAVIGATION_LEFT.0                 == winuser::VK_NAVIGATION_LEFT                 as u16);
+    assert!(VK_NAVIGATION_RIGHT.0                == winuser::VK_NAVIGATION_RIGHT                as u16);
+    assert!(VK_NAVIGATION_ACCEPT.0               == winuser::VK_NAVIGATION_ACCEPT               as u16);
+    assert!(VK_NAVIGATION_CANCEL.0               == winuser::VK_NAVIGATION_CANCEL               as u16);
+    assert!(VK_NUMLOCK.0                         == winuser::VK_NUMLOCK                         as u16);
+    assert!(VK_SCROLL.0                          == winuser::VK_SCROLL                          as u16);
+    assert!(VK_OEM_NEC_EQUAL.0                   == winuser::VK_OEM_NEC_EQUAL                   as u16);
+    assert!(VK_OEM_FJ_JISHO.0                    == winuser::VK_OEM_FJ_JISHO                    as u16);
+    assert!(VK_OEM_FJ_MASSHOU.0                  == winuser::VK_OEM_FJ_MASSHOU                  as u16);
+    assert!(VK_OEM_FJ_TOUROKU.0                  == winuser::VK_OEM_FJ_TOUROKU                  as u16);
+    assert!(VK_OEM_FJ_LOYA.0                     == winuser::VK_OEM_FJ_LOYA                     as u16);
+    assert!(VK_OEM_FJ_ROYA.0                     == winuser::VK_OEM_FJ_ROYA                     as u16);
+    assert!(VK_LSHIFT.0                          == winuser::VK_LSHIFT                          as u16);
+    assert!(VK_RSHIFT.0                          == winuser::VK_RSHIFT                          as u16);
+    assert!(VK_LCONTROL.0                        == winuser::VK_LCONTROL                        as u16);
+    assert!(VK_RCONTROL.0                        == winuser::VK_RCONTROL                        as u16);
+    assert!(VK_LMENU.0                           == winuser::VK_LMENU                           as u16);
+    assert!(VK_RMENU.0                           == winuser::VK_RMENU                           as u16);
+    assert!(VK_BROWSER_BACK.0                    == winuser::VK_BROWSER_BACK                    as u16);
+    assert!(VK_BROWSER_FORWARD.0                 == winuser::VK_BROWSER_FORWARD                 as u16);
+    assert!(VK_BROWSER_REFRESH.0                 == winuser::VK_BROWSER_REFRESH                 as u16);
+    assert!(VK_BROWSER_STOP.0                    == winuser::VK_BROWSER_STOP                    as u16);
+    assert!(VK_BROWSER_SEARCH.0                  == winuser::VK_BROWSER_SEARCH                  as u16);
+    assert!(VK_BROWSER_FAVORITES.0               == winuser::VK_BROWSER_FAVORITES               as u16);
+    assert!(VK_BROWSER_HOME.0                    == winuser::VK_BROWSER_HOME                    as u16);
+    assert!(VK_VOLUME_MUTE.0                     == winuser::VK_VOLUME_MUTE                     as u16);
+    assert!(VK_VOLUME_DOWN.0                     == winuser::VK_VOLUME_DOWN                     as u16);
+    assert!(VK_VOLUME_UP.0                       == winuser::VK_VOLUME_UP                       as u16);
+    assert!(VK_MEDIA_NEXT_TRACK.0                == winuser::VK_MEDIA_NEXT_TRACK                as u16);
+    assert!(VK_MEDIA_PREV_TRACK.0                == winuser::VK_MEDIA_PREV_TRACK                as u16);
+    assert!(VK_MEDIA_STOP.0                      == winuser::VK_MEDIA_STOP                      as u16);
+    assert!(VK_MEDIA_PLAY_PAUSE.0                == winuser::VK_MEDIA_PLAY_PAUSE                as u16);
+    assert!(VK_LAUNCH_MAIL.0                     == winuser::VK_LAUNCH_MAIL                     as u16);
+    assert!(VK_LAUNCH_MEDIA_SELECT.0             == winuser::VK_LAUNCH_MEDIA_SELECT             as u16);
+    assert!(VK_LAUNCH_APP1.0                     == winuser::VK_LAUNCH_APP1                     as u16);
+    assert!(VK_LAUNCH_APP2.0                     == winuser::VK_LAUNCH_APP2                     as u16);
+    assert!(VK_OEM_1.0                           == winuser::VK_OEM_1                           as u16);
+    assert!(VK_OEM_PLUS.0                        == winuser::VK_OEM_PLUS                        as u16);
+    assert!(VK_OEM_COMMA.0                       == winuser::VK_OEM_COMMA                       as u16);
+    assert!(VK_OEM_MINUS.0                       == winuser::VK_OEM_MINUS                       as u16);
+    assert!(VK_OEM_PERIOD.0                      == winuser::VK_OEM_PERIOD                      as u16);
+    assert!(VK_OEM_2.0                           == winuser::VK_OEM_2                           as u16);
+    assert!(VK_OEM_3.0                           == winuser::VK_OEM_3                           as u16);
+    assert!(VK_GAMEPAD_A.0                       == winuser::VK_GAMEPAD_A                       as u16);
+    assert!(VK_GAMEPAD_B.0                       == winuser::VK_GAMEPAD_B                       as u16);
+    assert!(VK_GAMEPAD_X.0                       == winuser::VK_GAMEPAD_X                       as u16);
+    assert!(VK_GAMEPAD_Y.0                       == winuser::VK_GAMEPAD_Y                       as u16);
+    assert!(VK_GAMEPAD_RIGHT_SHOULDER.0          == winuser::VK_GAMEPAD_RIGHT_SHOULDER          as u16);
+    assert!(VK_GAMEPAD_LEFT_SHOULDER.0           == winuser::VK_GAMEPAD_LEFT_SHOULDER           as u16);
+    assert!(VK_GAMEPAD_LEFT_TRIGGER.0            == winuser::VK_GAMEPAD_LEFT_TRIGGER            as u16);
+    assert!(VK_GAMEPAD_RIGHT_TRIGGER.0           == winuser::VK_GAMEPAD_RIGHT_TRIGGER           as u16);
+    assert!(VK_GAMEPAD_DPAD_UP.0                 == winuser::VK_GAMEPAD_DPAD_UP                 as u16);
+    assert!(VK_GAMEPAD_DPAD_DOWN.0               == winuser::VK_GAMEPAD_DPAD_DOWN               as u16);
+    assert!(VK_GAMEPAD_DPAD_LEFT.0               == winuser::VK_GAMEPAD_DPAD_LEFT               as u16);
+    assert!(VK_GAMEPAD_DPAD_RIGHT.0              == winuser::VK_GAMEPAD_DPAD_RIGHT              as u16);
+    assert!(VK_GAMEPAD_MENU.0                    == winuser::VK_GAMEPAD_MENU                    as u16);
+    assert!(VK_GAMEPAD_VIEW.0                    == winuser::VK_GAMEPAD_VIEW                    as u16);
+    assert!(VK_GAMEPAD_LEFT_THUMBSTICK_BUTTON.0  == winuser::VK_GAMEPAD_LEFT_THUMBSTICK_BUTTON  as u16);
+    assert!(VK_GAMEPAD_RIGHT_THUMBSTICK_BUTTON.0 == winuser::VK_GAMEPAD_RIGHT_THUMBSTICK_BUTTON as u16);
+    assert!(VK_GAMEPAD_LEFT_THUMBSTICK_UP.0      == winuser::VK_GAMEPAD_LEFT_THUMBSTICK_UP      as u16);
+    assert!(VK_GAMEPAD_LEFT_THUMBSTICK_DOWN.0    == winuser::VK_GAMEPAD_LEFT_THUMBSTICK_DOWN    as u16);
+    assert!(VK_GAMEPAD_LEFT_THUMBSTICK_RIGHT.0   == winuser::VK_GAMEPAD_LEFT_THUMBSTICK_RIGHT   as u16);
+    assert!(VK_GAMEPAD_LEFT_THUMBSTICK_LEFT.0    == winuser::VK_GAMEPAD_LEFT_THUMBSTICK_LEFT    as u16);
+    assert!(VK_GAMEPAD_RIGHT_THUMBSTICK_UP.0     == winuser::VK_GAMEPAD_RIGHT_THUMBSTICK_UP     as u16);
+    assert!(VK_GAMEPAD_RIGHT_THUMBSTICK_DOWN.0   == winuser::VK_GAMEPAD_RIGHT_THUMBSTICK_DOWN   as u16);
+    assert!(VK_GAMEPAD_RIGHT_THUMBSTICK_RIGHT.0  == winuser::VK_GAMEPAD_RIGHT_THUMBSTICK_RIGHT  as u16);
+    assert!(VK_GAMEPAD_RIGHT_THUMBSTICK_LEFT.0   == winuser::VK_GAMEPAD_RIGHT_THUMBSTICK_LEFT   as u16);
+    assert!(VK_OEM_4.0                           == winuser::VK_OEM_4                           as u16);
+    assert!(VK_OEM_5.0                           == winuser::VK_OEM_5                           as u16);
+    assert!(VK_OEM_6.0                           == winuser::VK_OEM_6                           as u16);
+    assert!(VK_OEM_7.0                           == winuser::VK_OEM_7                           as u16);
+    assert!(VK_OEM_8.0                           == winuser::VK_OEM_8                           as u16);
+    assert!(VK_OEM_AX.0                          == winuser::VK_OEM_AX                          as u16);
+    assert!(VK_OEM_102.0                         == winuser::VK_OEM_102                         as u16);
+    assert!(VK_ICO_HELP.0                        == winuser::VK_ICO_HELP                        as u16);
+    assert!(VK_ICO_00.0                          == winuser::VK_ICO_00                          as u16);
+    assert!(VK_PROCESSKEY.0                      == winuser::VK_PROCESSKEY                      as u16);
+    assert!(VK_ICO_CLEAR.0                       == winuser::VK_ICO_CLEAR                       as u16);
+    assert!(VK_PACKET.0                          == winuser::VK_PACKET                          as u16);
+    assert!(VK_OEM_RESET.0                       == winuser::VK_OEM_RESET                       as u16);
+    assert!(VK_OEM_JUMP.0                        == winuser::VK_OEM_JUMP                        as u16);
+    assert!(VK_OEM_PA1.0                         == winuser::VK_OEM_PA1                         as u16);
+    assert!(VK_OEM_PA2.0                         == winuser::VK_OEM_PA2                         as u16);
+    assert!(VK_OEM_PA3.0                         == winuser::VK_OEM_PA3                         as u16);
+    assert!(VK_OEM_WSCTRL.0                      == winuser::VK_OEM_WSCTRL                      as u16);
+    assert!(VK_OEM_CUSEL.0                       == winuser::VK_OEM_CUSEL                       as u16);
+    assert!(VK_OEM_ATTN.0                        == winuser::VK_OEM_ATTN                        as u16);
+    assert!(VK_OEM_FINISH.0                      == winuser::VK_OEM_FINISH                      as u16);
+    assert!(VK_OEM_COPY.0                        == winuser::VK_OEM_COPY                        as u16);
+    assert!(VK_OEM_AUTO.0                        == winuser::VK_OEM_AUTO                        as u16);
+    assert!(VK_OEM_ENLW.0                        == winuser::VK_OEM_ENLW                        as u16);
+    assert!(VK_OEM_BACKTAB.0                     == winuser::VK_OEM_BACKTAB                     as u16);
+    assert!(VK_ATTN.0                            == winuser::VK_ATTN                            as u16);
+    assert!(VK_CRSEL.0                           == winuser::VK_CRSEL                           as u16);
+    assert!(VK_EXSEL.0                           == winuser::VK_EXSEL                           as u16);
+    assert!(VK_EREOF.0                           == winuser::VK_EREOF                           as u16);
+    assert!(VK_PLAY.0                            == winuser::VK_PLAY                            as u16);
+    assert!(VK_ZOOM.0                            == winuser::VK_ZOOM                            as u16);
+    assert!(VK_NONAME.0                          == winuser::VK_NONAME                          as u16);
+    assert!(VK_PA1.0                             == winuser::VK_PA1                             as u16);
+    assert!(VK_OEM_CLEAR.0                       == winuser::VK_OEM_CLEAR                       as u16);
+};
+
+impl VirtualKey {
+    /// The `VK_*` name of `self` (e.g. `"VK_F5"`), if it has one.
+    pub fn name(self) -> Option<&'static str> { NAMES.iter().find(|(_, vk)| *vk == self).map(|(name, _)| *name) }
+
+    /// Returns `true` for <kbd>Shift</kbd>, <kbd>Ctrl</kbd>, <kbd>Alt</kbd>, and <kbd>Windows</kbd>, including their left/right variants.
+    /// Lock keys ([`VK_CAPITAL`](Self::VK_CAPITAL), [`VK_NUMLOCK`](Self::VK_NUMLOCK), [`VK_SCROLL`](Self::VK_SCROLL)) are not modifiers.
+    pub const fn is_modifier(self) -> bool {
+        matches!(self, VK_SHIFT | VK_CONTROL | VK_MENU | VK_LSHIFT | VK_RSHIFT | VK_LCONTROL | VK_RCONTROL | VK_LMENU | VK_RMENU | VK_LWIN | VK_RWIN)
+    }
+
+    /// Returns `true` for [`VK_F1`](Self::VK_F1) ..= [`VK_F24`](Self::VK_F24).
+    pub const fn is_function_key(self) -> bool { VK_F1.0 <= self.0 && self.0 <= VK_F24.0 }
+
+    /// Returns `true` for keys which only exist on the numeric keypad: [`VK_NUMPAD0`](Self::VK_NUMPAD0) ..= [`VK_NUMPAD9`](Self::VK_NUMPAD9) and [`VK_MULTIPLY`](Self::VK_MULTIPLY) ..= [`VK_DIVIDE`](Self::VK_DIVIDE).
+    ///
+    /// Keypad keys which share a code with another key (<kbd>Enter</kbd>, or the cursor keys without <kbd>Num Lock</kbd>) can only be told apart by [`ENHANCED_KEY`](KeyEventRecord::control_key_state).
+    pub const fn is_numpad(self) -> bool { VK_NUMPAD0.0 <= self.0 && self.0 <= VK_DIVIDE.0 }
+}
+
+impl Debug for VirtualKey {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        match self.name() {
+            Some(name)  => write!(fmt, "{name}"),
+            None        => write!(fmt, "VK_??? (0x{:02x})", self.0),
+        }
+    }
+}
+
+/// Formats as the name without its `VK_` prefix (e.g. `F5`, `OEM_PLUS`), or as hex (e.g. `0x07`) for unnamed codes.
+impl Display for VirtualKey {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        match self.name() {
+            Some(name)  => write!(fmt, "{}", &name[3..]),
+            None        => write!(fmt, "0x{:02X}", self.0),
+        }
+    }
+}
+
+/// Parses a name with or without its `VK_` prefix (e.g. `F5`, `VK_OEM_PLUS`, case insensitive), or a hex code (e.g. `0x07`.)
+impl FromStr for VirtualKey {
+    type Err = io::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            return u16::from_str_radix(hex, 16).map(Self).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "VirtualKey::from_str(): invalid hex virtual key code"));
+        }
+        let name = match s.get(..3) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("VK_") => &s[3..],
+            _ => s,
+        };
+        NAMES.iter().find(|(n, _)| n[3..].eq_ignore_ascii_case(name)).map(|(_, vk)| *vk)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "VirtualKey::from_str(): unrecognized virtual key name"))
+    }
+}
+
+
+
+#[test] fn names() {
+    for &(name, vk) in NAMES {
+        assert_eq!(name.parse::<VirtualKey>().unwrap(), vk);
+        assert_eq!(vk.to_string().parse::<VirtualKey>().unwrap(), vk);
+    }
+    assert_eq!(format!("{:?}", VK_HANGUL), "VK_KANA");
+    assert_eq!(format!("{:?} {}", VirtualKey(0x07), VirtualKey(0x07)), "VK_??? (0x07) 0x07");
+    assert_eq!("0x07".parse::<VirtualKey>().unwrap(), VirtualKey(0x07));
+    assert!("F25".parse::<VirtualKey>().is_err());
+    assert!("".parse::<VirtualKey>().is_err());
+}
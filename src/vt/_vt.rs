@@ -0,0 +1,7 @@
+//! \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/console-virtual-terminal-sequences)\]
+
+mod input_decoder;                      pub use input_decoder::*;
+mod input_encoder;                      pub use input_encoder::*;
+mod keys;
+mod parser;                             pub use parser::*;
+mod sgr;
@@ -0,0 +1,403 @@
+use crate::*;
+use super::keys::*;
+
+
+
+/// The longest sequence [`VtInputDecoder`] will buffer while waiting for it to complete.  Longer sequences are discarded.
+const MAX_SEQUENCE : usize = 32;
+
+
+
+#[doc(alias = "ENABLE_VIRTUAL_TERMINAL_INPUT")]
+/// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/console-virtual-terminal-sequences#input-sequences)\]
+/// A streaming decoder which turns VT input bytes (as read with [`ENABLE_VIRTUAL_TERMINAL_INPUT`], or from a pipe) back into [`KeyEventRecord`]s.
+///
+/// Recognizes:
+/// *   UTF-8 text, including control characters (<kbd>Ctrl</kbd>+<kbd>A</kbd> = `0x01`, <kbd>Backspace</kbd> = `0x7F`, ...)
+/// *   `ESC` followed by a key, as <kbd>Alt</kbd>+that key: `ESC x` is <kbd>Alt</kbd>+<kbd>X</kbd>, `ESC ESC` is <kbd>Alt</kbd>+<kbd>Esc</kbd> (as [`KeyEventRecord::to_vt_input`] encodes it),
+///     and `ESC ESC[A` is <kbd>Alt</kbd>+<kbd>↑</kbd>.  `ESC ESC x` can't add <kbd>Alt</kbd> twice, so it's <kbd>Esc</kbd> followed by <kbd>Alt</kbd>+<kbd>X</kbd>
+///     (so an encoded <kbd>Alt</kbd>+<kbd>Esc</kbd> only decodes as such when no other key immediately follows it.)
+/// *   CSI (`ESC[A`, `ESC[1;5C`, `ESC[15;2~`, `ESC[Z`) and SS3 (`ESC OA`, `ESC OP`) cursor, editing, and function keys, with xterm modifier parameters
+/// *   conhost's win32-input-mode (`ESC[Vk;Sc;Uc;Kd;Cs;Rc_`), which carries a complete [`KeyEventRecord`]
+///
+/// Each key is emitted as a key down [`InputRecord`] followed by a key up [`InputRecord`], as a console would report it.
+/// Virtual key codes assume a US keyboard layout, and virtual scan codes are `0` (except for win32-input-mode.)
+/// Unrecognized sequences are discarded.
+///
+/// ### Lone `ESC`
+/// A lone `ESC` byte is ambiguous: it's either <kbd>Esc</kbd>, or the start of a sequence whose remaining bytes haven't arrived yet.
+/// The decoder never guesses - it holds incomplete sequences until more input arrives, or until the caller [`flush`](Self::flush)es them
+/// (typically after a short timeout without new input, while [`is_pending`](Self::is_pending).)
+///
+/// ### Example
+/// ```
+/// # use maulingmonkey_console_winapi_wrappers::*;
+/// let mut decoder = VtInputDecoder::new();
+/// let mut keys = Vec::new();
+/// let mut on_record = |record: InputRecord| if let Ok(key) = <&KeyEventRecord>::try_from(&record) {
+///     if bool::from(key.key_down) { keys.push((key.virtual_key_code, key.char.unicode_char(), key.control_key_state)) }
+/// };
+///
+/// decoder.decode(b"a\x1B[1;5", &mut on_record);
+/// assert!(decoder.is_pending());
+/// decoder.decode(b"C\x1B", &mut on_record);
+/// assert!(decoder.is_pending()); // Esc, or the start of another sequence?
+/// decoder.flush(&mut on_record); // ...no more input arrived in time: Esc
+///
+/// assert_eq!(keys, [
+///     (VK_A,      b'a' as u16, ControlKeyState::default()),
+///     (VK_RIGHT,  0,           LEFT_CTRL_PRESSED | ENHANCED_KEY),
+///     (VK_ESCAPE, 0x1B,        ControlKeyState::default()),
+/// ]);
+/// ```
+///
+#[derive(Clone, Debug, Default)]
+pub struct VtInputDecoder {
+    pending:    [u8; MAX_SEQUENCE],
+    len:        usize,
+}
+
+enum Parsed {
+    /// `.0` bytes were consumed, producing a key
+    Key(usize, Key),
+    /// `.0` bytes were consumed, producing a key from win32-input-mode
+    Record(usize, KeyEventRecord),
+    /// `.0` bytes were consumed, producing nothing
+    Discard(usize),
+    /// More bytes are required
+    Incomplete,
+}
+
+#[derive(Clone, Copy)]
+struct Key { vk: VirtualKey, ch: char, state: ControlKeyState }
+
+impl VtInputDecoder {
+    /// Creates a decoder with nothing pending.
+    pub const fn new() -> Self { Self { pending: [0; MAX_SEQUENCE], len: 0 } }
+
+    /// Returns `true` if bytes of an incomplete sequence (or a lone `ESC`) are buffered, awaiting more input or a [`flush`](Self::flush).
+    pub fn is_pending(&self) -> bool { self.len > 0 }
+
+    /// Decodes `bytes`, invoking `on_record` for each resulting key event.  Incomplete sequences at the end of `bytes` are buffered.
+    pub fn decode(&mut self, bytes: &[u8], mut on_record: impl FnMut(InputRecord)) {
+        for &b in bytes {
+            if self.len == MAX_SEQUENCE { self.len = 0; } // overlong sequence: discard
+            self.pending[self.len] = b;
+            self.len += 1;
+            self.process(false, &mut on_record);
+        }
+    }
+
+    /// Resolves any buffered incomplete sequence as literal keys: a lone `ESC` becomes <kbd>Esc</kbd>, `ESC [` becomes <kbd>Alt</kbd>+<kbd>[</kbd>,
+    /// and a partial UTF-8 character becomes U+FFFD.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let mut decoder = VtInputDecoder::new();
+    /// let mut n = 0;
+    /// decoder.decode(b"\x1B", |_| n += 1);
+    /// assert_eq!(n, 0);
+    /// decoder.flush(|_| n += 1);
+    /// assert_eq!(n, 2); // Esc down + up
+    /// assert!(!decoder.is_pending());
+    /// ```
+    ///
+    pub fn flush(&mut self, mut on_record: impl FnMut(InputRecord)) {
+        self.process(true, &mut on_record);
+    }
+
+    fn process(&mut self, flush: bool, on_record: &mut impl FnMut(InputRecord)) {
+        while self.len > 0 {
+            let pending = &self.pending[.. self.len];
+            let consumed = match parse(pending) {
+                Parsed::Key(n, key)         => { emit(key, on_record); n },
+                Parsed::Record(n, record)   => { emit_record(record, on_record); n },
+                Parsed::Discard(n)          => n,
+                Parsed::Incomplete if !flush => return,
+                Parsed::Incomplete          => {
+                    if pending[0] == 0x1B {
+                        match pending.get(1) {
+                            Some(&b @ (b'[' | b'O')) => { emit(with_alt(char_key(char::from(b))), on_record); 2 },
+                            Some(0x1B) if pending.len() == 2 => { emit(with_alt(Key { vk: VK_ESCAPE, ch: '\x1B', state: ControlKeyState::default() }), on_record); 2 },
+                            _ => { emit(Key { vk: VK_ESCAPE, ch: '\x1B', state: ControlKeyState::default() }, on_record); 1 },
+                        }
+                    } else {
+                        emit(char_key(char::REPLACEMENT_CHARACTER), on_record);
+                        self.len // partial UTF-8
+                    }
+                },
+            };
+            self.pending.copy_within(consumed .. self.len, 0);
+            self.len -= consumed;
+        }
+    }
+}
+
+fn parse(bytes: &[u8]) -> Parsed {
+    match bytes {
+        []                      => Parsed::Incomplete,
+        [0x1B]                  => Parsed::Incomplete,
+        [0x1B, 0x1B, b, ..] if !matches!(b, b'[' | b'O') => Parsed::Key(1, Key { vk: VK_ESCAPE, ch: '\x1B', state: ControlKeyState::default() }),
+        [0x1B, b'[', rest @ ..] => parse_csi(rest).offset(2),
+        [0x1B, b'O']            => Parsed::Incomplete,
+        [0x1B, b'O', f, ..]     => match ss3_key(*f) {
+            Some(key)   => Parsed::Key(3, key),
+            None        => Parsed::Discard(3),
+        },
+        [0x1B, rest @ ..]       => match parse(rest) {
+            Parsed::Key(n, key) => Parsed::Key(n + 1, with_alt(key)),
+            other               => other.offset(1),
+        },
+        _ => match decode_utf8(bytes) {
+            None            => Parsed::Incomplete,
+            Some((n, ch))   => Parsed::Key(n, char_key(ch)),
+        },
+    }
+}
+
+impl Parsed {
+    fn offset(self, by: usize) -> Self {
+        match self {
+            Parsed::Key(n, key)         => Parsed::Key(n + by, key),
+            Parsed::Record(n, record)   => Parsed::Record(n + by, record),
+            Parsed::Discard(n)          => Parsed::Discard(n + by),
+            Parsed::Incomplete          => Parsed::Incomplete,
+        }
+    }
+}
+
+/// Parses the remainder of a CSI sequence (after `ESC [`.)
+fn parse_csi(bytes: &[u8]) -> Parsed {
+    let Some(end) = bytes.iter().position(|b| !(0x20 ..= 0x3F).contains(b)) else { return Parsed::Incomplete };
+    let consumed = end + 1;
+    let final_byte = bytes[end];
+    if !(0x40 ..= 0x7E).contains(&final_byte) { return Parsed::Discard(end) } // not a valid final byte: drop the prefix, reparse the rest
+
+    let body = &bytes[.. end];
+    if body.iter().any(|b| !matches!(b, b'0' ..= b'9' | b';')) { return Parsed::Discard(consumed) } // private markers / intermediates: not a key
+
+    let mut params = [0u32; 6];
+    let mut count = 0;
+    for (i, param) in body.split(|&b| b == b';').enumerate() {
+        if i >= params.len() { return Parsed::Discard(consumed) }
+        params[i] = param.iter().fold(0u32, |n, &d| n.saturating_mul(10).saturating_add(u32::from(d - b'0')));
+        count = i + 1;
+    }
+    if body.is_empty() { count = 0; }
+
+    let modifiers = if count >= 2 { modifier_state(params[1]) } else { ControlKeyState::default() };
+    let key = match final_byte {
+        b'_' => {
+            // win32-input-mode: ESC [ Vk ; Sc ; Uc ; Kd ; Cs ; Rc _
+            let [vk, sc, uc, kd, cs, rc] = params;
+            return Parsed::Record(consumed, KeyEventRecord {
+                key_down:           (kd != 0).into(),
+                repeat_count:       if count >= 6 { rc.min(0xFFFF) as u16 } else { 1 },
+                virtual_key_code:   (vk.min(0xFFFF) as u16).into(),
+                virtual_scan_code:  sc.min(0xFFFF) as u16,
+                char:               (uc.min(0xFFFF) as u16).into(),
+                control_key_state:  cs.into(),
+            });
+        },
+        b'Z' => Key { vk: VK_TAB, ch: '\t', state: SHIFT_PRESSED },
+        b'~' => {
+            let vk = match params[0] {
+                1 | 7   => VK_HOME,
+                2       => VK_INSERT,
+                3       => VK_DELETE,
+                4 | 8   => VK_END,
+                5       => VK_PRIOR,
+                6       => VK_NEXT,
+                11 ..= 14 => VirtualKey::from(u16::from(VK_F1) + (params[0] - 11) as u16),
+                n => match FUNCTION_KEY_CODES.iter().position(|&code| u32::from(code) == n) {
+                    Some(i) => VirtualKey::from(u16::from(VK_F5) + i as u16),
+                    None    => return Parsed::Discard(consumed),
+                },
+            };
+            let enhanced = if matches!(vk, VK_HOME | VK_INSERT | VK_DELETE | VK_END | VK_PRIOR | VK_NEXT) { ENHANCED_KEY } else { ControlKeyState::default() };
+            Key { vk, ch: '\0', state: enhanced | modifiers }
+        },
+        f => match ss3_key(f) {
+            Some(key)   => Key { state: key.state | modifiers, .. key },
+            None        => return Parsed::Discard(consumed),
+        },
+    };
+    Parsed::Key(consumed, key)
+}
+
+/// Keys which share a final byte between CSI and SS3 forms (`ESC[A` / `ESC OA`, `ESC[1;2P` / `ESC OP`, ...)
+fn ss3_key(final_byte: u8) -> Option<Key> {
+    let (vk, enhanced) = match final_byte {
+        b'A' => (VK_UP,     true),
+        b'B' => (VK_DOWN,   true),
+        b'C' => (VK_RIGHT,  true),
+        b'D' => (VK_LEFT,   true),
+        b'H' => (VK_HOME,   true),
+        b'F' => (VK_END,    true),
+        b'E' => (VK_CLEAR,  false),
+        b'P' ..= b'S' => (VirtualKey::from(u16::from(VK_F1) + u16::from(final_byte - b'P')), false),
+        _ => return None,
+    };
+    Some(Key { vk, ch: '\0', state: if enhanced { ENHANCED_KEY } else { ControlKeyState::default() } })
+}
+
+/// Converts an xterm modifier parameter (`1 + shift + 2*alt + 4*ctrl`) to a control key state.
+fn modifier_state(param: u32) -> ControlKeyState {
+    let bits = param.saturating_sub(1);
+    let mut state = ControlKeyState::default();
+    if bits & 1 != 0 { state |= SHIFT_PRESSED; }
+    if bits & 2 != 0 { state |= LEFT_ALT_PRESSED; }
+    if bits & 4 != 0 { state |= LEFT_CTRL_PRESSED; }
+    state
+}
+
+fn with_alt(key: Key) -> Key { Key { state: key.state | LEFT_ALT_PRESSED, .. key } }
+
+/// Maps a character - as typed on a US keyboard layout - to its key.
+fn char_key(ch: char) -> Key {
+    let none = ControlKeyState::default();
+    let key = |vk: VirtualKey, state: ControlKeyState| Key { vk, ch, state };
+    match ch {
+        '\0'                => key(VK_SPACE, LEFT_CTRL_PRESSED),
+        '\x08'              => key(VK_BACK, LEFT_CTRL_PRESSED),
+        '\t'                => key(VK_TAB, none),
+        '\n'                => key(VK_RETURN, LEFT_CTRL_PRESSED),
+        '\r'                => key(VK_RETURN, none),
+        '\x1B'              => key(VK_ESCAPE, none),
+        '\x01' ..= '\x1A'   => key(VirtualKey::from(0x40 + ch as u16), LEFT_CTRL_PRESSED), // Ctrl+A ..= Ctrl+Z
+        '\x1C'              => key(VK_OEM_5, LEFT_CTRL_PRESSED),
+        '\x1D'              => key(VK_OEM_6, LEFT_CTRL_PRESSED),
+        '\x1E'              => key(VK_6, LEFT_CTRL_PRESSED | SHIFT_PRESSED),
+        '\x1F'              => key(VK_OEM_MINUS, LEFT_CTRL_PRESSED | SHIFT_PRESSED),
+        '\x7F'              => Key { vk: VK_BACK, ch: '\x08', state: none },
+        ' '                 => key(VK_SPACE, none),
+        'a' ..= 'z'         => key(VirtualKey::from(ch.to_ascii_uppercase() as u16), none),
+        'A' ..= 'Z'         => key(VirtualKey::from(ch as u16), SHIFT_PRESSED),
+        '0' ..= '9'         => key(VirtualKey::from(ch as u16), none),
+        _ => {
+            const SHIFTED_DIGITS : &str = ")!@#$%^&*(";
+            if let Some(digit) = SHIFTED_DIGITS.find(ch) { return key(VirtualKey::from(u16::from(b'0') + digit as u16), SHIFT_PRESSED) }
+            let (vk, shift) = match ch {
+                ';' => (VK_OEM_1,      false), ':' => (VK_OEM_1,      true),
+                '=' => (VK_OEM_PLUS,   false), '+' => (VK_OEM_PLUS,   true),
+                ',' => (VK_OEM_COMMA,  false), '<' => (VK_OEM_COMMA,  true),
+                '-' => (VK_OEM_MINUS,  false), '_' => (VK_OEM_MINUS,  true),
+                '.' => (VK_OEM_PERIOD, false), '>' => (VK_OEM_PERIOD, true),
+                '/' => (VK_OEM_2,      false), '?' => (VK_OEM_2,      true),
+                '`' => (VK_OEM_3,      false), '~' => (VK_OEM_3,      true),
+                '[' => (VK_OEM_4,      false), '{' => (VK_OEM_4,      true),
+                '\\'=> (VK_OEM_5,      false), '|' => (VK_OEM_5,      true),
+                ']' => (VK_OEM_6,      false), '}' => (VK_OEM_6,      true),
+                '\''=> (VK_OEM_7,      false), '"' => (VK_OEM_7,      true),
+                _   => (VK_PACKET,     false),
+            };
+            key(vk, if shift { SHIFT_PRESSED } else { none })
+        },
+    }
+}
+
+/// Decodes the first UTF-8 character of `bytes`, returning the number of bytes consumed.  Returns [`None`] if `bytes` ends mid-character.
+fn decode_utf8(bytes: &[u8]) -> Option<(usize, char)> {
+    let need = match bytes[0] {
+        0x00 ..= 0x7F   => return Some((1, char::from(bytes[0]))),
+        0xC2 ..= 0xDF   => 2,
+        0xE0 ..= 0xEF   => 3,
+        0xF0 ..= 0xF4   => 4,
+        _               => return Some((1, char::REPLACEMENT_CHARACTER)),
+    };
+    for i in 1 .. need {
+        match bytes.get(i) {
+            None                            => return None,
+            Some(b) if b & 0xC0 == 0x80     => {},
+            Some(_)                         => return Some((i, char::REPLACEMENT_CHARACTER)),
+        }
+    }
+    let ch = core::str::from_utf8(&bytes[.. need]).ok().and_then(|s| s.chars().next()).unwrap_or(char::REPLACEMENT_CHARACTER);
+    Some((need, ch))
+}
+
+fn emit(key: Key, on_record: &mut impl FnMut(InputRecord)) {
+    let mut units = [0u16; 2];
+    let units = key.ch.encode_utf16(&mut units);
+    for &unit in units.iter() {
+        let record = KeyEventRecord {
+            key_down:           true.into(),
+            repeat_count:       1,
+            virtual_key_code:   key.vk,
+            virtual_scan_code:  0,
+            char:               unit.into(),
+            control_key_state:  key.state,
+        };
+        emit_record(record, on_record);
+        on_record(KeyEventRecord { key_down: false.into(), .. record }.into());
+    }
+}
+
+fn emit_record(record: KeyEventRecord, on_record: &mut impl FnMut(InputRecord)) {
+    on_record(record.into());
+}
+
+
+
+#[test] fn round_trip() {
+    let options = VtInputOptions::default();
+    let none = ControlKeyState::default();
+    let keys : &[(VirtualKey, u16, ControlKeyState)] = &[
+        (VK_A,      b'a' as u16,    none),
+        (VK_A,      b'A' as u16,    SHIFT_PRESSED),
+        (VK_A,      b'a' as u16,    LEFT_ALT_PRESSED),
+        (VK_A,      0x01,           LEFT_CTRL_PRESSED),
+        (VK_UP,     0,              ENHANCED_KEY),
+        (VK_RIGHT,  0,              ENHANCED_KEY | LEFT_CTRL_PRESSED | SHIFT_PRESSED),
+        (VK_DELETE, 0,              ENHANCED_KEY | LEFT_ALT_PRESSED),
+        (VK_F1,     0,              none),
+        (VK_F12,    0,              LEFT_CTRL_PRESSED),
+        (VK_TAB,    b'\t' as u16,   SHIFT_PRESSED),
+        (VK_BACK,   0x08,           none),
+        (VK_RETURN, b'\r' as u16,   none),
+        (VK_ESCAPE, 0x1B,           none),
+        (VK_ESCAPE, 0x1B,           LEFT_ALT_PRESSED),
+        (VK_OEM_2,  b'?' as u16,    SHIFT_PRESSED),
+        (VK_PACKET, 0xE9,           none), // é
+    ];
+
+    for &(vk, ch, state) in keys {
+        let key = KeyEventRecord { key_down: true.into(), repeat_count: 1, virtual_key_code: vk, virtual_scan_code: 0, char: ch.into(), control_key_state: state };
+        let bytes = key.to_vt_input(options);
+        let mut decoded = Vec::new();
+        let mut decoder = VtInputDecoder::new();
+        decoder.decode(&bytes, |record| decoded.push(*<&KeyEventRecord>::try_from(&record).unwrap()));
+        decoder.flush(|record| decoded.push(*<&KeyEventRecord>::try_from(&record).unwrap()));
+        assert_eq!(decoded.len(), 2, "{bytes:?}");
+        let down = decoded[0];
+        assert_eq!((down.virtual_key_code, down.control_key_state), (vk, state), "{bytes:?}");
+        if vk != VK_TAB { assert_eq!(down.char.unicode_char(), ch, "{bytes:?}"); }
+    }
+}
+
+#[test] fn sequences() {
+    let none = ControlKeyState::default();
+    let decode = |bytes: &[u8]| {
+        let mut decoder = VtInputDecoder::new();
+        let mut keys = Vec::new();
+        let mut on_record = |record: InputRecord| {
+            let key = <&KeyEventRecord>::try_from(&record).unwrap();
+            if bool::from(key.key_down) { keys.push((key.virtual_key_code, key.char.unicode_char(), key.control_key_state)) }
+        };
+        for &b in bytes { decoder.decode(&[b], &mut on_record); } // byte at a time
+        decoder.flush(&mut on_record);
+        keys
+    };
+
+    assert_eq!(decode(b"\x1BOA\x1B[1;3H\x1B[24~"), [(VK_UP, 0, ENHANCED_KEY), (VK_HOME, 0, ENHANCED_KEY | LEFT_ALT_PRESSED), (VK_F12, 0, none)]);
+    assert_eq!(decode(b"\x1B[?1;2c!"),      [(VK_1, u16::from(b'!'), SHIFT_PRESSED)]); // unrelated report discarded
+    assert_eq!(decode(b"\x1B\x1B"),         [(VK_ESCAPE, 0x1B, LEFT_ALT_PRESSED)]);
+    assert_eq!(decode(b"\x1B\x1B\x1B"),     [(VK_ESCAPE, 0x1B, none), (VK_ESCAPE, 0x1B, LEFT_ALT_PRESSED)]);
+    assert_eq!(decode(b"\x1B["),            [(VK_OEM_4, u16::from(b'['), LEFT_ALT_PRESSED)]);
+    assert_eq!(decode(b"\x1B\x1Bx"),        [(VK_ESCAPE, 0x1B, none), (VK_X, u16::from(b'x'), LEFT_ALT_PRESSED)]);
+    assert_eq!(decode(b"\x1B\x1B[A"),       [(VK_UP, 0, ENHANCED_KEY | LEFT_ALT_PRESSED)]);
+    assert_eq!(decode(b"\xF0\x9F\xA6\x80"), [(VK_PACKET, 0xD83E, none), (VK_PACKET, 0xDD80, none)]);
+    assert_eq!(decode(b"\xC3"),             [(VK_PACKET, 0xFFFD, none)]);
+    assert_eq!(decode(b"\x1B[65;30;97;1;16;1_"), [(VK_A, u16::from(b'a'), SHIFT_PRESSED)]);
+}
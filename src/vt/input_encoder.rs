@@ -0,0 +1,191 @@
+use crate::*;
+use super::keys::*;
+
+
+
+/// \[~~microsoft.com~~\]
+/// Terminal modes which affect how [`KeyEventRecord::to_vt_input`] encodes keys.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VtInputOptions {
+    /// DECCKM (`ESC[?1h`): unmodified cursor keys, Home, and End are sent as `ESC O A` instead of `ESC [ A`.
+    pub application_cursor_keys: bool,
+}
+
+impl KeyEventRecord {
+    #[doc(alias = "ENABLE_VIRTUAL_TERMINAL_INPUT")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/console-virtual-terminal-sequences#input-sequences)\]
+    /// Encodes `self` as the UTF-8 bytes a console with [`ENABLE_VIRTUAL_TERMINAL_INPUT`] would deliver for it.
+    ///
+    /// *   Key up events, and keys without a character or sequence (e.g. <kbd>Shift</kbd>), encode to nothing.
+    /// *   Cursor, editing, and function keys use xterm's sequences, with a modifier parameter when <kbd>Shift</kbd>, <kbd>Alt</kbd>, or <kbd>Ctrl</kbd> are held (e.g. `ESC[1;5C` for <kbd>Ctrl</kbd>+<kbd>→</kbd>.)
+    /// *   <kbd>Backspace</kbd> sends DEL (`0x7F`), or BS (`0x08`) with <kbd>Ctrl</kbd>.  <kbd>Shift</kbd>+<kbd>Tab</kbd> sends `ESC[Z`.  <kbd>Ctrl</kbd>+<kbd>Space</kbd> sends NUL.
+    /// *   Other keys send `char`, prefixed with `ESC` if <kbd>Alt</kbd> is held (but not for AltGr, which Windows reports as <kbd>Ctrl</kbd>+<kbd>Alt</kbd>.)
+    ///     <kbd>Alt</kbd>+<kbd>Esc</kbd> is therefore `ESC ESC`, which [`VtInputDecoder`] decodes as such when no more input follows before it's flushed.
+    ///     Followed by e.g. <kbd>X</kbd>, `ESC ESC x` is ambiguous, and decodes as <kbd>Esc</kbd>, <kbd>Alt</kbd>+<kbd>X</kbd>.
+    /// *   The encoding is repeated `repeat_count` times.
+    ///
+    /// A `char` that is half of a UTF-16 surrogate pair can't be encoded alone, and encodes as U+FFFD.  Use [`encode_vt_input`] to combine pairs.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let key = |vk: VirtualKey, ch: u8, control_key_state: u32| KeyEventRecord {
+    ///     key_down: true.into(), repeat_count: 1, virtual_key_code: vk, virtual_scan_code: 0, char: u16::from(ch).into(), control_key_state: control_key_state.into(),
+    /// };
+    /// let options = VtInputOptions::default();
+    /// assert_eq!(key(VK_A,     b'a', 0x00).to_vt_input(options), b"a");      // A
+    /// assert_eq!(key(VK_A,     b'a', 0x02).to_vt_input(options), b"\x1Ba");   // Alt+A
+    /// assert_eq!(key(VK_A,     0x01, 0x08).to_vt_input(options), b"\x01");    // Ctrl+A
+    /// assert_eq!(key(VK_UP,       0, 0x00).to_vt_input(options), b"\x1B[A");  // Up
+    /// assert_eq!(key(VK_RIGHT,    0, 0x08).to_vt_input(options), b"\x1B[1;5C"); // Ctrl+Right
+    /// assert_eq!(key(VK_F5,       0, 0x10).to_vt_input(options), b"\x1B[15;2~"); // Shift+F5
+    /// assert_eq!(key(VK_UP,       0, 0x00).to_vt_input(VtInputOptions { application_cursor_keys: true }), b"\x1BOA");
+    /// ```
+    ///
+    pub fn to_vt_input(&self, options: VtInputOptions) -> Vec<u8> {
+        let mut out = Vec::new();
+        let ch = self.char.unicode_char();
+        let ch = if (0xD800 ..= 0xDFFF).contains(&ch) { Some(char::REPLACEMENT_CHARACTER) } else { char::from_u32(ch.into()) };
+        encode_key(self, ch, options, &mut out);
+        out
+    }
+}
+
+#[doc(alias = "ENABLE_VIRTUAL_TERMINAL_INPUT")]
+/// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/console-virtual-terminal-sequences#input-sequences)\]
+/// Encodes the key events of `records` as the UTF-8 bytes a console with [`ENABLE_VIRTUAL_TERMINAL_INPUT`] would deliver for them.
+///
+/// Like [`KeyEventRecord::to_vt_input`], but UTF-16 surrogate pairs split across consecutive key down events are combined.
+/// Non-key records are skipped.
+///
+/// ### Example
+/// ```
+/// # use maulingmonkey_console_winapi_wrappers::*;
+/// let units : Vec<u16> = "🦀!".encode_utf16().collect();
+/// let records : Vec<InputRecord> = units.iter().map(|&unit| KeyEventRecord {
+///     key_down: true.into(), repeat_count: 1, virtual_key_code: VK_PACKET,
+///     virtual_scan_code: 0, char: unit.into(), control_key_state: Default::default(),
+/// }.into()).collect();
+/// assert_eq!(encode_vt_input(&records, VtInputOptions::default()), "🦀!".as_bytes());
+/// ```
+///
+pub fn encode_vt_input<'r>(records: impl IntoIterator<Item = &'r InputRecord>, options: VtInputOptions) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut high_surrogate = None;
+    for record in records {
+        let Ok(key) = <&KeyEventRecord>::try_from(record) else { continue };
+        if !bool::from(key.key_down) { continue }
+
+        let unit = key.char.unicode_char();
+        let ch = match (high_surrogate.take(), unit) {
+            (None, 0xD800 ..= 0xDBFF) => { high_surrogate = Some(unit); continue },
+            (Some(high), 0xDC00 ..= 0xDFFF) => char::decode_utf16([high, unit]).next().and_then(Result::ok),
+            (Some(_), _) => {
+                out.extend_from_slice("\u{FFFD}".as_bytes());
+                if (0xD800 ..= 0xDBFF).contains(&unit) { high_surrogate = Some(unit); continue }
+                char::from_u32(unit.into()).or(Some(char::REPLACEMENT_CHARACTER))
+            },
+            (None, _) => char::from_u32(unit.into()).or(Some(char::REPLACEMENT_CHARACTER)),
+        };
+        encode_key(key, ch, options, &mut out);
+    }
+    if high_surrogate.is_some() { out.extend_from_slice("\u{FFFD}".as_bytes()); }
+    out
+}
+
+fn encode_key(key: &KeyEventRecord, ch: Option<char>, options: VtInputOptions, out: &mut Vec<u8>) {
+    if !bool::from(key.key_down) { return }
+
+    let state   = key.control_key_state;
+    let shift   = state.shift();
+    let alt     = state.alt();
+    let ctrl    = state.ctrl();
+    let modifiers = 1 + u8::from(shift) + 2 * u8::from(alt) + 4 * u8::from(ctrl);
+    let ch = ch.filter(|&ch| ch != '\0');
+
+    let start = out.len();
+    match key.virtual_key_code {
+        VK_UP | VK_DOWN | VK_RIGHT | VK_LEFT | VK_HOME | VK_END | VK_CLEAR => {
+            let final_byte = match key.virtual_key_code { VK_UP => b'A', VK_DOWN => b'B', VK_RIGHT => b'C', VK_LEFT => b'D', VK_HOME => b'H', VK_END => b'F', _ => b'E' };
+            if modifiers != 1 {
+                out.extend_from_slice(format!("\x1B[1;{modifiers}").as_bytes());
+                out.push(final_byte);
+            } else if options.application_cursor_keys {
+                out.extend_from_slice(&[0x1B, b'O', final_byte]);
+            } else {
+                out.extend_from_slice(&[0x1B, b'[', final_byte]);
+            }
+        },
+        VK_F1 | VK_F2 | VK_F3 | VK_F4 => {
+            let final_byte = b'P' + (u16::from(key.virtual_key_code) - u16::from(VK_F1)) as u8;
+            if modifiers != 1 {
+                out.extend_from_slice(format!("\x1B[1;{modifiers}").as_bytes());
+                out.push(final_byte);
+            } else {
+                out.extend_from_slice(&[0x1B, b'O', final_byte]);
+            }
+        },
+        vk if matches!(vk, VK_INSERT | VK_DELETE | VK_PRIOR | VK_NEXT) || (VK_F5 ..= VK_F20).contains(&vk) => {
+            let n = match vk {
+                VK_INSERT => 2, VK_DELETE => 3, VK_PRIOR => 5, VK_NEXT => 6,
+                vk => FUNCTION_KEY_CODES[usize::from(u16::from(vk) - u16::from(VK_F5))],
+            };
+            if modifiers != 1 {
+                out.extend_from_slice(format!("\x1B[{n};{modifiers}~").as_bytes());
+            } else {
+                out.extend_from_slice(format!("\x1B[{n}~").as_bytes());
+            }
+        },
+        VK_BACK => {
+            if alt { out.push(0x1B); }
+            out.push(if ctrl { 0x08 } else { 0x7F });
+        },
+        VK_TAB if shift => out.extend_from_slice(b"\x1B[Z"),
+        VK_SPACE if ctrl && !alt => out.push(0x00),
+        VK_RETURN if ch.is_none() => {
+            if alt { out.push(0x1B); }
+            out.push(b'\r');
+        },
+        VK_ESCAPE if ch.is_none() => {
+            if alt { out.push(0x1B); }
+            out.push(0x1B);
+        },
+        _ => {
+            let Some(ch) = ch else { return };
+            let altgr = ctrl && alt && !ch.is_control();
+            if alt && !altgr { out.push(0x1B); }
+            let mut utf8 = [0u8; 4];
+            out.extend_from_slice(ch.encode_utf8(&mut utf8).as_bytes());
+        },
+    }
+
+    let single = out.len() - start;
+    for _ in 1 .. key.repeat_count {
+        out.extend_from_within(start .. start + single);
+    }
+}
+
+
+
+#[test] fn editing_keys() {
+    let key = |vk: VirtualKey, ch: u16, control_key_state: u32| KeyEventRecord {
+        key_down: true.into(), repeat_count: 1, virtual_key_code: vk, virtual_scan_code: 0, char: ch.into(), control_key_state: control_key_state.into(),
+    };
+    let options = VtInputOptions::default();
+    assert_eq!(key(VK_BACK,   0x08, 0x00).to_vt_input(options), b"\x7F");
+    assert_eq!(key(VK_BACK,   0x7F, 0x08).to_vt_input(options), b"\x08");
+    assert_eq!(key(VK_BACK,   0x08, 0x02).to_vt_input(options), b"\x1B\x7F");
+    assert_eq!(key(VK_TAB,    0x09, 0x10).to_vt_input(options), b"\x1B[Z");
+    assert_eq!(key(VK_RETURN, 0x0D, 0x00).to_vt_input(options), b"\r");
+    assert_eq!(key(VK_ESCAPE, 0x1B, 0x00).to_vt_input(options), b"\x1B");
+    assert_eq!(key(VK_SPACE,  0x20, 0x08).to_vt_input(options), b"\0");
+    assert_eq!(key(VK_DELETE, 0x00, 0x00).to_vt_input(options), b"\x1B[3~");
+    assert_eq!(key(VK_F1,     0x00, 0x00).to_vt_input(options), b"\x1BOP");
+    assert_eq!(key(VK_F1,     0x00, 0x0A).to_vt_input(options), b"\x1B[1;7P");
+    assert_eq!(key(VK_F12,    0x00, 0x00).to_vt_input(options), b"\x1B[24~");
+    assert_eq!(key(VK_Q,      0x40, 0x09).to_vt_input(options), b"@"); // AltGr+Q on a German layout
+    assert_eq!(key(VK_SHIFT,  0x00, 0x10).to_vt_input(options), b""); // Shift
+    assert_eq!(KeyEventRecord { repeat_count: 3, .. key(VK_A, 0x61, 0) }.to_vt_input(options), b"aaa");
+    assert_eq!(KeyEventRecord { key_down: false.into(), .. key(VK_A, 0x61, 0) }.to_vt_input(options), b"");
+}
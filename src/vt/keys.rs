@@ -0,0 +1,4 @@
+//! Key encodings shared by the VT input encoder and decoder.
+
+/// `n` of `ESC[n~` for F5 ..= F20
+pub(super) const FUNCTION_KEY_CODES : [u16; 16] = [15, 17, 18, 19, 20, 21, 23, 24, 25, 26, 28, 29, 31, 32, 33, 34];
@@ -0,0 +1,518 @@
+use core::fmt::{self, Debug, Formatter};
+
+
+
+/// The maximum number of parameters [`VtParser`] records for a single CSI or DCS sequence.  Additional parameters are dropped.
+pub const VT_MAX_PARAMS : usize = 16;
+
+/// The maximum number of intermediate bytes [`VtParser`] records for a single sequence.  Sequences with more are ignored.
+pub const VT_MAX_INTERMEDIATES : usize = 2;
+
+
+
+/// \[[vt100.net](https://vt100.net/emu/dec_ansi_parser)\]
+/// A streaming, allocation-free parser for virtual terminal output sequences, as written by [`write_console`](crate::ConsoleBackend::write_console) with
+/// [`ENABLE_VIRTUAL_TERMINAL_PROCESSING`](crate::ENABLE_VIRTUAL_TERMINAL_PROCESSING).
+///
+/// This is Paul Williams' DEC ANSI parser state machine, operating on decoded [`char`]s rather than bytes:
+/// *   [`advance`](Self::advance) decodes UTF-8 (invalid sequences become U+FFFD)
+/// *   [`advance_utf16`](Self::advance_utf16) decodes UTF-16 (unpaired surrogates become U+FFFD)
+/// *   [`advance_char`](Self::advance_char) takes pre-decoded characters
+///
+/// Partial sequences - including partial UTF-8 / UTF-16 characters - are retained between calls, so input can be split arbitrarily.
+///
+/// Deviations from the original state machine:
+/// *   `:` is accepted as a parameter separator, marking the following parameter as a sub-parameter (e.g. `ESC[38:2::255:0:0m`.)
+/// *   BEL terminates OSC strings, as it does for xterm and conhost.
+/// *   C1 controls are only recognized as [`char`]s (U+0080 ..= U+009F), never as raw 8-bit bytes, as those aren't valid UTF-8.
+/// *   DEL is ignored in all states.
+/// *   Non-ASCII characters are ignored in all states except ground (printed) and strings (passed through.)
+///
+/// ### Example
+/// ```
+/// # use maulingmonkey_console_winapi_wrappers::*;
+/// let mut parser = VtParser::new();
+/// let mut log = Vec::new();
+/// parser.advance(b"\x1B[1;31mHi\x1B[0m\r\n", |action| log.push(format!("{action:?}")));
+/// assert_eq!(log, [
+///     "CsiDispatch { params: [1, 31], intermediates: [], final_byte: 'm' }",
+///     "Print('H')",
+///     "Print('i')",
+///     "CsiDispatch { params: [0], intermediates: [], final_byte: 'm' }",
+///     "Execute(13)",
+///     "Execute(10)",
+/// ]);
+/// ```
+///
+#[derive(Clone)]
+pub struct VtParser {
+    state:                     State,
+    params:                    [u16; VT_MAX_PARAMS],
+    param_count:               usize,
+    subparams:                 u16,
+    param_started:             bool,
+    intermediates:             [u8; VT_MAX_INTERMEDIATES],
+    intermediate_count:        usize,
+    intermediate_overflow:     bool,
+    utf8:                      [u8; 4],
+    utf8_len:                  u8,
+    utf8_need:                 u8,
+    high_surrogate:            Option<u16>,
+}
+
+/// An action emitted by [`VtParser`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VtAction<'p> {
+    /// A printable character should be displayed.
+    Print(char),
+
+    /// A C0 or C1 control function (e.g. `\r`, `\n`, `\x07`) should be executed.  `.0` is the control code (`0x00 ..= 0x1F`, `0x7F`, or `0x80 ..= 0x9F`.)
+    Execute(u8),
+
+    /// An escape sequence (`ESC` intermediates final_byte) was completed.
+    EscDispatch {
+        /// The intermediate bytes (`0x20 ..= 0x2F`, e.g. the `(` of `ESC(B`) between `ESC` and `final_byte`, in order.  Empty for plain escapes such as `ESC7`.
+        /// At most [`VT_MAX_INTERMEDIATES`] long: sequences with more are ignored rather than dispatched.
+        intermediates: &'p [u8],
+        /// The byte which ended the sequence (`0x30 ..= 0x7E`, e.g. the `B` of `ESC(B`.)
+        final_byte: char,
+    },
+
+    /// A control sequence (`CSI` params intermediates final_byte) was completed.
+    CsiDispatch {
+        /// The numeric parameters (e.g. `[1, 31]` for `ESC[1;31m`.)  Empty for `ESC[m`.
+        params: VtParams<'p>,
+        /// The private marker (`0x3C ..= 0x3F`, e.g. the `?` of `ESC[?25h`), if any, followed by the intermediate bytes (`0x20 ..= 0x2F`, e.g. the space of `ESC[2 q`.)
+        /// Empty for plain CSI sequences such as `ESC[1;31m`.  At most [`VT_MAX_INTERMEDIATES`] long: sequences with more are ignored rather than dispatched.
+        intermediates: &'p [u8],
+        /// The byte which ended the sequence and selects the control function (`0x40 ..= 0x7E`, e.g. the `m` of `ESC[1;31m`.)
+        final_byte: char,
+    },
+
+    /// An operating system command (`OSC`) string has started.
+    OscStart,
+
+    /// A character of the current OSC string.
+    OscPut(char),
+
+    /// The current OSC string was terminated (by `BEL`, `ST`, or being cancelled.)
+    OscEnd,
+
+    /// A device control string (`DCS` params intermediates final_byte) has started.  Its data follows as [`DcsPut`](Self::DcsPut)s.
+    DcsHook {
+        /// The numeric parameters, as for [`CsiDispatch`](Self::CsiDispatch).
+        params: VtParams<'p>,
+        /// The private marker (if any) and intermediate bytes (e.g. the `$` of `DCS $q`), as for [`CsiDispatch`](Self::CsiDispatch).
+        intermediates: &'p [u8],
+        /// The byte which ended the header and selects the control string (`0x40 ..= 0x7E`, e.g. the `q` of `DCS $q`.)
+        final_byte: char,
+    },
+
+    /// A character of the current DCS data string.
+    DcsPut(char),
+
+    /// The current DCS string was terminated.
+    DcsUnhook,
+}
+
+/// The numeric parameters of a CSI or DCS sequence.  Omitted parameters are `0`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct VtParams<'p> {
+    values:     &'p [u16],
+    subparams:  u16,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    Ground,
+    Escape,
+    EscapeIntermediate,
+    CsiEntry,
+    CsiParam,
+    CsiIntermediate,
+    CsiIgnore,
+    DcsEntry,
+    DcsParam,
+    DcsIntermediate,
+    DcsPassthrough,
+    DcsIgnore,
+    OscString,
+    SosPmApcString,
+}
+
+impl Default for VtParser { fn default() -> Self { Self::new() } }
+
+impl Debug for VtParser {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VtParser").field("state", &self.state).finish_non_exhaustive()
+    }
+}
+
+impl VtParser {
+    /// Creates a parser in the ground state.
+    pub const fn new() -> Self {
+        Self {
+            state:                  State::Ground,
+            params:                 [0; VT_MAX_PARAMS],
+            param_count:            0,
+            subparams:              0,
+            param_started:          false,
+            intermediates:          [0; VT_MAX_INTERMEDIATES],
+            intermediate_count:     0,
+            intermediate_overflow:  false,
+            utf8:                   [0; 4],
+            utf8_len:               0,
+            utf8_need:              0,
+            high_surrogate:         None,
+        }
+    }
+
+    /// Returns `true` if the parser is in the ground state with no partially decoded characters - i.e. not in the middle of any sequence.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let mut parser = VtParser::new();
+    /// parser.advance(b"\x1B[3", |_| {});
+    /// assert!(!parser.is_ground());
+    /// parser.advance(b"1m", |_| {});
+    /// assert!(parser.is_ground());
+    /// ```
+    ///
+    pub fn is_ground(&self) -> bool {
+        self.state == State::Ground && self.utf8_need == 0 && self.high_surrogate.is_none()
+    }
+
+    /// Feeds UTF-8 encoded `bytes` through the parser, invoking `on_action` for each resulting [`VtAction`].
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let mut parser = VtParser::new();
+    /// let mut text = String::new();
+    /// for chunk in [&b"caf\xC3"[..], b"\xA9\x1B]0;title\x07!"] {
+    ///     parser.advance(chunk, |action| if let VtAction::Print(ch) = action { text.push(ch) });
+    /// }
+    /// assert_eq!(text, "café!");
+    /// ```
+    ///
+    pub fn advance(&mut self, bytes: &[u8], mut on_action: impl FnMut(VtAction<'_>)) {
+        for &b in bytes {
+            if self.utf8_need > 0 {
+                if b & 0xC0 == 0x80 {
+                    self.utf8[usize::from(self.utf8_len)] = b;
+                    self.utf8_len += 1;
+                    if self.utf8_len == self.utf8_need {
+                        let ch = core::str::from_utf8(&self.utf8[.. usize::from(self.utf8_len)]).ok().and_then(|s| s.chars().next()).unwrap_or(char::REPLACEMENT_CHARACTER);
+                        self.utf8_need = 0;
+                        self.utf8_len  = 0;
+                        self.advance_char(ch, &mut on_action);
+                    }
+                    continue;
+                }
+                self.utf8_need = 0;
+                self.utf8_len  = 0;
+                self.advance_char(char::REPLACEMENT_CHARACTER, &mut on_action);
+            }
+
+            let need = match b {
+                0x00 ..= 0x7F   => { self.advance_char(char::from(b), &mut on_action); continue },
+                0xC2 ..= 0xDF   => 2,
+                0xE0 ..= 0xEF   => 3,
+                0xF0 ..= 0xF4   => 4,
+                _               => { self.advance_char(char::REPLACEMENT_CHARACTER, &mut on_action); continue },
+            };
+            self.utf8[0]    = b;
+            self.utf8_len   = 1;
+            self.utf8_need  = need;
+        }
+    }
+
+    /// Feeds UTF-16 encoded `units` - as passed to [`write_console`](crate::ConsoleBackend::write_console) - through the parser, invoking `on_action` for each resulting [`VtAction`].
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let mut parser = VtParser::new();
+    /// let units : Vec<u16> = "\u{1B}[?25l🦀".encode_utf16().collect();
+    /// let mut actions = 0;
+    /// parser.advance_utf16(&units, |action| {
+    ///     actions += 1;
+    ///     match action {
+    ///         VtAction::CsiDispatch { params, intermediates, final_byte } => {
+    ///             assert_eq!(params.get(0), Some(25));
+    ///             assert_eq!(intermediates, b"?");
+    ///             assert_eq!(final_byte, 'l');
+    ///         },
+    ///         VtAction::Print(ch) => assert_eq!(ch, '🦀'),
+    ///         other => panic!("unexpected {other:?}"),
+    ///     }
+    /// });
+    /// assert_eq!(actions, 2);
+    /// ```
+    ///
+    pub fn advance_utf16(&mut self, units: &[u16], mut on_action: impl FnMut(VtAction<'_>)) {
+        for &unit in units {
+            if let Some(high) = self.high_surrogate.take() {
+                if (0xDC00 ..= 0xDFFF).contains(&unit) {
+                    let ch = 0x10000 + ((u32::from(high) - 0xD800) << 10) + (u32::from(unit) - 0xDC00);
+                    self.advance_char(char::from_u32(ch).unwrap_or(char::REPLACEMENT_CHARACTER), &mut on_action);
+                    continue;
+                }
+                self.advance_char(char::REPLACEMENT_CHARACTER, &mut on_action);
+            }
+
+            match unit {
+                0xD800 ..= 0xDBFF   => self.high_surrogate = Some(unit),
+                0xDC00 ..= 0xDFFF   => self.advance_char(char::REPLACEMENT_CHARACTER, &mut on_action),
+                _                   => self.advance_char(char::from_u32(unit.into()).unwrap_or(char::REPLACEMENT_CHARACTER), &mut on_action),
+            }
+        }
+    }
+
+    /// Feeds a single decoded `ch` through the parser, invoking `on_action` for each resulting [`VtAction`].
+    pub fn advance_char(&mut self, ch: char, mut on_action: impl FnMut(VtAction<'_>)) {
+        let c = ch as u32;
+
+        // "Anywhere" transitions
+        match c {
+            0x18 | 0x1A => { self.transition(State::Ground, &mut on_action); on_action(VtAction::Execute(c as u8)); return },
+            0x1B        => { self.transition(State::Escape, &mut on_action); return },
+            0x90        => { self.transition(State::DcsEntry, &mut on_action); return },
+            0x9B        => { self.transition(State::CsiEntry, &mut on_action); return },
+            0x9C        => { self.transition(State::Ground, &mut on_action); return },
+            0x9D        => { self.transition(State::OscString, &mut on_action); return },
+            0x98 | 0x9E | 0x9F => { self.transition(State::SosPmApcString, &mut on_action); return },
+            0x80 ..= 0x9F => { self.transition(State::Ground, &mut on_action); on_action(VtAction::Execute(c as u8)); return },
+            _ => {},
+        }
+
+        let c0 = c < 0x20;
+        match self.state {
+            State::Ground => match c {
+                _ if c0         => on_action(VtAction::Execute(c as u8)),
+                0x7F            => {},
+                _               => on_action(VtAction::Print(ch)),
+            },
+            State::Escape => match c {
+                _ if c0         => on_action(VtAction::Execute(c as u8)),
+                0x20 ..= 0x2F   => { self.collect(c); self.state = State::EscapeIntermediate },
+                0x50            => self.transition(State::DcsEntry, &mut on_action),
+                0x58 | 0x5E | 0x5F => self.transition(State::SosPmApcString, &mut on_action),
+                0x5B            => self.transition(State::CsiEntry, &mut on_action),
+                0x5D            => self.transition(State::OscString, &mut on_action),
+                0x30 ..= 0x7E   => { self.esc_dispatch(ch, &mut on_action); self.state = State::Ground },
+                _               => {},
+            },
+            State::EscapeIntermediate => match c {
+                _ if c0         => on_action(VtAction::Execute(c as u8)),
+                0x20 ..= 0x2F   => self.collect(c),
+                0x30 ..= 0x7E   => { self.esc_dispatch(ch, &mut on_action); self.state = State::Ground },
+                _               => {},
+            },
+            State::CsiEntry => match c {
+                _ if c0         => on_action(VtAction::Execute(c as u8)),
+                0x20 ..= 0x2F   => { self.collect(c); self.state = State::CsiIntermediate },
+                0x30 ..= 0x3B   => { self.param(c); self.state = State::CsiParam },
+                0x3C ..= 0x3F   => { self.collect(c); self.state = State::CsiParam },
+                0x40 ..= 0x7E   => { self.csi_dispatch(ch, &mut on_action); self.state = State::Ground },
+                _               => {},
+            },
+            State::CsiParam => match c {
+                _ if c0         => on_action(VtAction::Execute(c as u8)),
+                0x20 ..= 0x2F   => { self.collect(c); self.state = State::CsiIntermediate },
+                0x30 ..= 0x3B   => self.param(c),
+                0x3C ..= 0x3F   => self.state = State::CsiIgnore,
+                0x40 ..= 0x7E   => { self.csi_dispatch(ch, &mut on_action); self.state = State::Ground },
+                _               => {},
+            },
+            State::CsiIntermediate => match c {
+                _ if c0         => on_action(VtAction::Execute(c as u8)),
+                0x20 ..= 0x2F   => self.collect(c),
+                0x30 ..= 0x3F   => self.state = State::CsiIgnore,
+                0x40 ..= 0x7E   => { self.csi_dispatch(ch, &mut on_action); self.state = State::Ground },
+                _               => {},
+            },
+            State::CsiIgnore => match c {
+                _ if c0         => on_action(VtAction::Execute(c as u8)),
+                0x40 ..= 0x7E   => self.state = State::Ground,
+                _               => {},
+            },
+            State::DcsEntry => match c {
+                0x20 ..= 0x2F   => { self.collect(c); self.state = State::DcsIntermediate },
+                0x30 ..= 0x3B   => { self.param(c); self.state = State::DcsParam },
+                0x3C ..= 0x3F   => { self.collect(c); self.state = State::DcsParam },
+                0x40 ..= 0x7E   => self.hook(ch, &mut on_action),
+                _               => {},
+            },
+            State::DcsParam => match c {
+                0x20 ..= 0x2F   => { self.collect(c); self.state = State::DcsIntermediate },
+                0x30 ..= 0x3B   => self.param(c),
+                0x3C ..= 0x3F   => self.state = State::DcsIgnore,
+                0x40 ..= 0x7E   => self.hook(ch, &mut on_action),
+                _               => {},
+            },
+            State::DcsIntermediate => match c {
+                0x20 ..= 0x2F   => self.collect(c),
+                0x30 ..= 0x3F   => self.state = State::DcsIgnore,
+                0x40 ..= 0x7E   => self.hook(ch, &mut on_action),
+                _               => {},
+            },
+            State::DcsPassthrough => match c {
+                0x7F            => {},
+                _               => on_action(VtAction::DcsPut(ch)),
+            },
+            State::OscString => match c {
+                0x07            => self.transition(State::Ground, &mut on_action),
+                _ if c0         => {},
+                0x7F            => {},
+                _               => on_action(VtAction::OscPut(ch)),
+            },
+            State::DcsIgnore | State::SosPmApcString => {},
+        }
+    }
+
+    /// Leaves the current state (running its exit action), then enters `state` (running its entry action.)
+    fn transition(&mut self, state: State, on_action: &mut impl FnMut(VtAction<'_>)) {
+        match self.state {
+            State::OscString        => on_action(VtAction::OscEnd),
+            State::DcsPassthrough   => on_action(VtAction::DcsUnhook),
+            _                       => {},
+        }
+        self.state = state;
+        match state {
+            State::Escape | State::CsiEntry | State::DcsEntry => self.clear(),
+            State::OscString => on_action(VtAction::OscStart),
+            _ => {},
+        }
+    }
+
+    fn clear(&mut self) {
+        self.param_count            = 0;
+        self.subparams              = 0;
+        self.param_started          = false;
+        self.intermediate_count     = 0;
+        self.intermediate_overflow  = false;
+    }
+
+    fn collect(&mut self, c: u32) {
+        match self.intermediates.get_mut(self.intermediate_count) {
+            Some(slot)  => { *slot = c as u8; self.intermediate_count += 1 },
+            None        => self.intermediate_overflow = true,
+        }
+    }
+
+    fn param(&mut self, c: u32) {
+        if !self.param_started {
+            self.param_started = true;
+            self.push_param();
+        }
+        match c {
+            0x3A | 0x3B => {
+                if c == 0x3A && self.param_count < VT_MAX_PARAMS { self.subparams |= 1 << self.param_count; }
+                self.push_param();
+            },
+            _ => if let Some(p) = self.param_count.checked_sub(1).and_then(|i| self.params.get_mut(i)) {
+                *p = p.saturating_mul(10).saturating_add((c - 0x30) as u16);
+            },
+        }
+    }
+
+    /// Starts a new parameter.  `param_count` keeps counting past [`VT_MAX_PARAMS`] so digits of dropped parameters are dropped too.
+    fn push_param(&mut self) {
+        if let Some(p) = self.params.get_mut(self.param_count) { *p = 0; }
+        self.param_count = self.param_count.saturating_add(1);
+    }
+
+    fn params(&self) -> VtParams<'_> {
+        VtParams { values: &self.params[.. self.param_count.min(VT_MAX_PARAMS)], subparams: self.subparams }
+    }
+
+    fn esc_dispatch(&self, final_byte: char, on_action: &mut impl FnMut(VtAction<'_>)) {
+        if self.intermediate_overflow { return }
+        on_action(VtAction::EscDispatch { intermediates: &self.intermediates[.. self.intermediate_count], final_byte });
+    }
+
+    fn csi_dispatch(&self, final_byte: char, on_action: &mut impl FnMut(VtAction<'_>)) {
+        if self.intermediate_overflow { return }
+        on_action(VtAction::CsiDispatch { params: self.params(), intermediates: &self.intermediates[.. self.intermediate_count], final_byte });
+    }
+
+    fn hook(&mut self, final_byte: char, on_action: &mut impl FnMut(VtAction<'_>)) {
+        if self.intermediate_overflow { self.state = State::DcsIgnore; return }
+        self.state = State::DcsPassthrough;
+        on_action(VtAction::DcsHook { params: self.params(), intermediates: &self.intermediates[.. self.intermediate_count], final_byte });
+    }
+}
+
+impl<'p> VtParams<'p> {
+    /// The number of parameters.  `ESC[m` has `0` parameters, `ESC[;m` has `2`.
+    pub fn len(&self) -> usize { self.values.len() }
+
+    /// Returns `true` if there are no parameters.
+    pub fn is_empty(&self) -> bool { self.values.is_empty() }
+
+    /// Returns the `index`th parameter, or [`None`] if there are fewer parameters.
+    pub fn get(&self, index: usize) -> Option<u16> { self.values.get(index).copied() }
+
+    /// Returns `true` if the `index`th parameter was preceded by `:` rather than `;` - i.e. if it's a sub-parameter of the previous parameter.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let mut parser = VtParser::new();
+    /// parser.advance(b"\x1B[4:3;1m", |action| if let VtAction::CsiDispatch { params, .. } = action {
+    ///     assert_eq!(params.as_slice(), &[4, 3, 1]);
+    ///     assert_eq!(params.is_subparam(0), false);
+    ///     assert_eq!(params.is_subparam(1), true);
+    ///     assert_eq!(params.is_subparam(2), false);
+    /// });
+    /// ```
+    ///
+    pub fn is_subparam(&self, index: usize) -> bool { index < self.values.len() && (self.subparams >> index) & 1 != 0 }
+
+    /// All parameters, ignoring the distinction between `;` and `:` separators.
+    pub fn as_slice(&self) -> &'p [u16] { self.values }
+
+    /// Iterates over all parameters.
+    pub fn iter(&self) -> impl Iterator<Item = u16> + 'p { self.values.iter().copied() }
+}
+
+impl<'p> From<&'p [u16]> for VtParams<'p> { fn from(values: &'p [u16]) -> Self { Self { values, subparams: 0 } } }
+
+impl Debug for VtParams<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result { Debug::fmt(self.values, f) }
+}
+
+
+
+#[test] fn sequences() {
+    let parse = |input: &[u8]| {
+        let mut parser = VtParser::new();
+        let mut log = Vec::new();
+        parser.advance(input, |action| log.push(format!("{action:?}")));
+        log
+    };
+
+    assert_eq!(parse(b"\x1B]0;title\x1B\\"), [
+        "OscStart", "OscPut('0')", "OscPut(';')", "OscPut('t')", "OscPut('i')", "OscPut('t')", "OscPut('l')", "OscPut('e')", "OscEnd",
+        "EscDispatch { intermediates: [], final_byte: '\\\\' }",
+    ]);
+    assert_eq!(parse(b"\x1BP1$rx\x1B\\"), [
+        "DcsHook { params: [1], intermediates: [36], final_byte: 'r' }", "DcsPut('x')", "DcsUnhook",
+        "EscDispatch { intermediates: [], final_byte: '\\\\' }",
+    ]);
+    assert_eq!(parse(b"\x1B(0\x1B7"), [
+        "EscDispatch { intermediates: [40], final_byte: '0' }",
+        "EscDispatch { intermediates: [], final_byte: '7' }",
+    ]);
+    assert_eq!(parse(b"\x1B[;5\nH"),                ["Execute(10)", "CsiDispatch { params: [0, 5], intermediates: [], final_byte: 'H' }"]);
+    assert_eq!(parse(b"\x1B[99999C"),               ["CsiDispatch { params: [65535], intermediates: [], final_byte: 'C' }"]);
+    assert_eq!(parse(b"\x1B[1?h\x1B[ !!!p"),        Vec::<String>::new()); // malformed, too many intermediates
+    assert_eq!(parse(b"\x1B[31\x18m"),              ["Execute(24)", "Print('m')"]); // cancelled
+    assert_eq!(parse(b"\xC3("),                     ["Print('\u{FFFD}')", "Print('(')"]);
+    assert_eq!(parse("\u{9B}2J".as_bytes()),        ["CsiDispatch { params: [2], intermediates: [], final_byte: 'J' }"]);
+
+    let params = parse(b"\x1B[1;2;3;4;5;6;7;8;9;10;11;12;13;14;15;16;17;18m");
+    assert_eq!(params, ["CsiDispatch { params: [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16], intermediates: [], final_byte: 'm' }"]);
+}
@@ -0,0 +1,147 @@
+use crate::*;
+
+
+
+/// The [`Attributes`] bits representable by SGR: foreground and background colors, [`COMMON_LVB_REVERSE_VIDEO`], and [`COMMON_LVB_UNDERSCORE`].
+const SGR_BITS  : u16 = 0x00FF | 0x4000 | 0x8000;
+const FG_BITS   : u16 = 0x000F;
+const BG_BITS   : u16 = 0x00F0;
+
+/// Converts between console color bits (`1` = blue, `4` = red) and ANSI color indices (`1` = red, `4` = blue).  Self-inverse.
+const fn swap_red_blue(bits: u16) -> u16 { (bits & 0b010) | ((bits & 0b001) << 2) | ((bits & 0b100) >> 2) }
+
+impl Attributes {
+    #[doc(alias = "SGR")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/console-virtual-terminal-sequences#text-formatting)\]
+    /// Returns the `ESC[...m` (Select Graphic Rendition) sequence which, applied to any state, reproduces `self`'s colors, [`COMMON_LVB_REVERSE_VIDEO`], and [`COMMON_LVB_UNDERSCORE`].
+    ///
+    /// The sequence always starts with `0` (reset) and explicitly sets both colors.
+    /// Intensified colors use the bright color codes (`90..=97` / `100..=107`.)
+    /// Other bits (e.g. [`COMMON_LVB_GRID_HORIZONTAL`]) have no SGR equivalent and are ignored.
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// assert_eq!((FOREGROUND_RED | FOREGROUND_GREEN | FOREGROUND_BLUE).to_sgr(), "\x1B[0;37;40m");
+    /// assert_eq!((FOREGROUND_RED | FOREGROUND_INTENSITY | BACKGROUND_BLUE).to_sgr(), "\x1B[0;91;44m");
+    /// assert_eq!((FOREGROUND_GREEN | COMMON_LVB_REVERSE_VIDEO | COMMON_LVB_UNDERSCORE).to_sgr(), "\x1B[0;32;40;4;7m");
+    /// ```
+    ///
+    pub fn to_sgr(self) -> String {
+        let bits = u16::from(self);
+        let fg = swap_red_blue(bits & 0x7) + if bits & 0x08 != 0 {  90 } else { 30 };
+        let bg = swap_red_blue((bits >> 4) & 0x7) + if bits & 0x80 != 0 { 100 } else { 40 };
+        let mut sgr = format!("\x1B[0;{fg};{bg}");
+        if bits & u16::from(COMMON_LVB_UNDERSCORE  ) != 0 { sgr.push_str(";4"); }
+        if bits & u16::from(COMMON_LVB_REVERSE_VIDEO) != 0 { sgr.push_str(";7"); }
+        sgr.push('m');
+        sgr
+    }
+
+    #[doc(alias = "SGR")]
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/console-virtual-terminal-sequences#text-formatting)\]
+    /// Applies the parameters of an `ESC[...m` (Select Graphic Rendition) sequence to `self`, the same way conhost maps SGR onto legacy attributes.
+    ///
+    /// | Parameter             | Effect |
+    /// | --------------------- | ------ |
+    /// | (none), `0`           | Reset colors, reverse video, and underscore to `defaults`
+    /// | `1` / `22`            | Set / clear [`FOREGROUND_INTENSITY`]
+    /// | `4`, `21` / `24`      | Set / clear [`COMMON_LVB_UNDERSCORE`]
+    /// | `7` / `27`            | Set / clear [`COMMON_LVB_REVERSE_VIDEO`]
+    /// | `30..=37` / `40..=47` | Set foreground / background color, leaving intensity unchanged
+    /// | `90..=97` / `100..=107` | Set foreground / background color and intensity
+    /// | `39` / `49`           | Reset foreground / background color and intensity to `defaults`
+    /// | `38;5;n` / `48;5;n`   | Set foreground / background to one of the first 16 indexed colors.  Other indices are ignored.
+    /// | `38;2;r;g;b` / `48;2;r;g;b` | Ignored (no palette to match against)
+    ///
+    /// Unrecognized parameters are ignored, as are bits without SGR equivalents (e.g. [`COMMON_LVB_GRID_HORIZONTAL`].)
+    ///
+    /// ### Example
+    /// ```
+    /// # use maulingmonkey_console_winapi_wrappers::*;
+    /// let defaults = FOREGROUND_RED | FOREGROUND_GREEN | FOREGROUND_BLUE;
+    /// let mut attributes = defaults;
+    /// attributes.apply_sgr(defaults, &[1, 31, 44][..]);
+    /// assert_eq!(attributes, FOREGROUND_RED | FOREGROUND_INTENSITY | BACKGROUND_BLUE);
+    /// attributes.apply_sgr(defaults, &[39][..]);
+    /// assert_eq!(attributes, defaults | BACKGROUND_BLUE);
+    ///
+    /// // Parameters straight from the parser
+    /// let mut parser = VtParser::new();
+    /// parser.advance(b"\x1B[0;7m", |action| if let VtAction::CsiDispatch { params, intermediates: [], final_byte: 'm' } = action {
+    ///     attributes.apply_sgr(defaults, params);
+    /// });
+    /// assert_eq!(attributes, defaults | COMMON_LVB_REVERSE_VIDEO);
+    /// ```
+    ///
+    pub fn apply_sgr<'p>(&mut self, defaults: Attributes, params: impl Into<VtParams<'p>>) {
+        let params = params.into();
+        let defaults = u16::from(defaults);
+        let mut bits = u16::from(*self);
+        if params.is_empty() { bits = (bits & !SGR_BITS) | (defaults & SGR_BITS); }
+
+        let mut i = 0;
+        while let Some(p) = params.get(i) {
+            i += 1;
+            match p {
+                0               => bits = (bits & !SGR_BITS) | (defaults & SGR_BITS),
+                1               => bits |=  0x0008,
+                22              => bits &= !0x0008,
+                4 | 21          => bits |=  u16::from(COMMON_LVB_UNDERSCORE),
+                24              => bits &= !u16::from(COMMON_LVB_UNDERSCORE),
+                7               => bits |=  u16::from(COMMON_LVB_REVERSE_VIDEO),
+                27              => bits &= !u16::from(COMMON_LVB_REVERSE_VIDEO),
+                30  ..= 37      => bits = (bits & !0x0007) | swap_red_blue(p - 30),
+                40  ..= 47      => bits = (bits & !0x0070) | (swap_red_blue(p - 40) << 4),
+                90  ..= 97      => bits = (bits & !FG_BITS) | 0x0008 | swap_red_blue(p - 90),
+                100 ..= 107     => bits = (bits & !BG_BITS) | 0x0080 | (swap_red_blue(p - 100) << 4),
+                39              => bits = (bits & !FG_BITS) | (defaults & FG_BITS),
+                49              => bits = (bits & !BG_BITS) | (defaults & BG_BITS),
+                38 | 48         => {
+                    let shift = if p == 38 { 0 } else { 4 };
+                    let colon = params.is_subparam(i);
+                    let args : &[u16] = &params.as_slice()[i.min(params.len()) ..];
+                    let consumed = match args.first().copied() {
+                        Some(5) => {
+                            if let Some(&n @ 0 ..= 15) = args.get(1) {
+                                let color = swap_red_blue(n & 0x7) | if n >= 8 { 0x8 } else { 0 };
+                                bits = (bits & !(0xF << shift)) | (color << shift);
+                            }
+                            2
+                        },
+                        Some(2) => 4, // 38;2;r;g;b
+                        _       => 0,
+                    };
+                    if colon {
+                        // e.g. 38:2:colorspace:r:g:b - skip all sub-parameters, however many there are
+                        while params.is_subparam(i) { i += 1; }
+                    } else {
+                        i += consumed;
+                    }
+                },
+                _ => {},
+            }
+        }
+
+        *self = bits.into();
+    }
+}
+
+
+
+#[test] fn sgr_round_trip() {
+    let defaults = FOREGROUND_RED | FOREGROUND_GREEN | FOREGROUND_BLUE;
+    for bits in (0 ..= 0xFFu16).chain([0x40FF, 0x80FF, 0xC012]) {
+        let expected = Attributes::from(bits);
+        let sgr = expected.to_sgr();
+        let mut parser = VtParser::new();
+        let mut actual = COMMON_LVB_UNDERSCORE | BACKGROUND_INTENSITY;
+        parser.advance(sgr.as_bytes(), |action| if let VtAction::CsiDispatch { params, final_byte: 'm', .. } = action { actual.apply_sgr(defaults, params) });
+        assert_eq!(actual, expected, "{sgr:?}");
+    }
+
+    let mut parser = VtParser::new();
+    let mut actual = defaults;
+    parser.advance(b"\x1B[38;5;9;48:2::1:2:3;4m", |action| if let VtAction::CsiDispatch { params, final_byte: 'm', .. } = action { actual.apply_sgr(defaults, params) });
+    assert_eq!(actual, FOREGROUND_RED | FOREGROUND_INTENSITY | COMMON_LVB_UNDERSCORE);
+}
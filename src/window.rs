@@ -126,16 +126,20 @@ pub fn set_console_title(title: impl AsRef<OsStr>) -> io::Result<()> {
 /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/console/setconsolewindowinfo)\]
 /// Sets the current size and position of a console screen buffer's window.
 ///
+/// If `absolute`, `console_window` is the new (inclusive) window.
+/// Otherwise, its fields are deltas added to the current window's edges - build those with [`InclusiveRect::new`], as converting from a range would subtract 1 from `right` and `bottom`.
+///
 /// ### Example
 /// ```
 /// # use maulingmonkey_console_winapi_wrappers::*;
 /// # use std::io::{self, *};
 /// # let _ = (|| -> io::Result<()> {
-/// set_console_window_info(&mut stdout(), false, (0,0) .. (80,25))?;
+/// set_console_window_info(&mut stdout(), true, (0,0) .. (80,25))?;
+/// set_console_window_info(&mut stdout(), false, InclusiveRect::new(0, 1, 0, 1))?; // scroll down a line
 /// # Ok(())
 /// # })();
 /// ```
 ///
-pub fn set_console_window_info(console_output: &mut impl AsConsoleOutputHandle, absolute: bool, console_window: impl Into<SmallRect>) -> io::Result<()> {
+pub fn set_console_window_info(console_output: &mut impl AsConsoleOutputHandle, absolute: bool, console_window: impl Into<InclusiveRect>) -> io::Result<()> {
     succeeded_to_result(unsafe { SetConsoleWindowInfo(console_output.as_raw_handle().cast(), absolute.into(), &console_window.into().into()) })
 }